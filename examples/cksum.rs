@@ -1,20 +1,9 @@
-use std::fmt::Write;
-use std::path::{Path, PathBuf};
+use std::io::{self, Write};
 
 use anyhow::Result;
 use clap::{value_parser, Arg, ArgAction, Command, ValueEnum};
+use clap_digest::hash::{self, Input};
 use clap_digest::Digest;
-use digest::DynDigest;
-
-fn hash_path(
-    path: impl AsRef<Path>,
-    hasher: &mut dyn DynDigest,
-) -> Result<Box<[u8]>> {
-    let content = std::fs::read_to_string(path)?;
-    let bytes = content.as_bytes();
-    hasher.update(bytes);
-    Ok(hasher.finalize_reset())
-}
 
 fn main() -> Result<()> {
     let args = cli().get_matches();
@@ -25,26 +14,22 @@ fn main() -> Result<()> {
         }
     } else {
         let inputs = args
-            .get_many::<PathBuf>("input")
+            .get_many::<Input>("input")
             .expect("at least one input is required via clap");
 
         let digest = *args
             .get_one::<Digest>("digest")
             .expect("has default via clap");
 
-        let mut digest: Box<dyn DynDigest> = digest.into();
+        let formatter = clap_digest::arg::formatter(&args);
+        let mut stdout = io::stdout().lock();
 
         for input in inputs {
-            let hash = hash_path(input, digest.as_mut())?;
-
-            let hash = hash.iter().fold(String::new(), |mut output, b| {
-                // UNWRAP: safe to write! to String
-                write!(output, "{b:02x}").unwrap();
-                output
-            });
-
-            println!("{hash}  {}", input.display());
+            let hash = hash::hash_input(input, digest)?;
+            formatter.write_line(&mut stdout, digest, &hash, input)?;
         }
+
+        stdout.flush()?;
     }
 
     Ok(())
@@ -52,10 +37,10 @@ fn main() -> Result<()> {
 
 fn cli() -> Command {
     let input = Arg::new("input")
-        .help("input files")
+        .help("input files, `-` for standard input")
         .required_unless_present("list-digests")
         .action(ArgAction::Append)
-        .value_parser(value_parser!(PathBuf));
+        .value_parser(value_parser!(Input));
 
     Command::new("cksum")
         .arg(input)
@@ -63,7 +48,11 @@ fn cli() -> Command {
             clap_digest::arg::digest().required_unless_present("list-digests"),
         )
         .arg(clap_digest::arg::list_digests())
-        .about("simple cksum clone that hashes text files")
+        .arg(clap_digest::arg::tag())
+        .arg(clap_digest::arg::zero())
+        .arg(clap_digest::arg::binary())
+        .arg(clap_digest::arg::text())
+        .about("simple cksum clone that hashes files")
         .after_help(
             "try `cargo run --example cksum -- -d MD5 Cargo.toml | md5sum -c`",
         )
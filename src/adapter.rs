@@ -0,0 +1,546 @@
+//! Contains [`digest`] trait implementations for hash functions from crates
+//! that do not implement them.
+
+use std::fmt;
+
+use digest::core_api::BlockSizeUser;
+use digest::{
+    FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset,
+    Update,
+};
+
+/// Defines a hasher type over a state type, with the given output and block
+/// sizes. The state must implement [`Clone`].
+macro_rules! adapter {
+    (
+        $(#[$attr:meta])*
+        $name:ident($state:ty), $output_size:ty, $block_size:ty,
+        new: $new:expr,
+        update: |$update_state:ident, $data:ident| $update:expr,
+        finalize: |$finalize_state:ident, $out:ident| $finalize:expr $(,)?
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $name($state);
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self($new)
+            }
+        }
+
+        impl Update for $name {
+            fn update(&mut self, $data: &[u8]) {
+                let $update_state = &mut self.0;
+                $update;
+            }
+        }
+
+        impl OutputSizeUser for $name {
+            type OutputSize = $output_size;
+        }
+
+        impl BlockSizeUser for $name {
+            type BlockSize = $block_size;
+        }
+
+        impl FixedOutput for $name {
+            fn finalize_into(self, $out: &mut Output<Self>) {
+                let $finalize_state = self.0;
+                $finalize;
+            }
+        }
+
+        impl FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                std::mem::take(self).finalize_into(out);
+            }
+        }
+
+        impl Reset for $name {
+            fn reset(&mut self) {
+                *self = Self::default();
+            }
+        }
+
+        impl HashMarker for $name {}
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(concat!(stringify!($name), " { ... }"))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "adler")]
+adapter!(
+    /// Adler-32 as used by zlib.
+    Adler32(adler2::Adler32),
+    digest::consts::U4,
+    digest::consts::U64,
+    new: adler2::Adler32::new(),
+    update: |state, data| state.write_slice(data),
+    finalize: |state, out| out.copy_from_slice(&state.checksum().to_be_bytes()),
+);
+
+#[cfg(feature = "blake2")]
+adapter!(
+    /// Parallel BLAKE2 with 4 leaves of 512 bit output.
+    Blake2bp(blake2b_simd::blake2bp::State),
+    digest::consts::U64,
+    digest::consts::U128,
+    new: blake2b_simd::blake2bp::State::new(),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(state.finalize().as_bytes()),
+);
+
+#[cfg(feature = "blake2")]
+adapter!(
+    /// Parallel BLAKE2 with 8 leaves of 256 bit output.
+    Blake2sp(blake2s_simd::blake2sp::State),
+    digest::consts::U32,
+    digest::consts::U64,
+    new: blake2s_simd::blake2sp::State::new(),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(state.finalize().as_bytes()),
+);
+
+#[cfg(feature = "cityhash")]
+adapter!(
+    /// 64 bit City hash. The input is buffered, as City hash is not
+    /// incremental.
+    CityHash64(Vec<u8>),
+    digest::consts::U8,
+    digest::consts::U64,
+    new: Vec::new(),
+    update: |state, data| state.extend_from_slice(data),
+    finalize: |state, out| {
+        out.copy_from_slice(&cityhasher::hash::<u64>(state).to_be_bytes());
+    },
+);
+
+#[cfg(feature = "crc")]
+static CRC_32_ISO_HDLC: crc::Crc<u32> =
+    crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+
+#[cfg(feature = "crc")]
+adapter!(
+    /// CRC-32 as used by Ethernet, zlib and PNG.
+    Crc32(crc::Digest<'static, u32>),
+    digest::consts::U4,
+    digest::consts::U64,
+    new: CRC_32_ISO_HDLC.digest(),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(&state.finalize().to_be_bytes()),
+);
+
+#[cfg(feature = "crc")]
+static CRC_32_ISCSI: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+
+#[cfg(feature = "crc")]
+adapter!(
+    /// CRC-32C (Castagnoli) as used by iSCSI, ext4 and Btrfs.
+    Crc32c(crc::Digest<'static, u32>),
+    digest::consts::U4,
+    digest::consts::U64,
+    new: CRC_32_ISCSI.digest(),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(&state.finalize().to_be_bytes()),
+);
+
+#[cfg(feature = "crc")]
+static CRC_64_XZ: crc::Crc<u64> = crc::Crc::<u64>::new(&crc::CRC_64_XZ);
+
+#[cfg(feature = "crc")]
+adapter!(
+    /// CRC-64 as used by xz.
+    Crc64Xz(crc::Digest<'static, u64>),
+    digest::consts::U8,
+    digest::consts::U64,
+    new: CRC_64_XZ.digest(),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(&state.finalize().to_be_bytes()),
+);
+
+#[cfg(feature = "crc")]
+static CRC_64_ECMA_182: crc::Crc<u64> =
+    crc::Crc::<u64>::new(&crc::CRC_64_ECMA_182);
+
+#[cfg(feature = "crc")]
+adapter!(
+    /// CRC-64 as specified by ECMA-182.
+    Crc64Ecma182(crc::Digest<'static, u64>),
+    digest::consts::U8,
+    digest::consts::U64,
+    new: CRC_64_ECMA_182.digest(),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(&state.finalize().to_be_bytes()),
+);
+
+#[cfg(feature = "farmhash")]
+adapter!(
+    /// 64 bit Farm hash fingerprint. The input is buffered, as Farm hash is
+    /// not incremental.
+    FarmHash64(Vec<u8>),
+    digest::consts::U8,
+    digest::consts::U64,
+    new: Vec::new(),
+    update: |state, data| state.extend_from_slice(data),
+    finalize: |state, out| {
+        out.copy_from_slice(&farmhash::fingerprint64(&state).to_be_bytes());
+    },
+);
+
+#[cfg(feature = "fnv")]
+adapter!(
+    /// 32 bit FNV-1a.
+    Fnv1a32(u32),
+    digest::consts::U4,
+    digest::consts::U4,
+    new: 0x811c_9dc5,
+    update: |state, data| {
+        for &byte in data {
+            *state = (*state ^ u32::from(byte)).wrapping_mul(0x0100_0193);
+        }
+    },
+    finalize: |state, out| out.copy_from_slice(&state.to_be_bytes()),
+);
+
+#[cfg(feature = "fnv")]
+adapter!(
+    /// 64 bit FNV-1a.
+    Fnv1a64(u64),
+    digest::consts::U8,
+    digest::consts::U8,
+    new: 0xcbf2_9ce4_8422_2325,
+    update: |state, data| {
+        for &byte in data {
+            *state = (*state ^ u64::from(byte))
+                .wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    },
+    finalize: |state, out| out.copy_from_slice(&state.to_be_bytes()),
+);
+
+#[cfg(feature = "metrohash")]
+adapter!(
+    /// 64 bit Metro hash, with the output in little endian byte order like
+    /// the reference implementation.
+    MetroHash64(metrohash::MetroHash64),
+    digest::consts::U8,
+    digest::consts::U32,
+    new: metrohash::MetroHash64::with_seed(0),
+    update: |state, data| std::hash::Hasher::write(state, data),
+    finalize: |state, out| {
+        out.copy_from_slice(&std::hash::Hasher::finish(&state).to_le_bytes());
+    },
+);
+
+#[cfg(feature = "metrohash")]
+adapter!(
+    /// 128 bit Metro hash, with the output in little endian byte order like
+    /// the reference implementation.
+    MetroHash128(metrohash::MetroHash128),
+    digest::consts::U16,
+    digest::consts::U32,
+    new: metrohash::MetroHash128::with_seed(0),
+    update: |state, data| std::hash::Hasher::write(state, data),
+    finalize: |state, out| {
+        let (v0, v1) = state.finish128();
+        out[..8].copy_from_slice(&v0.to_le_bytes());
+        out[8..].copy_from_slice(&v1.to_le_bytes());
+    },
+);
+
+#[cfg(feature = "murmur3")]
+adapter!(
+    /// 32 bit Murmur3 for x86.
+    Murmur3_32(mur3::Hasher32),
+    digest::consts::U4,
+    digest::consts::U4,
+    new: mur3::Hasher32::with_seed(0),
+    update: |state, data| std::hash::Hasher::write(state, data),
+    finalize: |state, out| out.copy_from_slice(&state.finish32().to_be_bytes()),
+);
+
+#[cfg(feature = "murmur3")]
+adapter!(
+    /// 128 bit Murmur3 for x64.
+    Murmur3_128(mur3::Hasher128),
+    digest::consts::U16,
+    digest::consts::U16,
+    new: mur3::Hasher128::with_seed(0),
+    update: |state, data| std::hash::Hasher::write(state, data),
+    finalize: |state, out| {
+        let (h1, h2) = state.finish128();
+        out[..8].copy_from_slice(&h1.to_be_bytes());
+        out[8..].copy_from_slice(&h2.to_be_bytes());
+    },
+);
+
+#[cfg(feature = "seahash")]
+adapter!(
+    /// 64 bit Sea hash.
+    SeaHash(seahash::SeaHasher),
+    digest::consts::U8,
+    digest::consts::U32,
+    new: seahash::SeaHasher::new(),
+    update: |state, data| std::hash::Hasher::write(state, data),
+    finalize: |state, out| {
+        out.copy_from_slice(&std::hash::Hasher::finish(&state).to_be_bytes());
+    },
+);
+
+#[cfg(feature = "sum")]
+static CRC_32_CKSUM: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_CKSUM);
+
+#[cfg(feature = "sum")]
+adapter!(
+    /// CRC as computed by POSIX `cksum`, with the input length appended.
+    Cksum((crc::Digest<'static, u32>, u64)),
+    digest::consts::U4,
+    digest::consts::U64,
+    new: (CRC_32_CKSUM.digest(), 0),
+    update: |state, data| {
+        state.0.update(data);
+        state.1 += data.len() as u64;
+    },
+    finalize: |state, out| {
+        let (mut crc, len) = state;
+        // the length in as few bytes as possible, least significant first
+        let bytes = (71 - len.leading_zeros()) as usize / 8;
+        crc.update(&len.to_le_bytes()[..bytes]);
+        out.copy_from_slice(&crc.finalize().to_be_bytes());
+    },
+);
+
+#[cfg(feature = "sum")]
+adapter!(
+    /// 16 bit rotating checksum as computed by BSD `sum`.
+    BsdSum(u16),
+    digest::consts::U2,
+    digest::consts::U1,
+    new: 0,
+    update: |state, data| {
+        for &byte in data {
+            *state = state.rotate_right(1).wrapping_add(u16::from(byte));
+        }
+    },
+    finalize: |state, out| out.copy_from_slice(&state.to_be_bytes()),
+);
+
+#[cfg(feature = "sum")]
+adapter!(
+    /// 16 bit checksum as computed by System V `sum`.
+    SysVSum(u32),
+    digest::consts::U2,
+    digest::consts::U1,
+    new: 0,
+    update: |state, data| {
+        for &byte in data {
+            *state = state.wrapping_add(u32::from(byte));
+        }
+    },
+    finalize: |state, out| {
+        let folded = (state & 0xffff) + (state >> 16);
+        let folded = (folded & 0xffff) + (folded >> 16);
+        out.copy_from_slice(&folded.to_be_bytes()[2..]);
+    },
+);
+
+#[cfg(feature = "wyhash")]
+adapter!(
+    /// 64 bit wyhash. The input is buffered, as wyhash is not incremental.
+    WyHash(Vec<u8>),
+    digest::consts::U8,
+    digest::consts::U64,
+    new: Vec::new(),
+    update: |state, data| state.extend_from_slice(data),
+    finalize: |state, out| {
+        out.copy_from_slice(&wyhash::wyhash(&state, 0).to_be_bytes());
+    },
+);
+
+#[cfg(feature = "xxhash")]
+adapter!(
+    /// 32 bit xxHash.
+    Xxh32(xxhash_rust::xxh32::Xxh32),
+    digest::consts::U4,
+    digest::consts::U16,
+    new: xxhash_rust::xxh32::Xxh32::new(0),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(&state.digest().to_be_bytes()),
+);
+
+#[cfg(feature = "xxhash")]
+adapter!(
+    /// 64 bit xxHash.
+    Xxh64(xxhash_rust::xxh64::Xxh64),
+    digest::consts::U8,
+    digest::consts::U32,
+    new: xxhash_rust::xxh64::Xxh64::new(0),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(&state.digest().to_be_bytes()),
+);
+
+#[cfg(feature = "xxhash")]
+adapter!(
+    /// 64 bit XXH3.
+    Xxh3_64(xxhash_rust::xxh3::Xxh3),
+    digest::consts::U8,
+    digest::consts::U64,
+    new: xxhash_rust::xxh3::Xxh3::new(),
+    update: |state, data| state.update(data),
+    finalize: |state, out| out.copy_from_slice(&state.digest().to_be_bytes()),
+);
+
+#[cfg(feature = "xxhash")]
+adapter!(
+    /// 128 bit XXH3.
+    Xxh3_128(xxhash_rust::xxh3::Xxh3),
+    digest::consts::U16,
+    digest::consts::U64,
+    new: xxhash_rust::xxh3::Xxh3::new(),
+    update: |state, data| state.update(data),
+    finalize: |state, out| {
+        out.copy_from_slice(&state.digest128().to_be_bytes());
+    },
+);
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(
+        feature = "blake2",
+        feature = "metrohash",
+        feature = "murmur3",
+        feature = "sum",
+        feature = "xxhash"
+    ))]
+    use digest::Digest;
+    #[cfg(any(
+        feature = "blake2",
+        feature = "metrohash",
+        feature = "murmur3",
+        feature = "seahash"
+    ))]
+    use digest::{FixedOutputReset, Update};
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn blake2p() {
+        // leaf blocks are distributed round-robin, so updates must not have
+        // to be aligned to blocks
+        let data = (0..=255).cycle().take(1280).collect::<Vec<u8>>();
+
+        let mut hasher = super::Blake2bp::default();
+        data.chunks(33)
+            .for_each(|chunk| Update::update(&mut hasher, chunk));
+
+        assert_eq!(
+            hasher.finalize_fixed_reset()[..],
+            super::Blake2bp::digest(&data)[..]
+        );
+        assert_eq!(
+            hasher.finalize_fixed_reset()[..],
+            super::Blake2bp::digest(b"")[..]
+        );
+        assert_eq!(
+            super::Blake2sp::digest(&data)[..4],
+            [0xf8, 0x64, 0x3d, 0xbe]
+        );
+    }
+
+    #[cfg(feature = "metrohash")]
+    #[test]
+    fn metrohash() {
+        // reference implementation test vectors
+        let data =
+            b"012345678901234567890123456789012345678901234567890123456789012";
+
+        let mut hasher = super::MetroHash64::default();
+        data.chunks(5)
+            .for_each(|chunk| Update::update(&mut hasher, chunk));
+
+        assert_eq!(
+            hasher.finalize_fixed_reset()[..],
+            [0x6b, 0x75, 0x3d, 0xae, 0x06, 0x70, 0x4b, 0xad]
+        );
+        assert_eq!(
+            super::MetroHash128::digest(data)[..],
+            [
+                0xc7, 0x7c, 0xe2, 0xbf, 0xa4, 0xed, 0x9f, 0x9b, 0x05, 0x48,
+                0xb2, 0xac, 0x50, 0x74, 0xa2, 0x97
+            ]
+        );
+    }
+
+    #[cfg(feature = "murmur3")]
+    #[test]
+    fn murmur3_chunked() {
+        // tails are buffered across updates
+        let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+
+        let mut hasher = super::Murmur3_128::default();
+        data.chunks(7)
+            .for_each(|chunk| Update::update(&mut hasher, chunk));
+
+        assert_eq!(
+            hasher.finalize_fixed_reset()[..],
+            super::Murmur3_128::digest(&data)[..]
+        );
+        assert_eq!(
+            super::Murmur3_32::digest(b"Hello, world!")[..],
+            [0xc0, 0x36, 0x3e, 0x43]
+        );
+    }
+
+    #[cfg(feature = "seahash")]
+    #[test]
+    fn seahash() {
+        // crate documentation example
+        let mut hasher = super::SeaHash::default();
+        b"to be or not to be"
+            .chunks(3)
+            .for_each(|chunk| Update::update(&mut hasher, chunk));
+
+        assert_eq!(
+            hasher.finalize_fixed_reset()[..],
+            1_988_685_042_348_123_509_u64.to_be_bytes()
+        );
+    }
+
+    #[cfg(feature = "sum")]
+    #[test]
+    fn sum() {
+        // as printed by cksum, sum -r and sum -s, in hex
+        let data = b"123456789";
+
+        assert_eq!(
+            super::Cksum::digest(data)[..],
+            930_766_865_u32.to_be_bytes()
+        );
+        // the length takes two bytes
+        assert_eq!(
+            super::Cksum::digest([0; 256])[..],
+            [0xfb, 0x3e, 0xe2, 0x48]
+        );
+        assert_eq!(super::BsdSum::digest(data)[..], [0xd1, 0x6f]);
+        assert_eq!(super::SysVSum::digest(data)[..], [0x01, 0xdd]);
+    }
+
+    #[cfg(feature = "xxhash")]
+    #[test]
+    fn xxhash_canonical() {
+        // like xxhsum, outputs are the big endian representation
+        assert_eq!(super::Xxh32::digest(b"")[..], [0x02, 0xcc, 0x5d, 0x05]);
+        assert_eq!(
+            super::Xxh3_128::digest(b"")[..8],
+            [0x99, 0xaa, 0x06, 0xd3, 0x01, 0x47, 0x98, 0xd8]
+        );
+    }
+}
@@ -25,9 +25,46 @@
 //! }
 //! ```
 
-use clap::builder::{Arg, ArgAction, EnumValueParser};
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
 
-use crate::Digest;
+use clap::builder::{
+    Arg, ArgAction, EnumValueParser, OsStringValueParser, PossibleValue,
+    PossibleValuesParser, TypedValueParser,
+};
+use clap::{ArgMatches, Args, Command, FromArgMatches, ValueEnum};
+
+#[cfg(feature = "blake2")]
+use crate::blake2var::{self, Blake2Var, Variant};
+use crate::encode::Encoding;
+use crate::format::{Formatter, Mode, Style};
+use crate::hash::Input;
+#[cfg(any(
+    feature = "cmac",
+    feature = "hmac",
+    feature = "poly1305",
+    feature = "sha3",
+    feature = "siphash"
+))]
+use crate::mac::Mac;
+#[cfg(any(
+    feature = "argon2",
+    feature = "bcrypt",
+    feature = "pbkdf2",
+    feature = "scrypt"
+))]
+use crate::password::{Params, PasswordHash};
+use crate::verify::Verifier;
+#[cfg(any(
+    feature = "ascon",
+    feature = "blake3",
+    feature = "k12",
+    feature = "sha3"
+))]
+use crate::xof::{Xof, XofHasher};
+use crate::{Digest, DigestChoice, MultiDigest};
 
 /// Returns a ready-to-use [`clap::Arg`] to choose a supported digest
 /// algorithm.
@@ -47,9 +84,12 @@ use crate::Digest;
 ///
 /// assert_eq!(digest, Digest::MD5);
 /// ```
+///
+/// With the `completions` feature, the argument is set up for dynamic shell
+/// completion, see the `completions` module.
 #[must_use]
 pub fn digest() -> Arg {
-    Arg::new("digest")
+    let arg = Arg::new("digest")
         .short('d')
         .long("digest")
         .help("digest algorithm")
@@ -58,7 +98,302 @@ pub fn digest() -> Arg {
              dependencies/features that may be chosen during compilation.",
         )
         .action(ArgAction::Set)
-        .value_parser(EnumValueParser::<Digest>::new())
+        .value_parser(DigestValueParser);
+
+    #[cfg(feature = "completions")]
+    let arg = arg.add(crate::completions::completer());
+
+    arg
+}
+
+/// Value parser for [`digest()`], allowing common spellings of the digest
+/// names.
+///
+/// Names are looked up via [`Digest::from_name`], e.g. `sha-256` for
+/// [`Digest::SHA256`]. Only the canonical names are listed as possible
+/// values.
+///
+/// # Examples
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use clap_digest::arg::DigestValueParser;
+/// use clap_digest::Digest;
+///
+/// let cli = Command::new("myapp")
+///     .arg(Arg::new("digest").value_parser(DigestValueParser));
+/// let args = cli.get_matches_from(["myapp", "sha-256"]);
+///
+/// assert_eq!(args.get_one::<Digest>("digest"), Some(&Digest::SHA256));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DigestValueParser;
+
+impl TypedValueParser for DigestValueParser {
+    type Value = Digest;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        // the enum parser reports invalid values with the possible values
+        value.to_str().and_then(Digest::from_name).map_or_else(
+            || EnumValueParser::<Digest>::new().parse_ref(cmd, arg, value),
+            Ok,
+        )
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            Digest::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose a built-in or registered
+/// custom digest algorithm, see [`crate::registry`].
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::{Digest, DigestChoice};
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::digest_choice());
+/// let args = cli.get_matches_from(["myapp", "--digest", "sha-256"]);
+///
+/// let digest = *args
+///     .get_one::<DigestChoice>("digest")
+///     .unwrap();
+///
+/// assert_eq!(digest.builtin(), Some(Digest::SHA256));
+/// ```
+///
+/// With the `completions` feature, the registered custom digest algorithms
+/// are completed as well.
+#[must_use]
+pub fn digest_choice() -> Arg {
+    let arg = digest().value_parser(DigestChoiceValueParser);
+
+    #[cfg(feature = "completions")]
+    let arg = arg.add(crate::completions::choice_completer());
+
+    arg
+}
+
+/// Value parser for [`digest_choice`], allowing the spellings of
+/// [`DigestValueParser`] for both built-in and registered custom digest
+/// algorithms.
+///
+/// Names are looked up via [`DigestChoice::from_name`]. Only the canonical
+/// names are listed as possible values.
+///
+/// # Examples
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use clap_digest::arg::DigestChoiceValueParser;
+/// use clap_digest::{Digest, DigestChoice};
+///
+/// let cli = Command::new("myapp")
+///     .arg(Arg::new("digest").value_parser(DigestChoiceValueParser));
+/// let args = cli.get_matches_from(["myapp", "sha-256"]);
+///
+/// assert_eq!(
+///     args.get_one::<DigestChoice>("digest"),
+///     Some(&DigestChoice::Builtin(Digest::SHA256))
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DigestChoiceValueParser;
+
+impl TypedValueParser for DigestChoiceValueParser {
+    type Value = DigestChoice;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        // the enum parser reports invalid values with the possible values
+        value
+            .to_str()
+            .and_then(DigestChoice::from_name)
+            .map_or_else(
+                || {
+                    EnumValueParser::<DigestChoice>::new()
+                        .parse_ref(cmd, arg, value)
+                },
+                Ok,
+            )
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            DigestChoice::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose several supported digest
+/// algorithms.
+///
+/// The digests may be given by repeating the argument or separated by commas.
+/// The special value `all` chooses every supported digest, see
+/// [`DigestsValueParser`]. Use [`multi_digest`] to compute all of them in one
+/// pass.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::Digest;
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::digests());
+/// let args = cli.get_matches_from([
+///     "myapp", "-d", "MD5,SHA256", "--digest", "SHA512",
+/// ]);
+///
+/// let digests = args
+///     .get_many::<Vec<Digest>>("digest")
+///     .unwrap()
+///     .flatten()
+///     .copied()
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(digests, [Digest::MD5, Digest::SHA256, Digest::SHA512]);
+/// ```
+#[must_use]
+pub fn digests() -> Arg {
+    digest()
+        .help("digest algorithms, or all")
+        .long_help(
+            "Use these digest algorithms, separated by commas or by repeating \
+             this option, or all of them. These algorithms are optional \
+             dependencies/features that may be chosen during compilation.",
+        )
+        .action(ArgAction::Append)
+        .value_delimiter(',')
+        .value_parser(DigestsValueParser)
+}
+
+/// Returns a [`MultiDigest`] computing the digests chosen via [`digests`].
+///
+/// Digests chosen more than once are computed only once, in the order they
+/// were first given.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::Digest;
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::digests());
+/// let args = cli.get_matches_from(["myapp", "-d", "MD5,SHA256,MD5"]);
+///
+/// let multi = clap_digest::arg::multi_digest(&args);
+///
+/// assert_eq!(
+///     multi.digests().collect::<Vec<_>>(),
+///     [Digest::MD5, Digest::SHA256],
+/// );
+/// ```
+#[must_use]
+pub fn multi_digest(matches: &ArgMatches) -> MultiDigest {
+    let mut digests = Vec::new();
+
+    for &digest in matches
+        .get_many::<Vec<Digest>>("digest")
+        .into_iter()
+        .flatten()
+        .flatten()
+    {
+        if !digests.contains(&digest) {
+            digests.push(digest);
+        }
+    }
+
+    digests.into()
+}
+
+/// Value parser for [`digests`], expanding `all` to every supported digest.
+///
+/// Every value is parsed to a `Vec<Digest>`, either a single digest or all
+/// of them. Digest names allow the spellings of [`DigestValueParser`], and
+/// `all` is matched ignoring ASCII case.
+///
+/// # Examples
+///
+/// ```
+/// use clap::{Arg, Command, ValueEnum};
+/// use clap_digest::arg::DigestsValueParser;
+/// use clap_digest::Digest;
+///
+/// let cli = Command::new("myapp")
+///     .arg(Arg::new("digest").value_parser(DigestsValueParser));
+/// let args = cli.get_matches_from(["myapp", "all"]);
+///
+/// let digests = args.get_one::<Vec<Digest>>("digest").unwrap();
+///
+/// assert_eq!(digests, Digest::value_variants());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DigestsValueParser;
+
+impl DigestsValueParser {
+    /// Value choosing all digests.
+    const ALL: &'static str = "all";
+
+    /// Returns the possible values, i.e. the digest names and `all`.
+    fn values() -> impl Iterator<Item = PossibleValue> {
+        Digest::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .chain([PossibleValue::new(Self::ALL).help("all digests")])
+    }
+}
+
+impl TypedValueParser for DigestsValueParser {
+    type Value = Vec<Digest>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        if let Some(digest) = value.to_str().and_then(Digest::from_name) {
+            return Ok(vec![digest]);
+        }
+
+        if value.eq_ignore_ascii_case(Self::ALL) {
+            return Ok(Digest::value_variants().to_vec());
+        }
+
+        // the possible values parser reports invalid values
+        let value = PossibleValuesParser::new(Self::values())
+            .parse_ref(cmd, arg, value)?;
+
+        // UNWRAP: the value is one of the possible values
+        Ok(vec![Digest::from_str(&value, false).unwrap()])
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(Self::values()))
+    }
 }
 
 /// Returns a ready-to-use [`clap::Arg`] to list supported digest
@@ -82,18 +417,1295 @@ pub fn list_digests() -> Arg {
         .help("list supported digest algorithms")
 }
 
-// ----------------------------------------------------------------------------
-// tests
-// ----------------------------------------------------------------------------
+/// Returns a ready-to-use [`clap::Arg`] to choose the digest output
+/// encoding.
+///
+/// The encoding defaults to lowercase hex.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::encode::Encoding;
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::encoding());
+/// let args = cli.get_matches_from(["myapp", "--output-encoding", "base64"]);
+///
+/// let encoding = *args
+///     .get_one::<Encoding>("output-encoding")
+///     .unwrap();
+///
+/// assert_eq!(encoding, Encoding::Base64);
+/// ```
+#[must_use]
+pub fn encoding() -> Arg {
+    Arg::new("output-encoding")
+        .long("output-encoding")
+        .value_name("ENCODING")
+        .help("digest output encoding")
+        .action(ArgAction::Set)
+        .default_value("hex")
+        .value_parser(EnumValueParser::<Encoding>::new())
+}
 
-#[cfg(test)]
-mod tests {
-    use clap::Command;
+/// Returns a ready-to-use [`clap::Arg`] to choose the output length of an
+/// extendable-output function.
+///
+/// The length is given in bits, like the `--length` of `b2sum`, and must be a
+/// positive multiple of 8. Use [`output_len`] to get it in bytes.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::output_length());
+/// let args = cli.get_matches_from(["myapp", "--output-length", "128"]);
+///
+/// assert_eq!(clap_digest::arg::output_len(&args), Some(16));
+/// ```
+#[must_use]
+pub fn output_length() -> Arg {
+    Arg::new("output-length")
+        .short('l')
+        .long("output-length")
+        .value_name("BITS")
+        .help("output length in bits")
+        .long_help(
+            "Read this number of output bits from an extendable-output \
+             function. The length must be a positive multiple of 8.",
+        )
+        .action(ArgAction::Set)
+        .value_parser(parse_bits)
+}
 
-    #[test]
-    fn list_digests() {
-        let cli = Command::new("myapp").arg(crate::arg::list_digests());
-        let args = cli.get_matches_from(["myapp", "--list-digests"]);
-        assert!(args.contains_id("list-digests"));
+/// Returns the output length in bytes chosen with [`output_length`], if any.
+#[must_use]
+pub fn output_len(matches: &ArgMatches) -> Option<usize> {
+    matches
+        .try_get_one::<usize>("output-length")
+        .ok()
+        .flatten()
+        .map(|bits| bits / 8)
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the customization string
+/// of an extendable-output function, for domain separation.
+///
+/// Use [`xof_hasher`] to construct a hasher with it.
+#[must_use]
+pub fn customization() -> Arg {
+    Arg::new("customization")
+        .long("customization")
+        .value_name("STRING")
+        .help("customization string")
+        .long_help(
+            "Customize the extendable-output function with this string, so \
+             its output is unrelated to the output of other customizations. \
+             Only the cSHAKE functions and KMAC support this.",
+        )
+        .action(ArgAction::Set)
+}
+
+/// Returns a new hasher of the function, customized with the string chosen
+/// with [`customization`], if any.
+///
+/// This is [`None`] if a customization was chosen and the function doesn't
+/// take one, see [`Xof::is_customizable`](crate::xof::Xof::is_customizable).
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::xof::Xof;
+///
+/// let cli = Command::new("myapp")
+///     .arg(clap_digest::arg::customization())
+///     .arg(clap_digest::arg::output_length());
+/// let args = cli.get_matches_from(["myapp", "--customization", "Email"]);
+///
+/// let hasher = clap_digest::arg::xof_hasher(&args, Xof::CSHAKE256).unwrap();
+/// let len = clap_digest::arg::output_len(&args)
+///     .unwrap_or(Xof::CSHAKE256.default_output_len());
+///
+/// let output = hasher.finalize_output(len);
+///
+/// assert!(clap_digest::arg::xof_hasher(&args, Xof::SHAKE256).is_none());
+/// ```
+#[cfg(any(
+    feature = "ascon",
+    feature = "blake3",
+    feature = "k12",
+    feature = "sha3"
+))]
+#[must_use]
+pub fn xof_hasher(matches: &ArgMatches, xof: Xof) -> Option<XofHasher> {
+    let customization = matches
+        .try_get_one::<String>("customization")
+        .ok()
+        .flatten()
+        .map_or("", String::as_str);
+
+    XofHasher::new_customized(xof, customization.as_bytes())
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the context string of a
+/// key derivation.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+///
+/// let cli = Command::new("myapp")
+///     .arg(clap_digest::arg::context().required(true))
+///     .arg(clap_digest::arg::output_length());
+/// let args = cli.get_matches_from([
+///     "myapp", "--context", "myapp 2024-01-01 session keys", "-l", "128",
+/// ]);
+///
+/// let context = args.get_one::<String>("context").unwrap();
+/// let len = clap_digest::arg::output_len(&args).unwrap_or(32);
+///
+/// let key = clap_digest::kdf::derive_key(context, b"secret", len);
+///
+/// assert_eq!(key.len(), 16);
+/// ```
+#[must_use]
+pub fn context() -> Arg {
+    Arg::new("context")
+        .long("context")
+        .value_name("STRING")
+        .help("key derivation context string")
+        .long_help(
+            "Derive keys in this context, so they are unrelated to keys \
+             derived from the same key material in other contexts. The \
+             context should be hardcoded, globally unique and \
+             application-specific.",
+        )
+        .action(ArgAction::Set)
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the salt of a key
+/// derivation, given as hex.
+///
+/// See [`info`] for an example.
+#[must_use]
+pub fn salt() -> Arg {
+    Arg::new("salt")
+        .long("salt")
+        .value_name("HEX")
+        .help("key derivation salt as hex")
+        .long_help(
+            "Use this salt, given as hex, to extract the key material. A \
+             random salt strengthens the derivation, but it needn't be \
+             secret.",
+        )
+        .action(ArgAction::Set)
+        .value_parser(|value: &str| crate::encode::decode_hex(value))
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the info string of a key
+/// derivation.
+///
+/// Pass it with the salt chosen with [`salt`] and the digest chosen with
+/// [`digest()`] to `kdf::hkdf` (feature `hkdf`).
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+///
+/// let cli = Command::new("myapp")
+///     .arg(clap_digest::arg::salt())
+///     .arg(clap_digest::arg::info());
+/// let args = cli.get_matches_from([
+///     "myapp", "--salt", "0011", "--info", "session keys",
+/// ]);
+///
+/// let salt = args.get_one::<Vec<u8>>("salt").unwrap();
+/// let info = args.get_one::<String>("info").unwrap();
+///
+/// assert_eq!(salt, &[0x00, 0x11]);
+/// assert_eq!(info, "session keys");
+/// ```
+#[must_use]
+pub fn info() -> Arg {
+    Arg::new("info")
+        .long("info")
+        .value_name("STRING")
+        .help("key derivation info string")
+        .long_help(
+            "Derive keys for this application-specific info string, so they \
+             are unrelated to keys derived from the same key material for \
+             other info strings.",
+        )
+        .action(ArgAction::Set)
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose a key, given as hex.
+///
+/// Use [`blake2_hasher`] to construct a keyed BLAKE2 hasher with it.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::key());
+/// let args = cli.get_matches_from(["myapp", "--key", "6b6579"]);
+///
+/// let key = args.get_one::<Vec<u8>>("key").unwrap();
+///
+/// assert_eq!(key, b"key");
+/// ```
+#[must_use]
+pub fn key() -> Arg {
+    Arg::new("key")
+        .long("key")
+        .value_name("HEX")
+        .help("key as hex")
+        .long_help(
+            "Use this key, given as hex, making the digest a message \
+             authentication code.",
+        )
+        .action(ArgAction::Set)
+        .value_parser(|value: &str| crate::encode::decode_hex(value))
+}
+
+/// Returns a new BLAKE2 hasher with the output length chosen with
+/// [`output_length`] and the key chosen with [`key`], if any.
+///
+/// The output length defaults to the maximum of the variant, like `b2sum`.
+///
+/// # Errors
+///
+/// Returns an error if the output length or the key is too long for the
+/// variant.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::blake2var::Variant;
+/// use digest::Update;
+///
+/// let cli = Command::new("myapp")
+///     .arg(clap_digest::arg::output_length())
+///     .arg(clap_digest::arg::key());
+/// let args = cli.get_matches_from(["myapp", "-l", "160", "--key", "6b6579"]);
+///
+/// let mut hasher =
+///     clap_digest::arg::blake2_hasher(&args, Variant::Blake2b).unwrap();
+/// hasher.update(b"abc");
+///
+/// assert_eq!(
+///     hasher.finalize_output().to_string(),
+///     "63c5e9d2e167991e7fca9fe84b4afcb2fab7cc99",
+/// );
+/// ```
+#[cfg(feature = "blake2")]
+pub fn blake2_hasher(
+    matches: &ArgMatches,
+    variant: Variant,
+) -> Result<Blake2Var, blake2var::Error> {
+    let len = output_len(matches).unwrap_or_else(|| variant.max_output_len());
+    let key = matches
+        .try_get_one::<Vec<u8>>("key")
+        .ok()
+        .flatten()
+        .map_or(&[][..], Vec::as_slice);
+
+    Blake2Var::new_keyed(variant, key, len)
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose a supported message
+/// authentication code.
+///
+/// Use [`mac_key`] to get the key chosen with [`hmac_key`], [`key_file`] or
+/// [`key`].
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::mac::Mac;
+///
+/// let cli = Command::new("myapp")
+///     .arg(clap_digest::arg::mac())
+///     .arg(clap_digest::arg::hmac_key());
+/// let args = cli.get_matches_from([
+///     "myapp", "--mac", "KMAC128", "--hmac-key", "secret",
+/// ]);
+///
+/// let mac = *args.get_one::<Mac>("mac").unwrap();
+/// let key = clap_digest::arg::mac_key(&args).unwrap().unwrap();
+///
+/// assert_eq!(mac, Mac::KMAC128);
+/// assert_eq!(key, b"secret");
+/// ```
+#[cfg(any(
+    feature = "cmac",
+    feature = "hmac",
+    feature = "poly1305",
+    feature = "sha3",
+    feature = "siphash"
+))]
+#[must_use]
+pub fn mac() -> Arg {
+    Arg::new("mac")
+        .long("mac")
+        .value_name("MAC")
+        .help("message authentication code")
+        .long_help(
+            "Use this message authentication code. These codes are optional \
+             dependencies/features that may be chosen during compilation.",
+        )
+        .action(ArgAction::Set)
+        .value_parser(EnumValueParser::<Mac>::new())
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose a MAC key, given as a
+/// string like `openssl dgst -hmac`.
+///
+/// See [`mac_key`].
+#[must_use]
+pub fn hmac_key() -> Arg {
+    Arg::new("hmac-key")
+        .long("hmac-key")
+        .value_name("KEY")
+        .help("MAC key as string")
+        .long_help(
+            "Use this string as message authentication code key. Prefer \
+             --key-file, as command lines are visible to other users.",
+        )
+        .action(ArgAction::Set)
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose a file containing the raw
+/// MAC key.
+///
+/// See [`mac_key`].
+#[must_use]
+pub fn key_file() -> Arg {
+    Arg::new("key-file")
+        .long("key-file")
+        .value_name("FILE")
+        .help("file containing the MAC key")
+        .action(ArgAction::Set)
+        .value_parser(clap::value_parser!(PathBuf))
+}
+
+/// Returns the MAC key chosen with [`key_file`], [`hmac_key`] or [`key`],
+/// checked in this order, if any.
+///
+/// # Errors
+///
+/// Returns an error if reading the key file fails.
+pub fn mac_key(matches: &ArgMatches) -> io::Result<Option<Vec<u8>>> {
+    if let Ok(Some(path)) = matches.try_get_one::<PathBuf>("key-file") {
+        return fs::read(path).map(Some);
+    }
+
+    if let Ok(Some(key)) = matches.try_get_one::<String>("hmac-key") {
+        return Ok(Some(key.as_bytes().to_vec()));
+    }
+
+    Ok(matches
+        .try_get_one::<Vec<u8>>("key")
+        .ok()
+        .flatten()
+        .cloned())
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose a supported password
+/// hashing algorithm.
+///
+/// Use [`password_params`] to get the cost parameters chosen with
+/// [`iterations`], [`memory_cost`], [`parallelism`] and [`cost`].
+#[cfg(any(
+    feature = "argon2",
+    feature = "bcrypt",
+    feature = "pbkdf2",
+    feature = "scrypt"
+))]
+#[must_use]
+pub fn password_hash() -> Arg {
+    Arg::new("password-hash")
+        .long("password-hash")
+        .value_name("ALGORITHM")
+        .help("password hashing algorithm")
+        .long_help(
+            "Use this password hashing algorithm. These algorithms are \
+             optional dependencies/features that may be chosen during \
+             compilation.",
+        )
+        .action(ArgAction::Set)
+        .value_parser(EnumValueParser::<PasswordHash>::new())
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the number of password
+/// hashing iterations.
+///
+/// See [`password_params`].
+#[cfg(any(
+    feature = "argon2",
+    feature = "bcrypt",
+    feature = "pbkdf2",
+    feature = "scrypt"
+))]
+#[must_use]
+pub fn iterations() -> Arg {
+    Arg::new("iterations")
+        .long("iterations")
+        .value_name("N")
+        .help("number of Argon2 passes or PBKDF2 rounds")
+        .action(ArgAction::Set)
+        .value_parser(clap::value_parser!(u32).range(1..))
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the memory used for
+/// password hashing.
+///
+/// See [`password_params`].
+#[cfg(any(
+    feature = "argon2",
+    feature = "bcrypt",
+    feature = "pbkdf2",
+    feature = "scrypt"
+))]
+#[must_use]
+pub fn memory_cost() -> Arg {
+    Arg::new("memory-cost")
+        .long("memory-cost")
+        .value_name("KIB")
+        .help("memory in KiB used by Argon2")
+        .action(ArgAction::Set)
+        .value_parser(clap::value_parser!(u32).range(1..))
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the degree of parallelism
+/// of password hashing.
+///
+/// See [`password_params`].
+#[cfg(any(
+    feature = "argon2",
+    feature = "bcrypt",
+    feature = "pbkdf2",
+    feature = "scrypt"
+))]
+#[must_use]
+pub fn parallelism() -> Arg {
+    Arg::new("parallelism")
+        .long("parallelism")
+        .value_name("N")
+        .help("degree of parallelism of Argon2 and scrypt")
+        .action(ArgAction::Set)
+        .value_parser(clap::value_parser!(u32).range(1..))
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the logarithmic work factor
+/// of password hashing.
+///
+/// See [`password_params`].
+#[cfg(any(
+    feature = "argon2",
+    feature = "bcrypt",
+    feature = "pbkdf2",
+    feature = "scrypt"
+))]
+#[must_use]
+pub fn cost() -> Arg {
+    Arg::new("cost")
+        .long("cost")
+        .value_name("N")
+        .help("base-2 log of the work factor of bcrypt and scrypt")
+        .long_help(
+            "Use 2^N as work factor: this is the bcrypt cost, from 4 to 31, \
+             and the scrypt ln.",
+        )
+        .action(ArgAction::Set)
+        .value_parser(clap::value_parser!(u32))
+}
+
+/// Returns the password hashing parameters chosen with [`iterations`],
+/// [`memory_cost`], [`parallelism`] and [`cost`].
+///
+/// Parameters that weren't chosen are left at their defaults.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::password::Params;
+///
+/// let cli = Command::new("myapp")
+///     .arg(clap_digest::arg::iterations())
+///     .arg(clap_digest::arg::memory_cost());
+/// let args = cli.get_matches_from([
+///     "myapp", "--iterations", "3", "--memory-cost", "65536",
+/// ]);
+///
+/// assert_eq!(
+///     clap_digest::arg::password_params(&args),
+///     Params::new().iterations(3).memory(65536),
+/// );
+/// ```
+#[cfg(any(
+    feature = "argon2",
+    feature = "bcrypt",
+    feature = "pbkdf2",
+    feature = "scrypt"
+))]
+#[must_use]
+pub fn password_params(matches: &ArgMatches) -> Params {
+    let get = |id| matches.try_get_one::<u32>(id).ok().flatten().copied();
+    let mut params = Params::new();
+
+    if let Some(iterations) = get("iterations") {
+        params = params.iterations(iterations);
+    }
+
+    if let Some(memory) = get("memory-cost") {
+        params = params.memory(memory);
+    }
+
+    if let Some(parallelism) = get("parallelism") {
+        params = params.parallelism(parallelism);
+    }
+
+    if let Some(cost) = get("cost") {
+        params = params.cost(cost);
+    }
+
+    params
+}
+
+/// Parses a positive number of bits that is a multiple of 8.
+fn parse_bits(value: &str) -> Result<usize, String> {
+    let bits = value.parse::<usize>().map_err(|e| e.to_string())?;
+
+    if bits == 0 || bits % 8 != 0 {
+        return Err(format!("{bits} is not a positive multiple of 8"));
+    }
+
+    Ok(bits)
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to choose the number of threads used
+/// to hash a single input.
+///
+/// The value defaults to `1`, `0` meaning one thread per CPU core. Pass it to
+/// [`HashOptions::jobs`](crate::hash::HashOptions::jobs).
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::jobs());
+/// let args = cli.get_matches_from(["myapp", "--jobs", "4"]);
+///
+/// let jobs = *args.get_one::<usize>("jobs").unwrap();
+///
+/// assert_eq!(jobs, 4);
+/// ```
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn jobs() -> Arg {
+    Arg::new("jobs")
+        .short('j')
+        .long("jobs")
+        .value_name("N")
+        .help("number of threads per input, 0 for one per CPU core")
+        .long_help(
+            "Use this number of threads to hash a single input, 0 meaning \
+             one per CPU core. Only the BLAKE3 digest supports this.",
+        )
+        .action(ArgAction::Set)
+        .default_value("1")
+        .value_parser(clap::value_parser!(usize))
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to write BSD-style checksum lines.
+///
+/// See [`Style::Bsd`] and [`formatter`].
+#[must_use]
+pub fn tag() -> Arg {
+    Arg::new("tag")
+        .long("tag")
+        .action(ArgAction::SetTrue)
+        .help("create a BSD-style checksum")
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to terminate checksum lines with
+/// NUL instead of newline.
+///
+/// See [`Formatter::zero`] and [`formatter`].
+#[must_use]
+pub fn zero() -> Arg {
+    Arg::new("zero")
+        .short('z')
+        .long("zero")
+        .action(ArgAction::SetTrue)
+        .help("end each output line with NUL, not newline")
+        .long_help(
+            "End each output line with NUL, not newline, and disable file \
+             name escaping.",
+        )
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to read in binary mode.
+///
+/// See [`Mode::Binary`] and [`formatter`].
+#[must_use]
+pub fn binary() -> Arg {
+    Arg::new("binary")
+        .short('b')
+        .long("binary")
+        .action(ArgAction::SetTrue)
+        .help("read in binary mode")
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to read in text mode, the default.
+///
+/// See [`Mode::Text`] and [`formatter`].
+#[must_use]
+pub fn text() -> Arg {
+    Arg::new("text")
+        .short('t')
+        .long("text")
+        .action(ArgAction::SetTrue)
+        .help("read in text mode (default)")
+}
+
+/// Returns a [`Formatter`] configured via [`tag`], [`zero`], [`binary`] and
+/// [`text`].
+///
+/// Of [`binary`] and [`text`], the one given last wins. Args missing from
+/// the command are left at their defaults.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::Digest;
+///
+/// let cli = Command::new("myapp")
+///     .arg(clap_digest::arg::binary())
+///     .arg(clap_digest::arg::text())
+///     .arg(clap_digest::arg::zero());
+/// let args = cli.get_matches_from(["myapp", "-t", "-bz"]);
+///
+/// let formatter = clap_digest::arg::formatter(&args);
+/// let output = Digest::MD5.hash(b"foo");
+///
+/// assert_eq!(
+///     formatter.line(Digest::MD5, &output, "foo.txt"),
+///     "acbd18db4cc2f85cedef654fccc4a4d8 *foo.txt\0",
+/// );
+/// ```
+#[must_use]
+pub fn formatter(matches: &ArgMatches) -> Formatter {
+    let flag = |id| matches!(matches.try_get_one::<bool>(id), Ok(Some(true)));
+    let index = |id| flag(id).then(|| matches.index_of(id)).flatten();
+
+    let style = if flag("tag") { Style::Bsd } else { Style::Gnu };
+    let mode = if index("binary") > index("text") {
+        Mode::Binary
+    } else {
+        Mode::Text
+    };
+
+    Formatter::new().style(style).mode(mode).zero(flag("zero"))
+}
+
+/// Ids of the args of this module only meaningful when hashing, which
+/// conflict with [`check`].
+const HASHING_ARGS: &[&str] = &[
+    "binary",
+    "list-digests",
+    "output-encoding",
+    "tag",
+    "text",
+    "zero",
+];
+
+/// Returns a ready-to-use [`clap::Arg`] to verify checksum files instead of
+/// hashing.
+///
+/// The checksum files are optional, standard input is read if none is
+/// given. The values are parsed to [`Input`]s. Use [`add_check`] to add the
+/// argument with conflicts to the hashing args of the command.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+/// use clap_digest::hash::Input;
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::check());
+/// let args = cli.get_matches_from(["myapp", "--check"]);
+///
+/// let files = args
+///     .get_many::<Input>("check")
+///     .unwrap()
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(files, [&Input::Stdin]);
+/// ```
+#[must_use]
+pub fn check() -> Arg {
+    Arg::new("check")
+        .short('c')
+        .long("check")
+        .value_name("FILE")
+        .help("read checksums from the FILEs and verify them")
+        .long_help(
+            "Read checksums from the FILEs and verify them, instead of \
+             hashing. Without FILE, or when FILE is -, read standard input.",
+        )
+        .action(ArgAction::Append)
+        .num_args(0..)
+        .default_missing_value("-")
+        .value_parser(OsStringValueParser::new().map(Input::from))
+}
+
+/// Adds [`check`] to the command, conflicting with the args of this module
+/// that are only meaningful when hashing, if the command has them.
+///
+/// Add the hashing args before calling this.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+///
+/// let cli = Command::new("myapp").arg(clap_digest::arg::list_digests());
+/// let cli = clap_digest::arg::add_check(cli);
+///
+/// assert!(cli
+///     .try_get_matches_from(["myapp", "-c", "--list-digests"])
+///     .is_err());
+/// ```
+#[must_use]
+pub fn add_check(cmd: Command) -> Command {
+    let conflicts = HASHING_ARGS
+        .iter()
+        .filter(|id| cmd.get_arguments().any(|arg| arg.get_id() == **id))
+        .copied()
+        .collect::<Vec<_>>();
+
+    cmd.arg(check().conflicts_with_all(conflicts))
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to skip missing files when
+/// verifying checksums.
+///
+/// See [`Verifier::ignore_missing`] and [`verifier`].
+#[must_use]
+pub fn ignore_missing() -> Arg {
+    Arg::new("ignore-missing")
+        .long("ignore-missing")
+        .action(ArgAction::SetTrue)
+        .help("don't fail or report status for missing files")
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to not report successfully verified
+/// files.
+///
+/// See [`Verifier::quiet`] and [`verifier`].
+#[must_use]
+pub fn quiet() -> Arg {
+    Arg::new("quiet")
+        .long("quiet")
+        .action(ArgAction::SetTrue)
+        .help("don't print OK for each successfully verified file")
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to report nothing when verifying
+/// checksums.
+///
+/// See [`Verifier::status`] and [`verifier`].
+#[must_use]
+pub fn status() -> Arg {
+    Arg::new("status")
+        .long("status")
+        .action(ArgAction::SetTrue)
+        .help("don't output anything, status code shows success")
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to fail on improperly formatted
+/// checksum lines.
+///
+/// See [`Verifier::strict`] and [`verifier`].
+#[must_use]
+pub fn strict() -> Arg {
+    Arg::new("strict")
+        .long("strict")
+        .action(ArgAction::SetTrue)
+        .help("exit non-zero for improperly formatted checksum lines")
+}
+
+/// Returns a ready-to-use [`clap::Arg`] to warn about improperly formatted
+/// checksum lines.
+///
+/// See [`Verifier::warn`] and [`verifier`].
+#[must_use]
+pub fn warn() -> Arg {
+    Arg::new("warn")
+        .short('w')
+        .long("warn")
+        .action(ArgAction::SetTrue)
+        .help("warn about improperly formatted checksum lines")
+}
+
+/// Returns a [`Verifier`] configured via [`digest()`] and the verification
+/// args, i.e. [`ignore_missing`], [`quiet`], [`status`], [`strict`] and
+/// [`warn`].
+///
+/// Args missing from the command are left at their defaults.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+///
+/// let cli = Command::new("myapp")
+///     .arg(clap_digest::arg::quiet())
+///     .arg(clap_digest::arg::strict());
+/// let args = cli.get_matches_from(["myapp", "--quiet"]);
+///
+/// let verifier = clap_digest::arg::verifier(&args);
+/// ```
+#[must_use]
+pub fn verifier(matches: &ArgMatches) -> Verifier {
+    let flag = |id| matches!(matches.try_get_one::<bool>(id), Ok(Some(true)));
+
+    let verifier = Verifier::new()
+        .ignore_missing(flag("ignore-missing"))
+        .quiet(flag("quiet"))
+        .status(flag("status"))
+        .strict(flag("strict"))
+        .warn(flag("warn"));
+
+    match matches.try_get_one::<Digest>("digest") {
+        Ok(Some(&digest)) => verifier.digest(digest),
+        _ => verifier,
+    }
+}
+
+/// Ready-to-use [`clap::Args`] implementation combining [`digest()`] and
+/// [`list_digests`].
+///
+/// This is meant for users of the clap derive API, who can flatten it into
+/// their own arguments. The digest is required unless listing digests was
+/// requested.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Parser;
+/// use clap_digest::{arg::DigestArgs, Digest};
+///
+/// #[derive(Parser)]
+/// struct Cli {
+///     #[command(flatten)]
+///     digest: DigestArgs,
+/// }
+///
+/// let cli = Cli::parse_from(["myapp", "--digest", "MD5"]);
+///
+/// assert_eq!(cli.digest.digest(), Some(Digest::MD5));
+/// assert!(!cli.digest.list_requested());
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct DigestArgs {
+    digest: Option<Digest>,
+    list_digests: bool,
+}
+
+impl DigestArgs {
+    /// Returns the chosen digest algorithm.
+    ///
+    /// This is only [`None`] if listing digests was requested.
+    #[must_use]
+    pub const fn digest(&self) -> Option<Digest> {
+        self.digest
+    }
+
+    /// Returns `true` if listing the supported digest algorithms was
+    /// requested.
+    #[must_use]
+    pub const fn list_requested(&self) -> bool {
+        self.list_digests
+    }
+}
+
+impl FromArgMatches for DigestArgs {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        let mut args = Self::default();
+        args.update_from_arg_matches(matches)?;
+        Ok(args)
+    }
+
+    fn update_from_arg_matches(
+        &mut self,
+        matches: &ArgMatches,
+    ) -> Result<(), clap::Error> {
+        if let Some(digest) = matches.get_one::<Digest>("digest") {
+            self.digest = Some(*digest);
+        }
+
+        self.list_digests = matches.get_flag("list-digests");
+
+        Ok(())
+    }
+}
+
+impl Args for DigestArgs {
+    fn augment_args(cmd: Command) -> Command {
+        cmd.arg(digest().required_unless_present("list-digests"))
+            .arg(list_digests())
+    }
+
+    fn augment_args_for_update(cmd: Command) -> Command {
+        cmd.arg(digest()).arg(list_digests())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::Command;
+
+    use crate::encode::Encoding;
+    #[cfg(feature = "md5")]
+    use crate::hash::Input;
+    #[cfg(feature = "md5")]
+    use crate::Digest;
+
+    #[cfg(all(feature = "md5", feature = "sha1", feature = "sha3"))]
+    #[test]
+    fn digest() {
+        let cli = Command::new("myapp").arg(crate::arg::digest());
+        let args = cli.get_matches_from(["myapp", "-d", "sha3-256"]);
+        assert_eq!(args.get_one::<Digest>("digest"), Some(&Digest::SHA3_256));
+
+        let cli = Command::new("myapp").arg(crate::arg::digest());
+        let error = cli
+            .try_get_matches_from(["myapp", "-d", "SHA-257"])
+            .unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::InvalidValue);
+
+        let cli = Command::new("myapp").arg(crate::arg::digests());
+        let args = cli.get_matches_from(["myapp", "-d", "md5,Sha-1"]);
+        assert_eq!(
+            crate::arg::multi_digest(&args)
+                .digests()
+                .collect::<Vec<_>>(),
+            [Digest::MD5, Digest::SHA1]
+        );
+    }
+
+    #[cfg(all(feature = "md4", feature = "md5"))]
+    #[test]
+    fn digests() {
+        use clap::ValueEnum;
+
+        let cli = Command::new("myapp").arg(crate::arg::digests());
+        let args =
+            cli.get_matches_from(["myapp", "-d", "MD5,MD5", "-d", "MD4"]);
+
+        let multi = crate::arg::multi_digest(&args);
+        assert_eq!(
+            multi.digests().collect::<Vec<_>>(),
+            [Digest::MD5, Digest::MD4]
+        );
+
+        let cli = Command::new("myapp").arg(crate::arg::digests());
+        let args = cli.get_matches_from(["myapp"]);
+        assert!(crate::arg::multi_digest(&args).is_empty());
+
+        let cli = Command::new("myapp").arg(crate::arg::digests());
+        let args = cli.get_matches_from(["myapp", "-d", "all,MD5"]);
+        assert_eq!(
+            crate::arg::multi_digest(&args)
+                .digests()
+                .collect::<Vec<_>>(),
+            Digest::value_variants()
+        );
+
+        let cli = Command::new("myapp").arg(crate::arg::digests());
+        let args = cli.get_matches_from(["myapp", "-d", "ALL"]);
+        assert_eq!(
+            crate::arg::multi_digest(&args)
+                .digests()
+                .collect::<Vec<_>>(),
+            Digest::value_variants()
+        );
+    }
+
+    #[test]
+    fn list_digests() {
+        let cli = Command::new("myapp").arg(crate::arg::list_digests());
+        let args = cli.get_matches_from(["myapp", "--list-digests"]);
+        assert!(args.contains_id("list-digests"));
+    }
+
+    #[test]
+    fn encoding_default() {
+        let cli = Command::new("myapp").arg(crate::arg::encoding());
+        let args = cli.get_matches_from(["myapp"]);
+        assert_eq!(
+            args.get_one::<Encoding>("output-encoding"),
+            Some(&Encoding::Hex)
+        );
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn check() {
+        let cli = Command::new("myapp")
+            .arg(crate::arg::encoding())
+            .arg(crate::arg::digest());
+        let cli = crate::arg::add_check(cli);
+
+        let args = cli
+            .clone()
+            .get_matches_from(["myapp", "-c", "-d", "MD5", "-c", "a", "-"]);
+        let files = args.get_many::<Input>("check").unwrap();
+        assert_eq!(
+            files.collect::<Vec<_>>(),
+            [&Input::Stdin, &Input::Path("a".into()), &Input::Stdin]
+        );
+
+        let args = cli.clone().get_matches_from(["myapp"]);
+        assert!(args.get_many::<Input>("check").is_none());
+
+        assert!(cli
+            .try_get_matches_from(["myapp", "-c", "--output-encoding", "hex"])
+            .is_err());
+    }
+
+    #[test]
+    fn output_length() {
+        let cli = Command::new("myapp").arg(crate::arg::output_length());
+
+        let args = cli.clone().get_matches_from(["myapp", "-l", "256"]);
+        assert_eq!(crate::arg::output_len(&args), Some(32));
+
+        let args = cli.clone().get_matches_from(["myapp"]);
+        assert_eq!(crate::arg::output_len(&args), None);
+
+        for bits in ["0", "12", "-8", "x"] {
+            assert!(cli
+                .clone()
+                .try_get_matches_from(["myapp", "-l", bits])
+                .is_err());
+        }
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn xof_hasher() {
+        use crate::xof::Xof;
+
+        let cli = Command::new("myapp").arg(crate::arg::customization());
+
+        let args = cli.clone().get_matches_from(["myapp"]);
+        let hasher = crate::arg::xof_hasher(&args, Xof::SHAKE128).unwrap();
+        assert_eq!(hasher.finalize_output(4), Xof::SHAKE128.hash(b"", 4));
+
+        let args = cli.get_matches_from(["myapp", "--customization", "x"]);
+        assert!(crate::arg::xof_hasher(&args, Xof::SHAKE128).is_none());
+        assert_ne!(
+            crate::arg::xof_hasher(&args, Xof::CSHAKE128)
+                .unwrap()
+                .finalize_output(4),
+            Xof::CSHAKE128.hash(b"", 4)
+        );
+    }
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn blake2_hasher() {
+        use crate::blake2var::Variant;
+
+        let cli = Command::new("myapp")
+            .arg(crate::arg::output_length())
+            .arg(crate::arg::key());
+
+        let args = cli.clone().get_matches_from(["myapp"]);
+        let hasher = crate::arg::blake2_hasher(&args, Variant::Blake2s);
+        assert_eq!(hasher.unwrap().finalize_output().len(), 32);
+
+        let args = cli.clone().get_matches_from(["myapp", "-l", "512"]);
+        assert!(crate::arg::blake2_hasher(&args, Variant::Blake2s).is_err());
+
+        let args = cli.clone().get_matches_from(["myapp", "--key", "00"]);
+        let hasher = crate::arg::blake2_hasher(&args, Variant::Blake2b);
+        assert!(hasher.unwrap().is_keyed());
+
+        assert!(cli.try_get_matches_from(["myapp", "--key", "0"]).is_err());
+    }
+
+    #[test]
+    fn mac_key() {
+        let path = std::env::temp_dir().join("clap-digest-arg-mac-key");
+        std::fs::write(&path, b"from file").unwrap();
+
+        let cli = Command::new("myapp")
+            .arg(crate::arg::hmac_key())
+            .arg(crate::arg::key_file())
+            .arg(crate::arg::key());
+
+        let args = cli.clone().get_matches_from(["myapp"]);
+        assert_eq!(crate::arg::mac_key(&args).unwrap(), None);
+
+        let args = cli.clone().get_matches_from(["myapp", "--key", "6b"]);
+        assert_eq!(crate::arg::mac_key(&args).unwrap().unwrap(), b"k");
+
+        let args = cli.get_matches_from([
+            "myapp",
+            "--hmac-key",
+            "string",
+            "--key-file",
+            path.to_str().unwrap(),
+        ]);
+        assert_eq!(crate::arg::mac_key(&args).unwrap().unwrap(), b"from file");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(crate::arg::mac_key(&args).is_err());
+    }
+
+    #[cfg(any(
+        feature = "argon2",
+        feature = "bcrypt",
+        feature = "pbkdf2",
+        feature = "scrypt"
+    ))]
+    #[test]
+    fn password_params() {
+        use clap::ValueEnum;
+
+        use crate::password::{Params, PasswordHash};
+
+        let cli = Command::new("myapp")
+            .arg(crate::arg::password_hash())
+            .arg(crate::arg::iterations())
+            .arg(crate::arg::memory_cost())
+            .arg(crate::arg::parallelism())
+            .arg(crate::arg::cost());
+
+        let args = cli.clone().get_matches_from(["myapp"]);
+        assert_eq!(crate::arg::password_params(&args), Params::new());
+
+        let algorithm = PasswordHash::value_variants()[0];
+        let args = cli.clone().get_matches_from([
+            "myapp",
+            "--password-hash",
+            algorithm.name(),
+            "--parallelism",
+            "2",
+            "--cost",
+            "10",
+        ]);
+        assert_eq!(
+            args.get_one::<PasswordHash>("password-hash"),
+            Some(&algorithm)
+        );
+        assert_eq!(
+            crate::arg::password_params(&args),
+            Params::new().parallelism(2).cost(10)
+        );
+
+        assert!(cli
+            .try_get_matches_from(["myapp", "--iterations", "0"])
+            .is_err());
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn formatter() {
+        use crate::format::Formatter;
+
+        let cli = Command::new("myapp")
+            .arg(crate::arg::tag())
+            .arg(crate::arg::zero())
+            .arg(crate::arg::binary())
+            .arg(crate::arg::text());
+
+        let output = Digest::MD5.hash(b"foo");
+        let line = |args: &[&str]| {
+            let args = cli.clone().get_matches_from(args);
+            crate::arg::formatter(&args).line(Digest::MD5, &output, "foo")
+        };
+
+        let hex = "acbd18db4cc2f85cedef654fccc4a4d8";
+        assert_eq!(line(&["myapp"]), format!("{hex}  foo\n"));
+        assert_eq!(line(&["myapp", "-b"]), format!("{hex} *foo\n"));
+        assert_eq!(line(&["myapp", "-b", "-t"]), format!("{hex}  foo\n"));
+        assert_eq!(line(&["myapp", "-zt", "-b"]), format!("{hex} *foo\0"));
+        assert_eq!(line(&["myapp", "--tag"]), format!("MD5 (foo) = {hex}\n"));
+
+        let cli = Command::new("myapp").arg(crate::arg::binary());
+        let args = cli.get_matches_from(["myapp"]);
+        assert_eq!(crate::arg::formatter(&args), Formatter::new());
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn verifier() {
+        let cli = Command::new("myapp")
+            .arg(crate::arg::digest())
+            .arg(crate::arg::ignore_missing())
+            .arg(crate::arg::quiet())
+            .arg(crate::arg::status())
+            .arg(crate::arg::strict())
+            .arg(crate::arg::warn());
+
+        let output = crate::hash_file("Cargo.toml", Digest::MD5).unwrap();
+        let content = format!("{output}  Cargo.toml\nfoo\n");
+
+        let args = cli.clone().get_matches_from(["myapp", "-d", "MD5"]);
+        let verifier = crate::arg::verifier(&args);
+        assert!(verifier.verify(&content).passed());
+
+        let args = cli.get_matches_from(["myapp", "-d", "MD5", "--strict"]);
+        let verifier = crate::arg::verifier(&args);
+        assert!(!verifier.verify(&content).passed());
+
+        let cli = Command::new("myapp").arg(crate::arg::status());
+        let args = cli.get_matches_from(["myapp", "--status"]);
+        let verifier = crate::arg::verifier(&args);
+        let report = verifier.verify(&content);
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        verifier.report(&report, &mut out, &mut err).unwrap();
+        assert!(out.is_empty() && err.is_empty());
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn digest_args() {
+        use clap::{Args, FromArgMatches};
+
+        use super::DigestArgs;
+
+        let cli = DigestArgs::augment_args(Command::new("myapp"));
+
+        let args = cli.clone().get_matches_from(["myapp", "-d", "MD5"]);
+        let args = DigestArgs::from_arg_matches(&args).unwrap();
+        assert_eq!(args.digest(), Some(Digest::MD5));
+        assert!(!args.list_requested());
+
+        let args = cli.get_matches_from(["myapp", "--list-digests"]);
+        let args = DigestArgs::from_arg_matches(&args).unwrap();
+        assert_eq!(args.digest(), None);
+        assert!(args.list_requested());
     }
 }
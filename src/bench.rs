@@ -0,0 +1,182 @@
+//! Contains measuring the throughput of digest algorithms, like `openssl
+//! speed`.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use clap_digest::Digest;
+//!
+//! let throughput =
+//!     clap_digest::bench::measure(Digest::SHA256, 1024, Duration::from_millis(10));
+//!
+//! assert_eq!(throughput.digest(), Digest::SHA256);
+//! assert!(throughput.bytes() >= 1024);
+//! println!("{}: {throughput}", throughput.digest());
+//! ```
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use digest::Update;
+
+use crate::{Digest, Hasher};
+
+/// Measured throughput of a digest algorithm.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Throughput {
+    digest: Digest,
+    bytes: u64,
+    elapsed: Duration,
+}
+
+impl Throughput {
+    /// Returns the measured digest algorithm.
+    #[must_use]
+    pub const fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns the number of bytes hashed.
+    #[must_use]
+    pub const fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the time hashing took.
+    #[must_use]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the throughput in bytes per second.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bytes_per_second(&self) -> f64 {
+        self.bytes as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Returns the throughput in megabytes, i.e. 10^6 bytes, per second.
+    #[must_use]
+    pub fn megabytes_per_second(&self) -> f64 {
+        self.bytes_per_second() / 1_000_000.0
+    }
+}
+
+impl fmt::Display for Throughput {
+    /// Formats the throughput as `<n> MB/s`, with two decimals by default.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        write!(f, "{:.precision$} MB/s", self.megabytes_per_second())
+    }
+}
+
+/// Measures the throughput of hashing payloads of this size for about this
+/// duration.
+///
+/// Payloads are hashed until the duration has elapsed, at least once. Every
+/// payload is hashed separately, so small sizes include the finalization
+/// overhead.
+#[must_use]
+pub fn measure(
+    digest: Digest,
+    payload_size: usize,
+    duration: Duration,
+) -> Throughput {
+    #[allow(clippy::cast_possible_truncation)]
+    let payload = (0..payload_size).map(|i| i as u8).collect::<Vec<_>>();
+
+    let mut bytes = 0;
+    let start = Instant::now();
+
+    loop {
+        let mut hasher = Hasher::new(digest);
+        hasher.update(black_box(&payload));
+        black_box(hasher.finalize_output());
+
+        bytes += payload_size as u64;
+
+        if start.elapsed() >= duration {
+            break;
+        }
+    }
+
+    Throughput {
+        digest,
+        bytes,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Measures the throughput of every supported digest algorithm, see
+/// [`measure`].
+///
+/// The results are sorted by throughput, the fastest first.
+#[must_use]
+pub fn compare_all(
+    payload_size: usize,
+    duration: Duration,
+) -> Vec<Throughput> {
+    let mut results = Digest::value_variants()
+        .iter()
+        .map(|&digest| measure(digest, payload_size, duration))
+        .collect::<Vec<_>>();
+
+    results
+        .sort_by(|a, b| b.bytes_per_second().total_cmp(&a.bytes_per_second()));
+
+    results
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use clap::ValueEnum;
+
+    #[cfg(feature = "md5")]
+    use super::Throughput;
+    use crate::Digest;
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn measure() {
+        let duration = Duration::from_millis(5);
+        let throughput = super::measure(Digest::MD5, 100, duration);
+
+        assert_eq!(throughput.digest(), Digest::MD5);
+        assert_eq!(throughput.bytes() % 100, 0);
+        assert!(throughput.elapsed() >= duration);
+        assert!(throughput.bytes_per_second() > 0.0);
+    }
+
+    #[test]
+    fn compare_all() {
+        let results = super::compare_all(64, Duration::from_micros(100));
+
+        assert_eq!(results.len(), Digest::value_variants().len());
+        assert!(results.windows(2).all(|pair| {
+            pair[0].bytes_per_second() >= pair[1].bytes_per_second()
+        }));
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn display() {
+        let throughput = Throughput {
+            digest: Digest::MD5,
+            bytes: 1_500_000,
+            elapsed: Duration::from_millis(500),
+        };
+
+        assert_eq!(throughput.to_string(), "3.00 MB/s");
+        assert_eq!(format!("{throughput:.0}"), "3 MB/s");
+    }
+}
@@ -0,0 +1,401 @@
+//! Contains BLAKE2 with variable output length and optional key.
+//!
+//! Unlike [`Digest::BLAKE2b512`](crate::Digest::BLAKE2b512) and
+//! [`Digest::BLAKE2s256`](crate::Digest::BLAKE2s256), a [`Blake2Var`] outputs
+//! any number of bytes up to the maximum of its [`Variant`], like `b2sum
+//! --length`. With a key, it is a MAC.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::blake2var::{Blake2Var, Variant};
+//! use digest::Update;
+//!
+//! let mut hasher = Blake2Var::new(Variant::Blake2b, 32).unwrap();
+//! hasher.update(b"abc");
+//!
+//! assert_eq!(
+//!     hasher.finalize_output().to_string(),
+//!     "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+//! );
+//! ```
+
+use std::fmt;
+
+use blake2::{Blake2bVarCore, Blake2sVarCore};
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use digest::core_api::{Block, Buffer, UpdateCore, VariableOutputCore};
+use digest::{Reset, Update};
+
+use crate::Output;
+
+/// BLAKE2 variants.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    /// Optimized for 64-bit platforms.
+    Blake2b,
+
+    /// Optimized for 8- to 32-bit platforms.
+    Blake2s,
+}
+
+impl Variant {
+    /// Returns the name of the variant.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Blake2b => "BLAKE2b",
+            Self::Blake2s => "BLAKE2s",
+        }
+    }
+
+    /// Returns the maximum output length in bytes.
+    #[must_use]
+    pub const fn max_output_len(&self) -> usize {
+        match self {
+            Self::Blake2b => 64,
+            Self::Blake2s => 32,
+        }
+    }
+
+    /// Returns the maximum key length in bytes.
+    #[must_use]
+    pub const fn max_key_len(&self) -> usize {
+        self.max_output_len()
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl ValueEnum for Variant {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Blake2b, Self::Blake2s]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+/// Incremental BLAKE2 hasher with variable output length and optional key.
+#[derive(Clone)]
+pub struct Blake2Var {
+    variant: Variant,
+    key: Vec<u8>,
+    output_len: usize,
+    state: State,
+}
+
+impl Blake2Var {
+    /// Returns a new unkeyed hasher with this output length in bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output length is 0 or exceeds
+    /// [`Variant::max_output_len`].
+    pub fn new(variant: Variant, output_len: usize) -> Result<Self, Error> {
+        Self::new_keyed(variant, &[], output_len)
+    }
+
+    /// Returns a new keyed hasher with this output length in bytes.
+    ///
+    /// An empty key is the same as no key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output length is 0 or exceeds
+    /// [`Variant::max_output_len`], or if the key exceeds
+    /// [`Variant::max_key_len`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::blake2var::{Blake2Var, Variant};
+    /// use digest::Update;
+    ///
+    /// let mut hasher = Blake2Var::new_keyed(Variant::Blake2b, b"key", 20)
+    ///     .unwrap();
+    /// hasher.update(b"abc");
+    ///
+    /// assert_eq!(
+    ///     hasher.finalize_output().to_string(),
+    ///     "63c5e9d2e167991e7fca9fe84b4afcb2fab7cc99",
+    /// );
+    /// ```
+    pub fn new_keyed(
+        variant: Variant,
+        key: &[u8],
+        output_len: usize,
+    ) -> Result<Self, Error> {
+        if output_len == 0 || output_len > variant.max_output_len() {
+            return Err(Error::OutputLength(variant, output_len));
+        }
+
+        if key.len() > variant.max_key_len() {
+            return Err(Error::KeyLength(variant, key.len()));
+        }
+
+        Ok(Self {
+            variant,
+            key: key.to_vec(),
+            output_len,
+            state: State::new(variant, key, output_len),
+        })
+    }
+
+    /// Returns the variant of this hasher.
+    #[must_use]
+    pub const fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Returns the output length in bytes.
+    #[must_use]
+    pub const fn output_len(&self) -> usize {
+        self.output_len
+    }
+
+    /// Returns `true` if this hasher has a key.
+    #[must_use]
+    pub fn is_keyed(&self) -> bool {
+        !self.key.is_empty()
+    }
+
+    /// Returns the output, consuming the hasher.
+    #[must_use]
+    pub fn finalize_output(self) -> Output {
+        let len = self.output_len;
+
+        match self.state {
+            State::Blake2b(mut core, mut buffer) => {
+                let mut output = digest::Output::<Blake2bVarCore>::default();
+                core.finalize_variable_core(&mut buffer, &mut output);
+                output[..len].to_vec().into()
+            }
+            State::Blake2s(mut core, mut buffer) => {
+                let mut output = digest::Output::<Blake2sVarCore>::default();
+                core.finalize_variable_core(&mut buffer, &mut output);
+                output[..len].to_vec().into()
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Blake2Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // not showing the key
+        f.debug_struct("Blake2Var")
+            .field("variant", &self.variant)
+            .field("output_len", &self.output_len)
+            .field("keyed", &self.is_keyed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Update for Blake2Var {
+    fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            State::Blake2b(core, buffer) => {
+                buffer
+                    .digest_blocks(data, |blocks| core.update_blocks(blocks));
+            }
+            State::Blake2s(core, buffer) => {
+                buffer
+                    .digest_blocks(data, |blocks| core.update_blocks(blocks));
+            }
+        }
+    }
+}
+
+impl Reset for Blake2Var {
+    fn reset(&mut self) {
+        self.state = State::new(self.variant, &self.key, self.output_len);
+    }
+}
+
+/// Core hashing state, with the padded key as first block if keyed.
+#[derive(Clone)]
+enum State {
+    Blake2b(Blake2bVarCore, Buffer<Blake2bVarCore>),
+    Blake2s(Blake2sVarCore, Buffer<Blake2sVarCore>),
+}
+
+impl State {
+    /// Returns the initial state, the key and output length being valid.
+    fn new(variant: Variant, key: &[u8], output_len: usize) -> Self {
+        match variant {
+            Variant::Blake2b => {
+                let core = Blake2bVarCore::new_with_params(
+                    &[],
+                    &[],
+                    key.len(),
+                    output_len,
+                );
+                let mut block = Block::<Blake2bVarCore>::default();
+                block[..key.len()].copy_from_slice(key);
+                let buffer = if key.is_empty() {
+                    Buffer::<Blake2bVarCore>::default()
+                } else {
+                    Buffer::<Blake2bVarCore>::new(&block)
+                };
+
+                Self::Blake2b(core, buffer)
+            }
+            Variant::Blake2s => {
+                let core = Blake2sVarCore::new_with_params(
+                    &[],
+                    &[],
+                    key.len(),
+                    output_len,
+                );
+                let mut block = Block::<Blake2sVarCore>::default();
+                block[..key.len()].copy_from_slice(key);
+                let buffer = if key.is_empty() {
+                    Buffer::<Blake2sVarCore>::default()
+                } else {
+                    Buffer::<Blake2sVarCore>::new(&block)
+                };
+
+                Self::Blake2s(core, buffer)
+            }
+        }
+    }
+}
+
+/// Error constructing a [`Blake2Var`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The output length in bytes is 0 or exceeds the maximum.
+    OutputLength(Variant, usize),
+
+    /// The key length in bytes exceeds the maximum.
+    KeyLength(Variant, usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutputLength(variant, len) => write!(
+                f,
+                "{variant} output length must be 1 to {} bytes, not {len}",
+                variant.max_output_len()
+            ),
+            Self::KeyLength(variant, len) => write!(
+                f,
+                "{variant} key length must be at most {} bytes, not {len}",
+                variant.max_key_len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use digest::{Reset, Update};
+
+    use super::{Blake2Var, Error, Variant};
+    use crate::Digest;
+
+    #[test]
+    fn hash() {
+        let key32 = (0..32).collect::<Vec<u8>>();
+        let long = [b'x'; 200];
+        let cases = [
+            (
+                Variant::Blake2b,
+                &b""[..],
+                &b"abc"[..],
+                32,
+                "bddd813c634239723171ef3fee98579b\
+                 94964e3bb1cb3e427262c8c068d52319",
+            ),
+            (
+                Variant::Blake2b,
+                b"key",
+                b"abc",
+                20,
+                "63c5e9d2e167991e7fca9fe84b4afcb2fab7cc99",
+            ),
+            (
+                Variant::Blake2b,
+                b"key",
+                b"",
+                16,
+                "1bc5b5f140c8cb507a6b0e4adc292525",
+            ),
+            (
+                Variant::Blake2b,
+                b"key",
+                &long,
+                48,
+                "40ffd0dbefac5ac2bb618580660aba29\
+                 0a1ce525eb8b0fc48df2758ccc96f3b9\
+                 d514d9061b20764320825aaad614bed1",
+            ),
+            (
+                Variant::Blake2s,
+                b"",
+                b"abc",
+                16,
+                "aa4938119b1dc7b87cbad0ffd200d0ae",
+            ),
+            (
+                Variant::Blake2s,
+                &key32,
+                b"abc",
+                32,
+                "a281f725754969a702f6fe36fc591b7d\
+                 ef866e4b70173ece402fc01c064d6b65",
+            ),
+        ];
+
+        for (variant, key, data, len, expected) in cases {
+            let mut hasher = Blake2Var::new_keyed(variant, key, len).unwrap();
+            hasher.update(b"garbage");
+            hasher.reset();
+            hasher.update(data);
+
+            assert_eq!(hasher.finalize_output().to_string(), expected);
+        }
+    }
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn full_length() {
+        let mut hasher = Blake2Var::new(Variant::Blake2b, 64).unwrap();
+        hasher.update(b"abc");
+
+        assert_eq!(hasher.finalize_output(), Digest::BLAKE2b512.hash(b"abc"));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Blake2Var::new(Variant::Blake2s, 33).unwrap_err(),
+            Error::OutputLength(Variant::Blake2s, 33)
+        );
+        assert_eq!(
+            Blake2Var::new(Variant::Blake2b, 0).unwrap_err(),
+            Error::OutputLength(Variant::Blake2b, 0)
+        );
+        assert_eq!(
+            Blake2Var::new_keyed(Variant::Blake2b, &[0; 65], 64)
+                .unwrap_err()
+                .to_string(),
+            "BLAKE2b key length must be at most 64 bytes, not 65"
+        );
+    }
+}
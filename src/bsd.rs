@@ -0,0 +1,71 @@
+//! Contains the mapping of digest algorithms to BSD checksum tags.
+
+use clap::ValueEnum;
+
+use crate::Digest;
+
+impl Digest {
+    /// Returns the tag of BSD-style checksum lines, i.e. `<TAG> (<file>) =
+    /// <hex>`, as written by GNU coreutils with `--tag`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA256.bsd_tag(), "SHA256");
+    /// assert_eq!(Digest::BLAKE2b512.bsd_tag(), "BLAKE2b");
+    /// ```
+    #[must_use]
+    pub const fn bsd_tag(&self) -> &'static str {
+        match self {
+            // GNU coreutils tags BLAKE2b512 as just BLAKE2b
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2b512 => "BLAKE2b",
+
+            _ => self.name(),
+        }
+    }
+
+    /// Returns the digest algorithm for a tag of BSD-style checksum lines.
+    ///
+    /// Besides [`Digest::bsd_tag`], this accepts the digest name and file
+    /// extension, case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_bsd_tag("BLAKE2b"), Some(Digest::BLAKE2b512));
+    /// assert_eq!(Digest::from_bsd_tag("sha256"), Some(Digest::SHA256));
+    /// assert_eq!(Digest::from_bsd_tag("CRC32"), None);
+    /// ```
+    #[must_use]
+    pub fn from_bsd_tag(tag: &str) -> Option<Self> {
+        Self::value_variants()
+            .iter()
+            .find(|digest| digest.bsd_tag() == tag)
+            .copied()
+            .or_else(|| <Self as ValueEnum>::from_str(tag, true).ok())
+            .or_else(|| Self::from_file_extension(tag))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use crate::Digest;
+
+    #[test]
+    fn roundtrip() {
+        for digest in Digest::value_variants() {
+            assert_eq!(Digest::from_bsd_tag(digest.bsd_tag()), Some(*digest));
+        }
+    }
+}
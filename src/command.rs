@@ -0,0 +1,133 @@
+//! Contains a ready-to-use [`clap::Command`] for digest management.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap::Command;
+//!
+//! let cli = Command::new("myapp").subcommand(clap_digest::command::digests());
+//! let args = cli.get_matches_from(["myapp", "digests", "info", "MD5"]);
+//!
+//! match args.subcommand() {
+//!     Some(("digests", args)) => clap_digest::command::run(args)?,
+//!     _ => todo!(),
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{self, Write};
+
+use clap::builder::{Arg, ArgAction};
+use clap::{ArgMatches, Command, ValueEnum};
+
+use crate::arg::DigestChoiceValueParser;
+use crate::DigestChoice;
+
+/// Returns a ready-to-use `digests` [`clap::Command`] with `list` and `info`
+/// subcommands.
+///
+/// Use [`run`] to dispatch the parsed subcommand.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Command;
+///
+/// let cli = Command::new("myapp").subcommand(clap_digest::command::digests());
+/// let args = cli.get_matches_from(["myapp", "digests", "list"]);
+///
+/// assert_eq!(
+///     args.subcommand_matches("digests")
+///         .and_then(|args| args.subcommand_name()),
+///     Some("list")
+/// );
+/// ```
+#[must_use]
+pub fn digests() -> Command {
+    let list = Command::new("list").about("list supported digest algorithms");
+
+    let name = Arg::new("name")
+        .value_name("NAME")
+        .help("digest algorithm")
+        .required(true)
+        .action(ArgAction::Set)
+        .value_parser(DigestChoiceValueParser);
+
+    let info = Command::new("info")
+        .about("show information about a digest algorithm")
+        .arg(name);
+
+    Command::new("digests")
+        .about("digest algorithm management")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(list)
+        .subcommand(info)
+}
+
+/// Runs the subcommand parsed by [`digests`], printing to standard output.
+///
+/// The matches need to be the ones of the `digests` subcommand itself.
+///
+/// # Errors
+///
+/// Returns an error if writing to standard output fails.
+///
+/// # Panics
+///
+/// Panics if the matches do not originate from [`digests`].
+pub fn run(matches: &ArgMatches) -> io::Result<()> {
+    run_to(matches, &mut io::stdout().lock())
+}
+
+fn run_to(matches: &ArgMatches, out: &mut impl Write) -> io::Result<()> {
+    match matches.subcommand() {
+        Some(("list", _)) => {
+            for digest in DigestChoice::value_variants() {
+                writeln!(out, "{digest}")?;
+            }
+        }
+
+        Some(("info", matches)) => {
+            let digest = *matches
+                .get_one::<DigestChoice>("name")
+                .expect("is required via clap");
+
+            let size = digest.output_size();
+
+            writeln!(out, "name:        {digest}")?;
+            writeln!(out, "output size: {size} bytes ({} bits)", size * 8)?;
+        }
+
+        _ => unreachable!("subcommand is required via clap"),
+    }
+
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use clap::Command;
+
+    use super::run_to;
+
+    #[test]
+    fn info() {
+        let cli = Command::new("myapp").subcommand(super::digests());
+        let args =
+            cli.get_matches_from(["myapp", "digests", "info", "sha-256"]);
+        let args = args.subcommand_matches("digests").unwrap();
+
+        let mut out = Vec::new();
+        run_to(args, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "name:        SHA256\noutput size: 32 bytes (256 bits)\n"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Dynamic shell completion of digest algorithm names via [clap_complete].
+//!
+//! [`crate::arg::digest()`] registers [`completer`] and
+//! [`crate::arg::digest_choice`] registers [`choice_completer`]
+//! automatically, so all that is left to do is to hook up
+//! [`clap_complete::CompleteEnv`]:
+//!
+//! ```no_run
+//! use clap::Command;
+//! use clap_complete::CompleteEnv;
+//!
+//! fn cli() -> Command {
+//!     Command::new("myapp").arg(clap_digest::arg::digest())
+//! }
+//!
+//! CompleteEnv::with_factory(cli).complete();
+//!
+//! let args = cli().get_matches();
+//! ```
+//!
+//! [clap_complete]: https://crates.io/crates/clap_complete
+
+use std::ffi::OsStr;
+
+use clap::ValueEnum;
+use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
+
+use crate::{Digest, DigestChoice};
+
+/// Returns a [`clap_complete`] completer for digest algorithm names.
+///
+/// # Examples
+///
+/// ```
+/// use clap::Arg;
+///
+/// let arg = Arg::new("hash").add(clap_digest::completions::completer());
+/// ```
+#[must_use]
+pub fn completer() -> ArgValueCompleter {
+    ArgValueCompleter::new(complete_digest)
+}
+
+/// Completes the digest algorithm names compiled into this binary.
+///
+/// Matching is case-insensitive. Each candidate comes with the digest
+/// description as help.
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+///
+/// let candidates = clap_digest::completions::complete_digest(OsStr::new("md"));
+///
+/// assert!(candidates.iter().any(|c| c.get_value() == "MD5"));
+/// ```
+#[must_use]
+pub fn complete_digest(current: &OsStr) -> Vec<CompletionCandidate> {
+    complete(
+        current,
+        Digest::value_variants()
+            .iter()
+            .map(|digest| (digest.name(), digest.description())),
+    )
+}
+
+/// Returns a [`clap_complete`] completer for built-in and registered custom
+/// digest algorithm names, see [`crate::registry`].
+///
+/// # Examples
+///
+/// ```
+/// use clap::Arg;
+///
+/// let arg =
+///     Arg::new("hash").add(clap_digest::completions::choice_completer());
+/// ```
+#[must_use]
+pub fn choice_completer() -> ArgValueCompleter {
+    ArgValueCompleter::new(complete_digest_choice)
+}
+
+/// Completes the digest algorithm names compiled into this binary and the
+/// registered custom ones, like [`complete_digest`].
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+///
+/// let candidates =
+///     clap_digest::completions::complete_digest_choice(OsStr::new("md"));
+///
+/// assert!(candidates.iter().any(|c| c.get_value() == "MD5"));
+/// ```
+#[must_use]
+pub fn complete_digest_choice(current: &OsStr) -> Vec<CompletionCandidate> {
+    complete(
+        current,
+        DigestChoice::value_variants()
+            .iter()
+            .map(|digest| (digest.name(), digest.description())),
+    )
+}
+
+/// Returns the names starting with the current value, ignoring case, with
+/// their descriptions as help.
+fn complete(
+    current: &OsStr,
+    names: impl Iterator<Item = (&'static str, &'static str)>,
+) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+
+    let current = current.to_lowercase();
+
+    names
+        .filter(|(name, _)| name.to_lowercase().starts_with(&current))
+        .map(|(name, description)| {
+            CompletionCandidate::new(name).help(Some(description.into()))
+        })
+        .collect()
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+
+    use crate::Digest;
+
+    #[test]
+    fn complete_all() {
+        let candidates = super::complete_digest(OsStr::new(""));
+        assert_eq!(candidates.len(), Digest::all_names().len());
+
+        let candidates = super::complete_digest_choice(OsStr::new(""));
+        assert!(Digest::all_names()
+            .iter()
+            .all(|&name| candidates.iter().any(|c| c.get_value() == name)));
+    }
+
+    #[test]
+    fn complete_prefix() {
+        let candidates = super::complete_digest(OsStr::new("sha3"));
+
+        assert!(!candidates.is_empty());
+        assert!(candidates
+            .iter()
+            .all(|c| c.get_value().to_string_lossy().starts_with("SHA3")));
+    }
+}
@@ -0,0 +1,122 @@
+//! Contains the mapping of digest algorithms to coreutils programs.
+
+use std::path::Path;
+
+use crate::Digest;
+
+impl Digest {
+    /// Returns the name of the coreutils program for this digest algorithm,
+    /// if there is one.
+    ///
+    /// BLAKE3 maps to `b3sum`, the reference implementation's program.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA256.coreutils_program(), Some("sha256sum"));
+    /// ```
+    #[must_use]
+    pub const fn coreutils_program(&self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2b512 => Some("b2sum"),
+
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3 => Some("b3sum"),
+
+            #[cfg(feature = "md5")]
+            Self::MD5 => Some("md5sum"),
+
+            #[cfg(feature = "sha1")]
+            Self::SHA1 => Some("sha1sum"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA224 => Some("sha224sum"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => Some("sha256sum"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => Some("sha384sum"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => Some("sha512sum"),
+
+            #[cfg(feature = "sum")]
+            Self::Cksum => Some("cksum"),
+
+            // GNU coreutils sum defaults to the BSD algorithm
+            #[cfg(feature = "sum")]
+            Self::BSDSum => Some("sum"),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the digest algorithm for a coreutils program.
+    ///
+    /// The program may be given as a path, e.g. `argv[0]` of a multi-call
+    /// binary. Directories and extensions like `.exe` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(
+    ///     Digest::from_coreutils_program("/usr/bin/sha256sum"),
+    ///     Some(Digest::SHA256)
+    /// );
+    /// assert_eq!(Digest::from_coreutils_program("ls"), None);
+    /// ```
+    #[must_use]
+    pub fn from_coreutils_program(program: impl AsRef<Path>) -> Option<Self> {
+        use clap::ValueEnum;
+
+        let program = program.as_ref().file_stem()?;
+
+        Self::value_variants()
+            .iter()
+            .find(|digest| {
+                digest
+                    .coreutils_program()
+                    .is_some_and(|name| program == name)
+            })
+            .copied()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use crate::Digest;
+
+    #[test]
+    fn roundtrip() {
+        for digest in Digest::value_variants() {
+            if let Some(program) = digest.coreutils_program() {
+                assert_eq!(
+                    Digest::from_coreutils_program(program),
+                    Some(*digest)
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn exe() {
+        assert_eq!(
+            Digest::from_coreutils_program("md5sum.exe"),
+            Some(Digest::MD5)
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Contains heuristic detection of checksum file formats and algorithms.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::{detect, Digest};
+//!
+//! let content = "\
+//! # SHA256 checksums
+//! b5bb9d8014a0f9b1d61e21e796d78dccdf1352f23cd32812f4850b878ae4944c  foo.txt
+//! ";
+//!
+//! let detection = detect::detect(content).unwrap();
+//!
+//! assert_eq!(detection.format(), detect::Format::Gnu);
+//! assert_eq!(detection.digest(), Some(Digest::SHA256));
+//! ```
+
+use clap::ValueEnum;
+
+use crate::Digest;
+
+/// Checksum file formats.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub enum Format {
+    /// GNU coreutils format, i.e. `<hex>  <file>` or `<hex> *<file>`.
+    Gnu,
+
+    /// BSD tag format, i.e. `<TAG> (<file>) = <hex>`.
+    Bsd,
+}
+
+/// Result of [`detect`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Detection {
+    format: Format,
+    digest: Option<Digest>,
+    candidates: Vec<Digest>,
+}
+
+impl Detection {
+    /// Returns the detected checksum file format.
+    #[must_use]
+    pub const fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the most likely digest algorithm.
+    ///
+    /// This is [`None`] if the candidates could not be narrowed down, or if
+    /// lines use different digest algorithms.
+    #[must_use]
+    pub const fn digest(&self) -> Option<Digest> {
+        self.digest
+    }
+
+    /// Returns all digest algorithms matching the checksum lengths.
+    #[must_use]
+    pub fn candidates(&self) -> &[Digest] {
+        &self.candidates
+    }
+}
+
+/// Digest algorithms preferred in this order if the checksum length is
+/// ambiguous, roughly by how common their checksum files are.
+const PREFERRED: &[&str] = &[
+    "SHA256",
+    "SHA512",
+    "SHA1",
+    "MD5",
+    "SHA384",
+    "SHA224",
+    "BLAKE2b512",
+    "BLAKE3",
+];
+
+/// Detects the format and digest algorithm of checksum file content.
+///
+/// The format is taken from the first checksum line. For the BSD tag format
+/// the algorithm is taken from the tags. For the GNU format, the candidates
+/// are narrowed down by checksum length, then by algorithm names mentioned
+/// in `#` comment lines, and finally by preferring common algorithms.
+///
+/// Returns [`None`] if there are no checksum lines.
+#[must_use]
+pub fn detect(content: &str) -> Option<Detection> {
+    let mut format = None;
+    let mut tags = Vec::new();
+    let mut lengths = Vec::new();
+    let mut hints = Vec::new();
+
+    for line in content.lines().map(str::trim_end) {
+        if let Some(comment) = line.strip_prefix('#') {
+            hints.extend(mentioned_digests(comment));
+        } else if let Some((tag, hex)) = parse_bsd(line) {
+            format.get_or_insert(Format::Bsd);
+            tags.push(Digest::from_bsd_tag(tag));
+            lengths.push(hex.len());
+        } else if let Some(hex) = parse_gnu(line) {
+            format.get_or_insert(Format::Gnu);
+            lengths.push(hex.len());
+        }
+    }
+
+    let format = format?;
+
+    lengths.sort_unstable();
+    lengths.dedup();
+
+    let candidates = Digest::value_variants()
+        .iter()
+        .filter(|digest| lengths.contains(&(digest.output_size() * 2)))
+        .copied()
+        .collect::<Vec<_>>();
+
+    let digest = if format == Format::Bsd {
+        tags.dedup();
+        match tags[..] {
+            [Some(digest)] => Some(digest),
+            _ => None,
+        }
+    } else if lengths.len() == 1 {
+        choose(&candidates, &hints)
+    } else {
+        None
+    };
+
+    Some(Detection {
+        format,
+        digest,
+        candidates,
+    })
+}
+
+fn choose(candidates: &[Digest], hints: &[Digest]) -> Option<Digest> {
+    if let [digest] = candidates {
+        return Some(*digest);
+    }
+
+    let hinted = hints
+        .iter()
+        .find(|digest| candidates.contains(digest))
+        .copied();
+
+    hinted.or_else(|| {
+        PREFERRED
+            .iter()
+            .filter_map(|name| Digest::from_str(name, false).ok())
+            .find(|digest| candidates.contains(digest))
+    })
+}
+
+/// Returns the digest algorithms whose names or file extensions appear as
+/// words in the text.
+fn mentioned_digests(text: &str) -> impl Iterator<Item = Digest> + '_ {
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == ':')
+        .filter(|word| !word.is_empty())
+        .filter_map(|word| {
+            Digest::from_str(word, true)
+                .ok()
+                .or_else(|| Digest::from_file_extension(word))
+        })
+}
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Parses `<TAG> (<file>) = <hex>`, returning tag and hex.
+fn parse_bsd(line: &str) -> Option<(&str, &str)> {
+    let line = line.strip_prefix('\\').unwrap_or(line);
+    let (tag, rest) = line.split_once(" (")?;
+    let (_file, hex) = rest.rsplit_once(") = ")?;
+
+    (!tag.is_empty() && is_hex(hex)).then_some((tag, hex))
+}
+
+/// Parses `<hex>  <file>` or `<hex> *<file>`, returning hex.
+fn parse_gnu(line: &str) -> Option<&str> {
+    let line = line.strip_prefix('\\').unwrap_or(line);
+    let (hex, rest) = line.split_once(' ')?;
+
+    (is_hex(hex) && (rest.starts_with(' ') || rest.starts_with('*')))
+        .then_some(hex)
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::detect;
+    #[cfg(any(feature = "md5", feature = "sha1"))]
+    use super::Format;
+    #[cfg(any(feature = "blake3", feature = "md5", feature = "sha1"))]
+    use crate::Digest;
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn bsd() {
+        let content = "\
+SHA1 (foo) = 0beec7b5ea3f0fdbc95d0dd47f3c5bc275da8a33
+SHA1 (bar) = 62cdb7020ff920e5aa642c3d4066950dd1f01f4d
+";
+
+        let detection = detect(content).unwrap();
+
+        assert_eq!(detection.format(), Format::Bsd);
+        assert_eq!(detection.digest(), Some(Digest::SHA1));
+    }
+
+    #[cfg(all(feature = "md5", feature = "sha1"))]
+    #[test]
+    fn bsd_mixed() {
+        let content = "\
+MD5 (foo) = acbd18db4cc2f85cedef654fccc4a4d8
+SHA1 (foo) = 0beec7b5ea3f0fdbc95d0dd47f3c5bc275da8a33
+";
+
+        let detection = detect(content).unwrap();
+
+        assert_eq!(detection.format(), Format::Bsd);
+        assert_eq!(detection.digest(), None);
+        assert!(detection.candidates().contains(&Digest::MD5));
+        assert!(detection.candidates().contains(&Digest::SHA1));
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn gnu_by_length() {
+        let content = "acbd18db4cc2f85cedef654fccc4a4d8 *foo\n";
+
+        let detection = detect(content).unwrap();
+
+        assert_eq!(detection.format(), Format::Gnu);
+        assert_eq!(detection.digest(), Some(Digest::MD5));
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn gnu_by_hint() {
+        let hash =
+            "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+        let content = format!("# BLAKE3 checksums\n{hash}  foo\n");
+
+        let detection = detect(&content).unwrap();
+
+        assert_eq!(detection.digest(), Some(Digest::BLAKE3));
+    }
+
+    #[test]
+    fn no_checksums() {
+        assert_eq!(detect("# nothing to see here\n"), None);
+    }
+}
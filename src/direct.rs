@@ -0,0 +1,82 @@
+//! Contains the direct I/O read backend of [`crate::hash::hash_file_with`].
+
+use std::fs::OpenOptions;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+use crate::hash::{Error, HashOptions};
+use crate::{Digest, Hasher, Output};
+
+/// Alignment of buffers and file offsets required by `O_DIRECT`.
+const ALIGNMENT: usize = 4096;
+
+/// Returns the digest of the file content read without the page cache, or
+/// `None` if the file system doesn't support direct I/O.
+///
+/// The buffer size and the offset are rounded to multiples of
+/// [`ALIGNMENT`]. Data read before the offset is skipped.
+pub fn hash_file(
+    path: &Path,
+    digest: Digest,
+    options: &mut HashOptions<'_>,
+) -> Result<Option<Output>, Error> {
+    let file = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_DIRECT)
+        .open(path);
+
+    let mut file = match file {
+        Ok(file) => file,
+        Err(e) if e.raw_os_error() == Some(libc::EINVAL) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let len = (options.buffer_size + ALIGNMENT - 1) / ALIGNMENT * ALIGNMENT;
+    let mut storage = vec![0; len + ALIGNMENT];
+    let start = storage.as_ptr().align_offset(ALIGNMENT);
+    let buffer = &mut storage[start..start + len];
+
+    let aligned = options.offset / ALIGNMENT as u64 * ALIGNMENT as u64;
+    file.seek(SeekFrom::Start(aligned))?;
+
+    // UNWRAP: less than the alignment
+    let mut skip = usize::try_from(options.offset - aligned).unwrap();
+    let mut remaining = options.max_bytes.unwrap_or(u64::MAX);
+    let mut hasher = Hasher::new(digest);
+    let mut total = 0;
+
+    while remaining > 0 {
+        options.check_cancelled()?;
+
+        let n = match file.read(buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            // some file systems only reject direct I/O when reading
+            Err(e) if total == 0 && e.raw_os_error() == Some(libc::EINVAL) => {
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let start = skip.min(n);
+        skip -= start;
+
+        let chunk = &buffer[start..n];
+        let chunk = match usize::try_from(remaining) {
+            Ok(remaining) if remaining < chunk.len() => &chunk[..remaining],
+            _ => chunk,
+        };
+
+        options.update(&mut hasher, chunk);
+        remaining -= chunk.len() as u64;
+        total += chunk.len() as u64;
+
+        if !chunk.is_empty() {
+            options.report_progress(total);
+        }
+    }
+
+    Ok(Some(hasher.finalize_output()))
+}
@@ -0,0 +1,414 @@
+//! Contains encodings for digest output.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::{encode, Digest};
+//!
+//! let output = Digest::MD5.hash(b"foo");
+//!
+//! assert_eq!(encode::hex(&output), "acbd18db4cc2f85cedef654fccc4a4d8");
+//! assert_eq!(encode::base64(&output), "rL0Y20zC+Fzt72VPzMSk2A==");
+//! ```
+
+use std::fmt;
+
+use clap::{builder::PossibleValue, ValueEnum};
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+const BASE64: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const BASE64URL: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Supported output encodings.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// Lowercase hex, see [`hex`].
+    Hex,
+
+    /// Uppercase hex, see [`hex_upper`].
+    HexUpper,
+
+    /// Padded standard base64, see [`base64`].
+    Base64,
+
+    /// Unpadded URL-safe base64, see [`base64url_nopad`].
+    Base64Url,
+
+    /// Padded base32, see [`base32`].
+    Base32,
+
+    /// Raw bytes.
+    Binary,
+}
+
+impl Encoding {
+    /// Returns the encoding name.
+    ///
+    /// This is used for both [`std::fmt::Display`] as well as
+    /// [`clap::ValueEnum::to_possible_value`].
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::HexUpper => "HEX",
+            Self::Base64 => "base64",
+            Self::Base64Url => "base64url",
+            Self::Base32 => "base32",
+            Self::Binary => "binary",
+        }
+    }
+
+    /// Returns `true` if the encoding produces raw bytes instead of text.
+    #[must_use]
+    pub const fn is_binary(&self) -> bool {
+        matches!(self, Self::Binary)
+    }
+
+    /// Returns the encoded bytes.
+    ///
+    /// All encodings except [`Encoding::Binary`] produce ASCII text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::encode::Encoding;
+    ///
+    /// assert_eq!(Encoding::Hex.encode(b"foo"), b"666f6f");
+    /// assert_eq!(Encoding::Binary.encode(b"foo"), b"foo");
+    /// ```
+    #[must_use]
+    pub fn encode(&self, bytes: impl AsRef<[u8]>) -> Vec<u8> {
+        let bytes = bytes.as_ref();
+
+        match self {
+            Self::Hex => hex(bytes).into_bytes(),
+            Self::HexUpper => hex_upper(bytes).into_bytes(),
+            Self::Base64 => base64(bytes).into_bytes(),
+            Self::Base64Url => base64url_nopad(bytes).into_bytes(),
+            Self::Base32 => base32(bytes).into_bytes(),
+            Self::Binary => bytes.to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl ValueEnum for Encoding {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::Hex,
+            Self::HexUpper,
+            Self::Base64,
+            Self::Base64Url,
+            Self::Base32,
+            Self::Binary,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+/// Returns the bytes as lowercase hex.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::hex(&[0x00, 0xab, 0xff]), "00abff");
+/// ```
+#[must_use]
+pub fn hex(bytes: impl AsRef<[u8]>) -> String {
+    encode(bytes.as_ref(), HEX, 4, false)
+}
+
+/// Returns the bytes as uppercase hex.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::hex_upper(&[0x00, 0xab, 0xff]), "00ABFF");
+/// ```
+#[must_use]
+pub fn hex_upper(bytes: impl AsRef<[u8]>) -> String {
+    encode(bytes.as_ref(), HEX_UPPER, 4, false)
+}
+
+/// Returns the bytes as padded standard base64 (RFC 4648, section 4).
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::base64(b"fo"), "Zm8=");
+/// ```
+#[must_use]
+pub fn base64(bytes: impl AsRef<[u8]>) -> String {
+    encode(bytes.as_ref(), BASE64, 6, true)
+}
+
+/// Returns the bytes as unpadded URL-safe base64 (RFC 4648, section 5).
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::base64url_nopad(&[0xfb, 0xff]), "-_8");
+/// ```
+#[must_use]
+pub fn base64url_nopad(bytes: impl AsRef<[u8]>) -> String {
+    encode(bytes.as_ref(), BASE64URL, 6, false)
+}
+
+/// Returns the bytes as padded base32 (RFC 4648, section 6).
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::base32(b"fo"), "MZXQ====");
+/// ```
+#[must_use]
+pub fn base32(bytes: impl AsRef<[u8]>) -> String {
+    encode(bytes.as_ref(), BASE32, 5, true)
+}
+
+/// Returns the bytes of lowercase or uppercase hex.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid hex.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::decode_hex("00abFF").unwrap(), [0x00, 0xab, 0xff]);
+/// assert!(clap_digest::encode::decode_hex("abc").is_err());
+/// ```
+pub fn decode_hex(input: &str) -> Result<Vec<u8>, DecodeError> {
+    let input = input.to_ascii_lowercase();
+    decode(&input, HEX, 4)
+}
+
+/// Returns the bytes of padded or unpadded standard base64.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid base64.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::decode_base64("Zm8=").unwrap(), b"fo");
+/// ```
+pub fn decode_base64(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode(input.trim_end_matches('='), BASE64, 6)
+}
+
+/// Returns the bytes of padded or unpadded URL-safe base64.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid URL-safe base64.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::decode_base64url("-_8").unwrap(), [0xfb, 0xff]);
+/// ```
+pub fn decode_base64url(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode(input.trim_end_matches('='), BASE64URL, 6)
+}
+
+/// Returns the bytes of padded or unpadded base32.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid base32.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clap_digest::encode::decode_base32("MZXQ====").unwrap(), b"fo");
+/// ```
+pub fn decode_base32(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode(input.trim_end_matches('='), BASE32, 5)
+}
+
+/// Error decoding encoded digest output.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input contains a character outside of the alphabet.
+    InvalidCharacter(char),
+
+    /// The input length does not correspond to a whole number of bytes.
+    InvalidLength,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => write!(f, "invalid character {c:?}"),
+            Self::InvalidLength => write!(f, "invalid length"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Encodes bytes with an alphabet of `2^bits` characters, most significant
+/// bits first, optionally padding to full blocks with `=`.
+fn encode(bytes: &[u8], alphabet: &[u8], bits: u32, pad: bool) -> String {
+    let mut encoded = String::with_capacity(bytes.len() * 8 / bits as usize);
+
+    let mask = (1 << bits) - 1;
+    let mut buffer = 0_u32;
+    let mut buffered = 0;
+
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        buffered += 8;
+
+        while buffered >= bits {
+            buffered -= bits;
+            let index = (buffer >> buffered) & mask;
+            encoded.push(char::from(alphabet[index as usize]));
+        }
+    }
+
+    if buffered > 0 {
+        let index = (buffer << (bits - buffered)) & mask;
+        encoded.push(char::from(alphabet[index as usize]));
+    }
+
+    if pad {
+        // block of characters encoding a whole number of bytes
+        let block = match bits {
+            5 => 8,
+            6 => 4,
+            _ => 1,
+        };
+
+        while encoded.len() % block != 0 {
+            encoded.push('=');
+        }
+    }
+
+    encoded
+}
+
+/// Decodes unpadded input with an alphabet of `2^bits` characters, the
+/// inverse of [`encode`].
+fn decode(
+    input: &str,
+    alphabet: &[u8],
+    bits: u32,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = Vec::with_capacity(input.len() * bits as usize / 8);
+
+    let mut buffer = 0_u32;
+    let mut buffered = 0;
+
+    for c in input.chars() {
+        let index = u8::try_from(c)
+            .ok()
+            .and_then(|byte| alphabet.iter().position(|&a| a == byte))
+            .ok_or(DecodeError::InvalidCharacter(c))?;
+
+        // UNWRAP: alphabets have at most 64 characters
+        buffer = (buffer << bits) | u32::try_from(index).unwrap();
+        buffered += bits;
+
+        if buffered >= 8 {
+            buffered -= 8;
+            // truncation to the lowest byte is intended
+            #[allow(clippy::cast_possible_truncation)]
+            decoded.push((buffer >> buffered) as u8);
+        }
+    }
+
+    // leftover bits must neither make up a whole character nor be set
+    if buffered >= bits || buffer & ((1 << buffered) - 1) != 0 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    Ok(decoded)
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    // test vectors from RFC 4648, section 10
+    const INPUTS: [&[u8]; 7] =
+        [b"", b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"];
+
+    #[test]
+    fn base64() {
+        let expected = [
+            "", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy",
+        ];
+
+        for (input, expected) in INPUTS.iter().zip(expected) {
+            assert_eq!(super::base64(input), expected);
+            assert_eq!(
+                super::base64url_nopad(input),
+                expected.trim_end_matches('=')
+            );
+            assert_eq!(super::decode_base64(expected).unwrap(), *input);
+        }
+    }
+
+    #[test]
+    fn base32() {
+        let expected = [
+            "",
+            "MY======",
+            "MZXQ====",
+            "MZXW6===",
+            "MZXW6YQ=",
+            "MZXW6YTB",
+            "MZXW6YTBOI======",
+        ];
+
+        for (input, expected) in INPUTS.iter().zip(expected) {
+            assert_eq!(super::base32(input), expected);
+            assert_eq!(super::decode_base32(expected).unwrap(), *input);
+        }
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(super::hex(b"foobar"), "666f6f626172");
+        assert_eq!(super::hex_upper(b"foobar"), "666F6F626172");
+        assert_eq!(super::decode_hex("666F6f626172").unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        use super::DecodeError;
+
+        assert_eq!(
+            super::decode_hex("0g"),
+            Err(DecodeError::InvalidCharacter('g'))
+        );
+        assert_eq!(super::decode_hex("0"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            super::decode_base64("Zh"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(super::decode_base64("Z"), Err(DecodeError::InvalidLength));
+    }
+}
@@ -0,0 +1,160 @@
+//! Contains the mapping of digest algorithms to file names.
+
+use std::path::Path;
+
+use crate::Digest;
+
+/// Defines [`Digest::file_extension`] from the table.
+macro_rules! define_file_extension {
+    (
+        []
+        $(
+            #[cfg(feature = $feature:literal)]
+            $variant:ident {
+                name: $name:literal,
+                description: $description:literal,
+                cryptographic: $cryptographic:literal,
+                hasher: $hasher:ty,
+                output_size: $output_size:literal,
+                extension: $extension:literal,
+            }
+        )*
+    ) => {
+        impl Digest {
+            /// Returns the conventional file extension, without the leading
+            /// dot, for checksum files of this digest algorithm.
+            ///
+            /// [`Digest::BLAKE2b512`] and [`Digest::BLAKE3`] use the `b2` and
+            /// `b3` extensions of their `*sum` programs, all other extensions
+            /// are derived from the digest algorithm name.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use clap_digest::Digest;
+            ///
+            /// assert_eq!(Digest::SHA256.file_extension(), "sha256");
+            /// assert_eq!(Digest::BLAKE2b512.file_extension(), "b2");
+            /// ```
+            #[must_use]
+            pub const fn file_extension(&self) -> &'static str {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant => $extension,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+crate::table::for_each_digest!(define_file_extension);
+
+impl Digest {
+    /// Returns the digest algorithm for a checksum file extension.
+    ///
+    /// The extension is matched case-insensitively, a leading dot is
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use clap_digest::Digest;
+    ///
+    /// let path = Path::new("release.tar.gz.sha256");
+    /// let ext = path.extension().and_then(|ext| ext.to_str()).unwrap();
+    ///
+    /// assert_eq!(Digest::from_file_extension(ext), Some(Digest::SHA256));
+    /// assert_eq!(Digest::from_file_extension(".MD5"), Some(Digest::MD5));
+    /// ```
+    #[must_use]
+    pub fn from_file_extension(ext: &str) -> Option<Self> {
+        use clap::ValueEnum;
+
+        let ext = ext.strip_prefix('.').unwrap_or(ext);
+
+        Self::value_variants()
+            .iter()
+            .find(|digest| digest.file_extension().eq_ignore_ascii_case(ext))
+            .copied()
+    }
+
+    /// Returns the digest algorithm for a conventionally named checksum
+    /// file, e.g. `SHA256SUMS`, `MD5SUMS`, `B2SUMS` or `CHECKSUM.SHA512`.
+    ///
+    /// Names are matched case-insensitively, directories and a `.txt`
+    /// extension are ignored. The algorithm part is matched like
+    /// [`Digest::from_file_extension`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_sums_filename("SHA256SUMS"), Some(Digest::SHA256));
+    /// assert_eq!(Digest::from_sums_filename("dist/b2sums.txt"), Some(Digest::BLAKE2b512));
+    /// assert_eq!(Digest::from_sums_filename("CHECKSUM.SHA512"), Some(Digest::SHA512));
+    /// assert_eq!(Digest::from_sums_filename("README"), None);
+    /// ```
+    #[must_use]
+    pub fn from_sums_filename(path: impl AsRef<Path>) -> Option<Self> {
+        let name = path.as_ref().file_name()?.to_str()?.to_ascii_uppercase();
+        let name = name.strip_suffix(".TXT").unwrap_or(&name);
+
+        let algorithm = name
+            .strip_suffix("SUMS")
+            .or_else(|| name.strip_suffix("SUM"))
+            .or_else(|| name.strip_prefix("CHECKSUMS."))
+            .or_else(|| name.strip_prefix("CHECKSUM."))?;
+
+        Self::from_file_extension(algorithm)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use crate::Digest;
+
+    #[test]
+    fn roundtrip() {
+        for digest in Digest::value_variants() {
+            assert_eq!(
+                Digest::from_file_extension(digest.file_extension()),
+                Some(*digest)
+            );
+        }
+    }
+
+    #[cfg(all(
+        feature = "blake3",
+        feature = "md5",
+        feature = "sha1",
+        feature = "sha2"
+    ))]
+    #[test]
+    fn sums_filenames() {
+        let cases = [
+            ("MD5SUMS", Some(Digest::MD5)),
+            ("SHA1SUMS", Some(Digest::SHA1)),
+            ("SHA512SUM", Some(Digest::SHA512)),
+            ("sha256sums.txt", Some(Digest::SHA256)),
+            ("CHECKSUMS.sha384", Some(Digest::SHA384)),
+            ("B3SUMS", Some(Digest::BLAKE3)),
+            ("SUMS", None),
+            ("CHECKSUM", None),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(Digest::from_sums_filename(name), expected, "{name}");
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Contains formatting of checksum lines, like GNU coreutils.
+//!
+//! Use [`Formatter`] for complete output lines, in any of the styles and
+//! with either line terminator.
+//!
+//! File names containing backslashes, carriage returns or newlines are
+//! escaped like GNU coreutils do: the line starts with `\` and those
+//! characters are written as `\\`, `\r` and `\n`.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::format::{self, Mode};
+//! use clap_digest::Digest;
+//!
+//! let output = Digest::MD5.hash(b"foo");
+//!
+//! assert_eq!(
+//!     format::gnu_line(&output, "foo.txt", Mode::Text),
+//!     "acbd18db4cc2f85cedef654fccc4a4d8  foo.txt",
+//! );
+//! assert_eq!(
+//!     format::bsd_line(Digest::MD5, &output, "foo.txt"),
+//!     "MD5 (foo.txt) = acbd18db4cc2f85cedef654fccc4a4d8",
+//! );
+//! ```
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::{Digest, Output};
+
+/// Mode a file is read in, marked in GNU checksum lines.
+///
+/// There is no difference between the modes on POSIX systems.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default,
+)]
+pub enum Mode {
+    /// Text mode, marked with a space, i.e. `<hex>  <file>`.
+    #[default]
+    Text,
+
+    /// Binary mode, marked with `*`, i.e. `<hex> *<file>`.
+    Binary,
+}
+
+impl Mode {
+    /// Returns the character marking the mode in GNU checksum lines.
+    #[must_use]
+    pub const fn marker(&self) -> char {
+        match self {
+            Self::Text => ' ',
+            Self::Binary => '*',
+        }
+    }
+}
+
+/// Style of checksum lines.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default,
+)]
+pub enum Style {
+    /// GNU checksum lines, see [`gnu_line`].
+    #[default]
+    Gnu,
+
+    /// BSD-style checksum lines, see [`bsd_line`].
+    Bsd,
+}
+
+/// Returns the GNU checksum line `<hex>  <file>` or `<hex> *<file>`, without
+/// line terminator.
+#[must_use]
+pub fn gnu_line(
+    output: &Output,
+    path: impl AsRef<Path>,
+    mode: Mode,
+) -> String {
+    let (prefix, name) = escape(path.as_ref());
+
+    gnu(output, prefix, &name, mode)
+}
+
+/// Returns the BSD-style checksum line `<TAG> (<file>) = <hex>`, without
+/// line terminator.
+///
+/// This is the format GNU coreutils write with `--tag`, see
+/// [`Digest::bsd_tag`].
+#[must_use]
+pub fn bsd_line(
+    digest: Digest,
+    output: &Output,
+    path: impl AsRef<Path>,
+) -> String {
+    let (prefix, name) = escape(path.as_ref());
+
+    bsd(digest, output, prefix, &name)
+}
+
+/// Formats complete checksum lines, like GNU coreutils.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::format::{Formatter, Mode, Style};
+/// use clap_digest::Digest;
+///
+/// let output = Digest::MD5.hash(b"foo");
+///
+/// assert_eq!(
+///     Formatter::new()
+///         .mode(Mode::Binary)
+///         .zero(true)
+///         .line(Digest::MD5, &output, "foo.txt"),
+///     "acbd18db4cc2f85cedef654fccc4a4d8 *foo.txt\0",
+/// );
+/// assert_eq!(
+///     Formatter::new()
+///         .style(Style::Bsd)
+///         .line(Digest::MD5, &output, "foo.txt"),
+///     "MD5 (foo.txt) = acbd18db4cc2f85cedef654fccc4a4d8\n",
+/// );
+/// ```
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default,
+)]
+pub struct Formatter {
+    style: Style,
+    mode: Mode,
+    zero: bool,
+}
+
+impl Formatter {
+    /// Returns a new formatter of newline-terminated GNU checksum lines in
+    /// text mode.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            style: Style::Gnu,
+            mode: Mode::Text,
+            zero: false,
+        }
+    }
+
+    /// Sets the style of the lines.
+    #[must_use]
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the mode marked in GNU checksum lines.
+    #[must_use]
+    pub const fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Terminates lines with NUL instead of newline.
+    ///
+    /// Like GNU coreutils, file names aren't escaped then.
+    #[must_use]
+    pub const fn zero(mut self, zero: bool) -> Self {
+        self.zero = zero;
+        self
+    }
+
+    /// Returns the line terminator, i.e. `\n` or NUL.
+    #[must_use]
+    pub const fn terminator(&self) -> char {
+        if self.zero {
+            '\0'
+        } else {
+            '\n'
+        }
+    }
+
+    /// Returns the complete checksum line, including the line terminator.
+    #[must_use]
+    pub fn line(
+        &self,
+        digest: Digest,
+        output: &Output,
+        path: impl AsRef<Path>,
+    ) -> String {
+        let path = path.as_ref();
+
+        let (prefix, name) = if self.zero {
+            ("", path.display().to_string())
+        } else {
+            escape(path)
+        };
+
+        let mut line = match self.style {
+            Style::Gnu => gnu(output, prefix, &name, self.mode),
+            Style::Bsd => bsd(digest, output, prefix, &name),
+        };
+
+        line.push(self.terminator());
+        line
+    }
+
+    /// Writes the complete checksum line to the writer.
+    ///
+    /// The path may also be an [`Input`](crate::hash::Input), standard input
+    /// being written as `-`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_line(
+        &self,
+        mut writer: impl Write,
+        digest: Digest,
+        output: &Output,
+        path: impl AsRef<Path>,
+    ) -> io::Result<()> {
+        writer.write_all(self.line(digest, output, path).as_bytes())
+    }
+}
+
+/// Returns the GNU checksum line of the already escaped file name.
+fn gnu(output: &Output, prefix: &str, name: &str, mode: Mode) -> String {
+    format!("{prefix}{output} {}{name}", mode.marker())
+}
+
+/// Returns the BSD-style checksum line of the already escaped file name.
+fn bsd(digest: Digest, output: &Output, prefix: &str, name: &str) -> String {
+    format!("{prefix}{} ({name}) = {output}", digest.bsd_tag())
+}
+
+/// Returns the line prefix and the file name, escaped if necessary.
+fn escape(path: &Path) -> (&'static str, String) {
+    let name = path.display().to_string();
+
+    if !name.contains(['\\', '\n', '\r']) {
+        return ("", name);
+    }
+
+    let mut escaped = String::with_capacity(name.len() + 2);
+
+    for c in name.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+
+    ("\\", escaped)
+}
+
+/// Returns the file name with escapes replaced, or [`None`] if there are
+/// invalid escapes.
+pub(crate) fn unescape(name: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next()? {
+                '\\' => unescaped.push('\\'),
+                'n' => unescaped.push('\n'),
+                'r' => unescaped.push('\r'),
+                _ => return None,
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+
+    Some(unescaped)
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sha1")]
+    use super::{bsd_line, gnu_line, unescape, Style};
+    #[cfg(any(feature = "md5", feature = "sha1"))]
+    use super::{Formatter, Mode};
+    #[cfg(feature = "md5")]
+    use crate::hash::Input;
+    #[cfg(any(feature = "md5", feature = "sha1"))]
+    use crate::Digest;
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn lines() {
+        let output = Digest::SHA1.hash(b"foo");
+        let hex = "0beec7b5ea3f0fdbc95d0dd47f3c5bc275da8a33";
+
+        assert_eq!(
+            gnu_line(&output, "dir/foo bar", Mode::Binary),
+            format!("{hex} *dir/foo bar")
+        );
+        assert_eq!(
+            bsd_line(Digest::SHA1, &output, "dir/foo bar"),
+            format!("SHA1 (dir/foo bar) = {hex}")
+        );
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn escaping() {
+        let output = Digest::SHA1.hash(b"foo");
+        let hex = "0beec7b5ea3f0fdbc95d0dd47f3c5bc275da8a33";
+        let name = "a\\b\nc\rd";
+
+        assert_eq!(
+            gnu_line(&output, name, Mode::Text),
+            format!("\\{hex}  a\\\\b\\nc\\rd")
+        );
+        assert_eq!(
+            bsd_line(Digest::SHA1, &output, name),
+            format!("\\SHA1 (a\\\\b\\nc\\rd) = {hex}")
+        );
+
+        assert_eq!(unescape("a\\\\b\\nc\\rd").unwrap(), name);
+        assert_eq!(unescape("a\\x"), None);
+        assert_eq!(unescape("a\\"), None);
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn formatter() {
+        let output = Digest::SHA1.hash(b"foo");
+        let hex = "0beec7b5ea3f0fdbc95d0dd47f3c5bc275da8a33";
+
+        let formatter = Formatter::new();
+        assert_eq!(
+            formatter.line(Digest::SHA1, &output, "a\nb"),
+            format!("\\{hex}  a\\nb\n")
+        );
+
+        let formatter = formatter.zero(true);
+        assert_eq!(
+            formatter.line(Digest::SHA1, &output, "a\nb"),
+            format!("{hex}  a\nb\0")
+        );
+
+        let formatter = formatter.style(Style::Bsd).mode(Mode::Binary);
+        assert_eq!(
+            formatter.line(Digest::SHA1, &output, "a"),
+            format!("SHA1 (a) = {hex}\0")
+        );
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn write_line() {
+        let output = Digest::MD5.hash(b"foo");
+        let mut lines = Vec::new();
+
+        let formatter = Formatter::new().mode(Mode::Binary);
+        for input in [Input::Stdin, Input::from("foo\\bar")] {
+            formatter
+                .write_line(&mut lines, Digest::MD5, &output, input)
+                .unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(lines).unwrap(),
+            "acbd18db4cc2f85cedef654fccc4a4d8 *-\n\
+             \\acbd18db4cc2f85cedef654fccc4a4d8 *foo\\\\bar\n"
+        );
+    }
+}
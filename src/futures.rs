@@ -0,0 +1,311 @@
+//! Contains async hashing helpers and adapters for [`futures_io`] and
+//! [`futures_core`] streams.
+//!
+//! These are the async counterparts of [`crate::hash`] and [`crate::io`] for
+//! runtimes other than Tokio, e.g. async-std and smol. [`hash_stream`] works
+//! with any runtime.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::{futures, Digest};
+//!
+//! let output = ::futures::executor::block_on(futures::hash_reader(
+//!     &b"foo"[..],
+//!     Digest::MD5,
+//! ))?;
+//!
+//! assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::future::poll_fn;
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use digest::Update;
+use futures_core::Stream;
+use futures_io::{AsyncRead, AsyncWrite};
+
+use crate::hash::BUFFER_SIZE;
+use crate::{Digest, Hasher, Output};
+
+/// Returns the digest of everything read from the reader.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub async fn hash_reader(
+    mut reader: impl AsyncRead + Unpin,
+    digest: Digest,
+) -> io::Result<Output> {
+    let mut hasher = Hasher::new(digest);
+    let mut buffer = vec![0; BUFFER_SIZE];
+
+    loop {
+        let read =
+            poll_fn(|cx| Pin::new(&mut reader).poll_read(cx, &mut buffer));
+
+        match read.await {
+            Ok(0) => break,
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(hasher.finalize_output())
+}
+
+/// Returns the digest of all chunks of the stream.
+///
+/// This fits the shape of HTTP bodies, e.g. from reqwest or hyper, so a
+/// payload can be verified while it is received.
+///
+/// # Errors
+///
+/// Returns the first error of the stream.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::{futures, Digest};
+///
+/// let chunks = [Ok::<_, std::io::Error>("f"), Ok("oo")];
+/// let chunks = ::futures::stream::iter(chunks);
+/// let output = ::futures::executor::block_on(futures::hash_stream(
+///     chunks,
+///     Digest::MD5,
+/// ))?;
+///
+/// assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub async fn hash_stream<B, E>(
+    mut stream: impl Stream<Item = Result<B, E>> + Unpin,
+    digest: Digest,
+) -> Result<Output, E>
+where
+    B: AsRef<[u8]>,
+{
+    let mut hasher = Hasher::new(digest);
+
+    while let Some(chunk) =
+        poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await
+    {
+        hasher.update(chunk?.as_ref());
+    }
+
+    Ok(hasher.finalize_output())
+}
+
+/// Async reader that hashes everything read through it.
+///
+/// This is the async counterpart of [`crate::io::HashingReader`].
+#[derive(Clone, Debug)]
+pub struct AsyncHashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R> AsyncHashingReader<R> {
+    /// Wraps the reader, hashing with the digest.
+    pub fn new(inner: R, digest: Digest) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(digest),
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// Data read directly from the inner reader is not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the hasher, e.g. to get an intermediate output via
+    /// [`Hasher::checkpoint`].
+    pub const fn hasher(&self) -> &Hasher {
+        &self.hasher
+    }
+
+    /// Returns the output of the data read so far.
+    #[must_use]
+    pub fn finalize(self) -> Output {
+        self.hasher.finalize_output()
+    }
+
+    /// Returns the inner reader and the output of the data read so far.
+    pub fn into_parts(self) -> (R, Output) {
+        (self.inner, self.hasher.finalize_output())
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncHashingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        let n = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.hasher.update(&buf[..n]);
+
+        Poll::Ready(Ok(n))
+    }
+}
+
+/// Async writer that hashes everything written through it.
+///
+/// This is the async counterpart of [`crate::io::HashingWriter`].
+#[derive(Clone, Debug)]
+pub struct AsyncHashingWriter<W> {
+    inner: W,
+    hasher: Hasher,
+}
+
+impl<W> AsyncHashingWriter<W> {
+    /// Wraps the writer, hashing with the digest.
+    pub fn new(inner: W, digest: Digest) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(digest),
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Data written directly to the inner writer is not hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the hasher, e.g. to get an intermediate output via
+    /// [`Hasher::checkpoint`].
+    pub const fn hasher(&self) -> &Hasher {
+        &self.hasher
+    }
+
+    /// Returns the output of the data written so far.
+    ///
+    /// The inner writer is dropped without flushing it.
+    #[must_use]
+    pub fn finalize(self) -> Output {
+        self.hasher.finalize_output()
+    }
+
+    /// Returns the inner writer and the output of the data written so far.
+    pub fn into_parts(self) -> (W, Output) {
+        (self.inner, self.hasher.finalize_output())
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncHashingWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        let n = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+        this.hasher.update(&buf[..n]);
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use ::futures::executor::block_on;
+    use ::futures::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::{AsyncHashingReader, AsyncHashingWriter};
+    use crate::Digest;
+
+    #[test]
+    fn hash_reader() {
+        let data = (0..=255).cycle().take(100_000).collect::<Vec<u8>>();
+        let output = block_on(super::hash_reader(&data[..], Digest::SHA256));
+
+        assert_eq!(output.unwrap(), Digest::SHA256.hash(&data));
+    }
+
+    #[test]
+    fn hash_stream() {
+        let data = (0..=255).cycle().take(10_000).collect::<Vec<u8>>();
+        let chunks = data.chunks(999).map(Ok::<_, ()>);
+        let output = block_on(super::hash_stream(
+            ::futures::stream::iter(chunks),
+            Digest::SHA256,
+        ));
+
+        assert_eq!(output, Ok(Digest::SHA256.hash(&data)));
+
+        let chunks = [Ok(&b"foo"[..]), Err(42), Ok(&b"bar"[..])];
+        let output = block_on(super::hash_stream(
+            ::futures::stream::iter(chunks),
+            Digest::SHA256,
+        ));
+
+        assert_eq!(output, Err(42));
+    }
+
+    #[test]
+    fn reader() {
+        let data = (0..=255).cycle().take(10_000).collect::<Vec<u8>>();
+        let mut reader = AsyncHashingReader::new(&data[..], Digest::SHA256);
+
+        let mut content = Vec::new();
+        block_on(reader.read_to_end(&mut content)).unwrap();
+
+        assert_eq!(content, data);
+        assert_eq!(reader.finalize(), Digest::SHA256.hash(&data));
+    }
+
+    #[test]
+    fn writer() {
+        let data = (0..=255).cycle().take(10_000).collect::<Vec<u8>>();
+        let mut writer = AsyncHashingWriter::new(Vec::new(), Digest::SHA256);
+
+        block_on(async {
+            writer.write_all(&data).await.unwrap();
+            writer.flush().await.unwrap();
+        });
+
+        let (content, output) = writer.into_parts();
+        assert_eq!(content, data);
+        assert_eq!(output, Digest::SHA256.hash(&data));
+    }
+}
@@ -0,0 +1,280 @@
+//! Contains hashing of Git objects, like `git hash-object`.
+//!
+//! Git hashes the object content with a header prepended, i.e. `<type>
+//! <length>\0`. Repositories use SHA1 or, with the newer object format,
+//! SHA256, see [`ObjectFormat`].
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::git::{self, ObjectFormat};
+//!
+//! let id = git::hash_blob(b"foo\n", ObjectFormat::Sha1.digest());
+//!
+//! assert_eq!(id.to_string(), "257cc5642cb1a054f08cc83f2d943e56fd3ebe99");
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use digest::Update;
+
+use crate::hash::BUFFER_SIZE;
+use crate::{Digest, Hasher, Output};
+
+/// Object format of a Git repository, i.e. its digest algorithm.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "sha1", derive(Default))]
+pub enum ObjectFormat {
+    /// SHA1, the traditional object format.
+    #[cfg(feature = "sha1")]
+    #[cfg_attr(feature = "sha1", default)]
+    Sha1,
+
+    /// SHA256, the newer object format.
+    #[cfg(feature = "sha2")]
+    Sha256,
+}
+
+impl ObjectFormat {
+    /// Returns the digest algorithm of the object format.
+    #[must_use]
+    pub const fn digest(self) -> Digest {
+        match self {
+            #[cfg(feature = "sha1")]
+            Self::Sha1 => Digest::SHA1,
+
+            #[cfg(feature = "sha2")]
+            Self::Sha256 => Digest::SHA256,
+        }
+    }
+
+    /// Returns the object format with the digest algorithm, or [`None`] if
+    /// Git doesn't support it.
+    #[must_use]
+    pub const fn from_digest(digest: Digest) -> Option<Self> {
+        match digest {
+            #[cfg(feature = "sha1")]
+            Digest::SHA1 => Some(Self::Sha1),
+
+            #[cfg(feature = "sha2")]
+            Digest::SHA256 => Some(Self::Sha256),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+}
+
+impl From<ObjectFormat> for Digest {
+    fn from(format: ObjectFormat) -> Self {
+        format.digest()
+    }
+}
+
+/// Type of a Git object.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum ObjectType {
+    /// File content.
+    Blob,
+
+    /// Directory listing.
+    Tree,
+
+    /// Commit.
+    Commit,
+
+    /// Annotated tag.
+    Tag,
+}
+
+impl ObjectType {
+    /// Returns the name of the type in object headers.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Blob => "blob",
+            Self::Tree => "tree",
+            Self::Commit => "commit",
+            Self::Tag => "tag",
+        }
+    }
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Returns the object ID of the object content.
+#[must_use]
+pub fn hash_object(data: &[u8], kind: ObjectType, digest: Digest) -> Output {
+    let mut hasher = header(kind, data.len() as u64, digest);
+    hasher.update(data);
+    hasher.finalize_output()
+}
+
+/// Returns the object ID of the blob, i.e. file content.
+#[must_use]
+pub fn hash_blob(data: &[u8], digest: Digest) -> Output {
+    hash_object(data, ObjectType::Blob, digest)
+}
+
+/// Returns the object ID of the object content read, which must have this
+/// length, as the header comes first.
+///
+/// # Errors
+///
+/// Returns an error if reading fails, or with
+/// [`io::ErrorKind::InvalidData`] if the length doesn't match.
+pub fn hash_object_reader(
+    mut reader: impl Read,
+    len: u64,
+    kind: ObjectType,
+    digest: Digest,
+) -> io::Result<Output> {
+    let mut hasher = header(kind, len, digest);
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut total = 0;
+
+    loop {
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        hasher.update(&buffer[..n]);
+        total += n as u64;
+    }
+
+    if total != len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("expected {len} bytes, read {total}"),
+        ));
+    }
+
+    Ok(hasher.finalize_output())
+}
+
+/// Returns the blob object ID of the file content, like `git hash-object
+/// <file>` without filters.
+///
+/// # Errors
+///
+/// Returns an error if opening or reading the file fails, or if it changes
+/// size while reading.
+pub fn hash_blob_file(
+    path: impl AsRef<Path>,
+    digest: Digest,
+) -> io::Result<Output> {
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    hash_object_reader(file, len, ObjectType::Blob, digest)
+}
+
+/// Returns a hasher with the object header hashed.
+fn header(kind: ObjectType, len: u64, digest: Digest) -> Hasher {
+    let mut hasher = Hasher::new(digest);
+    hasher.update(format!("{kind} {len}\0").as_bytes());
+    hasher
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "sha1"))]
+mod tests {
+    #[cfg(feature = "sha2")]
+    use super::ObjectFormat;
+    use super::ObjectType;
+    use crate::Digest;
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn hash_blob() {
+        let cases = [
+            (
+                ObjectFormat::Sha1,
+                &b""[..],
+                "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+            ),
+            (
+                ObjectFormat::Sha1,
+                b"foo\n",
+                "257cc5642cb1a054f08cc83f2d943e56fd3ebe99",
+            ),
+            (
+                ObjectFormat::Sha256,
+                b"",
+                "473a0f4c3be8a93681a267e3b1e9a7dc\
+                 da1185436fe141f7749120a303721813",
+            ),
+            (
+                ObjectFormat::Sha256,
+                b"foo\n",
+                "47d6aca82756ff2e61e53520bfdf1faa\
+                 6c86d933be4854eb34840c57d12e0c85",
+            ),
+        ];
+
+        for (format, data, expected) in cases {
+            let digest = format.digest();
+
+            assert_eq!(super::hash_blob(data, digest).to_string(), expected);
+            assert_eq!(
+                super::hash_object_reader(
+                    data,
+                    data.len() as u64,
+                    ObjectType::Blob,
+                    digest
+                )
+                .unwrap()
+                .to_string(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn hash_object_reader_len() {
+        let error = super::hash_object_reader(
+            &b"foo"[..],
+            4,
+            ObjectType::Blob,
+            Digest::SHA1,
+        )
+        .unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn hash_blob_file() {
+        let data = std::fs::read("Cargo.toml").unwrap();
+
+        assert_eq!(
+            super::hash_blob_file("Cargo.toml", Digest::SHA1).unwrap(),
+            super::hash_blob(&data, Digest::SHA1)
+        );
+    }
+
+    #[cfg(all(feature = "md5", feature = "sha2"))]
+    #[test]
+    fn object_format() {
+        assert_eq!(ObjectFormat::default(), ObjectFormat::Sha1);
+        assert_eq!(
+            ObjectFormat::from_digest(Digest::SHA256),
+            Some(ObjectFormat::Sha256)
+        );
+        assert_eq!(ObjectFormat::from_digest(Digest::MD5), None);
+        assert_eq!(Digest::from(ObjectFormat::Sha1), Digest::SHA1);
+    }
+}
@@ -0,0 +1,190 @@
+//! Contains gitignore-style glob patterns.
+
+/// Gitignore-style pattern matched against `/`-separated relative paths.
+///
+/// Like in `.gitignore` files:
+///
+/// - `*` matches anything but `/`, `?` matches one character but `/`, and
+///   `[...]` matches one character of a class, e.g. `[a-z]` or `[!0-9]`.
+/// - `**` matches anything, including `/`, so `**/` matches any number of
+///   leading directories.
+/// - A pattern with a `/` at the start or in the middle is anchored at the
+///   root, otherwise it matches the file name at any depth.
+/// - A pattern with a trailing `/` only matches directories.
+/// - A pattern starting with `!` re-includes what it matches.
+/// - `\` escapes the next character.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern {
+    glob: Vec<char>,
+    anchored: bool,
+    dir_only: bool,
+    negated: bool,
+}
+
+impl Pattern {
+    /// Parses the pattern.
+    pub fn new(pattern: &str) -> Self {
+        let (negated, pattern) = pattern
+            .strip_prefix('!')
+            .map_or((false, pattern), |pattern| (true, pattern));
+
+        let (dir_only, pattern) = pattern
+            .strip_suffix('/')
+            .map_or((false, pattern), |pattern| (true, pattern));
+
+        let anchored = pattern.contains('/');
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+
+        Self {
+            glob: pattern.chars().collect(),
+            anchored,
+            dir_only,
+            negated,
+        }
+    }
+
+    /// Returns `true` if the pattern re-includes what it matches.
+    pub const fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// Returns `true` if the pattern matches the `/`-separated relative
+    /// path.
+    pub fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+
+        let path = if self.anchored {
+            path
+        } else {
+            path.rsplit('/').next().unwrap_or(path)
+        };
+
+        glob_match(&self.glob, &path.chars().collect::<Vec<_>>())
+    }
+}
+
+/// Returns `true` if the glob matches the whole text.
+fn glob_match(glob: &[char], text: &[char]) -> bool {
+    match glob {
+        [] => text.is_empty(),
+        ['*', '*', rest @ ..] => {
+            if let ['/', after @ ..] = rest {
+                if glob_match(after, text) {
+                    return true;
+                }
+            }
+
+            (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+        }
+        ['*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != '/')
+            .any(|i| glob_match(rest, &text[i..])),
+        ['?', rest @ ..] => match text {
+            [c, text @ ..] if *c != '/' => glob_match(rest, text),
+            _ => false,
+        },
+        ['[', class @ ..] => match (text, class_match(class, text.first())) {
+            ([_, text @ ..], Some((true, rest))) => glob_match(rest, text),
+            (_, None) => literal_match('[', class, text),
+            _ => false,
+        },
+        ['\\', c, rest @ ..] | [c, rest @ ..] => literal_match(*c, rest, text),
+    }
+}
+
+/// Returns `true` if the text starts with the character and the rest of
+/// the glob matches the rest of the text.
+fn literal_match(c: char, glob: &[char], text: &[char]) -> bool {
+    match text {
+        [first, text @ ..] if *first == c => glob_match(glob, text),
+        _ => false,
+    }
+}
+
+/// Matches the character against the class after `[`, returning whether it
+/// matched and the glob after the closing `]`, or [`None`] if the class is
+/// unterminated.
+fn class_match<'a>(
+    class: &'a [char],
+    c: Option<&char>,
+) -> Option<(bool, &'a [char])> {
+    let (negated, class) = match class {
+        ['!' | '^', class @ ..] => (true, class),
+        _ => (false, class),
+    };
+
+    // a `]` right after the opening bracket is part of the class
+    let end = class
+        .iter()
+        .skip(1)
+        .position(|&c| c == ']')
+        .map(|i| i + 1)?;
+    let (members, rest) = (&class[..end], &class[end + 1..]);
+
+    let Some(&c) = c.filter(|&&c| c != '/') else {
+        return Some((false, rest));
+    };
+
+    let mut matched = false;
+    let mut i = 0;
+
+    while i < members.len() {
+        if i + 2 < members.len() && members[i + 1] == '-' {
+            matched |= (members[i]..=members[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= members[i] == c;
+            i += 1;
+        }
+    }
+
+    Some((matched != negated, rest))
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+
+    #[test]
+    fn matches() {
+        let cases = [
+            ("*.rs", "src/lib.rs", false, true),
+            ("*.rs", "src/lib.rsx", false, false),
+            ("target", "target", true, true),
+            ("target", "sub/target", true, true),
+            ("target/", "target", false, false),
+            ("/target", "sub/target", true, false),
+            ("src/*.rs", "src/lib.rs", false, true),
+            ("src/*.rs", "src/a/lib.rs", false, false),
+            ("src/**/*.rs", "src/lib.rs", false, true),
+            ("src/**/*.rs", "src/a/b/lib.rs", false, true),
+            ("**/foo", "foo", false, true),
+            ("**/foo", "a/b/foo", false, true),
+            ("a/**", "a/b/c", false, true),
+            ("?.txt", "a.txt", false, true),
+            ("?.txt", "ab.txt", false, false),
+            ("[a-c].txt", "b.txt", false, true),
+            ("[!a-c].txt", "b.txt", false, false),
+            ("[]].txt", "].txt", false, true),
+            ("[a", "[a", false, true),
+            ("\\*", "*", false, true),
+            ("\\*", "a", false, false),
+        ];
+
+        for (pattern, path, is_dir, expected) in cases {
+            assert_eq!(
+                Pattern::new(pattern).matches(path, is_dir),
+                expected,
+                "{pattern} {path}"
+            );
+        }
+
+        assert!(Pattern::new("!*.rs").is_negated());
+    }
+}
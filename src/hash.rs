@@ -0,0 +1,977 @@
+//! Contains streaming hashing helpers.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::{hash, Digest};
+//!
+//! let output = hash::hash_reader(&b"foo"[..], Digest::MD5)?;
+//!
+//! assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{Digest, Hasher, Output};
+
+/// Default size of the buffer used for reading.
+pub(crate) const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Minimum size of the buffer used for reading with multiple jobs.
+#[cfg(feature = "rayon")]
+const PARALLEL_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Returns the digest of the bytes.
+///
+/// This is the same as [`Digest::hash`].
+#[must_use]
+pub fn hash_bytes(bytes: &[u8], digest: Digest) -> Output {
+    digest.hash(bytes)
+}
+
+/// Returns the digest of everything read from the reader.
+///
+/// The reader is read in chunks until EOF, so it doesn't need to be
+/// buffered.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub fn hash_reader(reader: impl Read, digest: Digest) -> io::Result<Output> {
+    Ok(hash_reader_with(
+        reader,
+        digest,
+        &mut HashOptions::default(),
+    )?)
+}
+
+/// Returns the digest of everything read from the reader, using the options.
+///
+/// The file-specific options, e.g. memory mapping, don't apply to readers.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or hashing is cancelled.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::hash::{self, HashOptions};
+/// use clap_digest::Digest;
+///
+/// let mut options = HashOptions::new().buffer_size(1024 * 1024);
+/// let output =
+///     hash::hash_reader_with(&b"foo"[..], Digest::MD5, &mut options)?;
+///
+/// assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn hash_reader_with(
+    mut reader: impl Read,
+    digest: Digest,
+    options: &mut HashOptions<'_>,
+) -> Result<Output, Error> {
+    io::copy(&mut (&mut reader).take(options.offset), &mut io::sink())?;
+
+    read_to_end(reader, digest, options)
+}
+
+/// Returns the digest of everything read from the reader, limited to the
+/// maximum number of bytes of the options.
+fn read_to_end(
+    reader: impl Read,
+    digest: Digest,
+    options: &mut HashOptions<'_>,
+) -> Result<Output, Error> {
+    let mut reader = reader.take(options.max_bytes.unwrap_or(u64::MAX));
+    let mut hasher = Hasher::new(digest);
+    let mut buffer = vec![0; options.buffer_size];
+    let mut total = 0;
+
+    loop {
+        options.check_cancelled()?;
+
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                options.update(&mut hasher, &buffer[..n]);
+                total += n as u64;
+                options.report_progress(total);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(hasher.finalize_output())
+}
+
+/// Returns the digest of the file content.
+///
+/// The file is read in fixed-size chunks, so it may contain arbitrary binary
+/// data and be larger than the available memory. This is re-exported as
+/// [`crate::hash_file`].
+///
+/// With the `io-uring` feature on Linux, the file is read via `io_uring`, so
+/// reading the next chunk overlaps with hashing the current one. If `io_uring`
+/// is not available, e.g. on older kernels, this falls back to buffered
+/// reads, as on all other platforms.
+///
+/// # Errors
+///
+/// Returns an error if opening or reading the file fails.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::{hash, Digest};
+///
+/// let output = hash::hash_file("Cargo.toml", Digest::SHA256)?;
+///
+/// assert_eq!(output.len(), 32);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn hash_file(
+    path: impl AsRef<Path>,
+    digest: Digest,
+) -> io::Result<Output> {
+    Ok(hash_file_with(path, digest, &mut HashOptions::default())?)
+}
+
+/// Returns the digest of the file content, using the options.
+///
+/// See [`HashOptions`] for how the file is read.
+///
+/// # Errors
+///
+/// Returns an error if opening or reading the file fails or hashing is
+/// cancelled.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::hash::{self, HashOptions};
+/// use clap_digest::Digest;
+///
+/// let mut options = HashOptions::new().direct_io(true);
+/// let output =
+///     hash::hash_file_with("Cargo.toml", Digest::SHA256, &mut options)?;
+///
+/// assert_eq!(output, hash::hash_file("Cargo.toml", Digest::SHA256)?);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn hash_file_with(
+    path: impl AsRef<Path>,
+    digest: Digest,
+    options: &mut HashOptions<'_>,
+) -> Result<Output, Error> {
+    let path = path.as_ref();
+
+    #[cfg(feature = "mmap")]
+    if options.mmap {
+        options.check_cancelled()?;
+
+        let file = File::open(path)?;
+
+        // SAFETY: see hash_file_mmap
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        let data = options.range_of(&map);
+        let mut hasher = Hasher::new(digest);
+        options.update(&mut hasher, data);
+
+        if !data.is_empty() {
+            options.report_progress(data.len() as u64);
+        }
+
+        return Ok(hasher.finalize_output());
+    }
+
+    #[cfg(all(feature = "direct-io", target_os = "linux"))]
+    if options.direct_io {
+        if let Some(output) = crate::direct::hash_file(path, digest, options)?
+        {
+            return Ok(output);
+        }
+    }
+
+    let mut file = File::open(path)?;
+
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    if let Some(output) = crate::uring::hash_file(&file, digest, options)? {
+        return Ok(output);
+    }
+
+    file.seek(SeekFrom::Start(options.offset))?;
+
+    read_to_end(file, digest, options)
+}
+
+/// Returns the digests of the files' contents, hashing them in parallel.
+///
+/// The results are in the same order as the paths. A failure to hash one
+/// file doesn't affect the others, so a checksum tool can report each error
+/// and carry on.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::{hash, Digest};
+///
+/// let results =
+///     hash::hash_files_parallel(&["Cargo.toml", "missing"], Digest::SHA256);
+///
+/// assert_eq!(results[0].as_ref().unwrap().len(), 32);
+/// assert!(results[1].is_err());
+/// ```
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn hash_files_parallel<P>(
+    paths: &[P],
+    digest: Digest,
+) -> Vec<io::Result<Output>>
+where
+    P: AsRef<Path> + Sync,
+{
+    use rayon::prelude::*;
+
+    paths
+        .par_iter()
+        .map(|path| hash_file(path, digest))
+        .collect()
+}
+
+/// Returns the digest of the file content, reading it via a memory map.
+///
+/// For large files on local disks this is usually faster than
+/// [`hash_file`], because the whole content is fed to the hasher at once
+/// without copying it into a buffer first.
+///
+/// The file must not be modified while it is hashed. Truncating a mapped
+/// file may even terminate the process, so prefer [`hash_file`] when the
+/// file may be changed concurrently, e.g. on network file systems.
+///
+/// # Errors
+///
+/// Returns an error if opening or mapping the file fails.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::{hash, Digest};
+///
+/// let output = hash::hash_file_mmap("Cargo.toml", Digest::SHA256)?;
+///
+/// assert_eq!(output, hash::hash_file("Cargo.toml", Digest::SHA256)?);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "mmap")]
+pub fn hash_file_mmap(
+    path: impl AsRef<Path>,
+    digest: Digest,
+) -> io::Result<Output> {
+    let file = File::open(path)?;
+
+    // SAFETY: The map is read-only and dropped before returning. Concurrent
+    // modification of the file is documented above as unsupported.
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+
+    Ok(digest.hash(&map))
+}
+
+/// Returns the digest of the input.
+///
+/// # Errors
+///
+/// Returns an error if opening or reading the input fails.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::{hash, Digest};
+///
+/// let input = hash::Input::from("Cargo.toml");
+/// let output = hash::hash_input(&input, Digest::SHA256)?;
+///
+/// assert_eq!(output, hash::hash_file("Cargo.toml", Digest::SHA256)?);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn hash_input(input: &Input, digest: Digest) -> io::Result<Output> {
+    match input {
+        Input::Path(path) => hash_file(path, digest),
+        Input::Stdin => hash_reader(io::stdin().lock(), digest),
+    }
+}
+
+/// Options for reading the data to hash.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::hash::HashOptions;
+///
+/// let options = HashOptions::new().buffer_size(1024 * 1024).direct_io(true);
+/// ```
+pub struct HashOptions<'a> {
+    pub(crate) buffer_size: usize,
+    #[cfg(feature = "mmap")]
+    mmap: bool,
+    direct_io: bool,
+    pub(crate) offset: u64,
+    pub(crate) max_bytes: Option<u64>,
+    #[cfg(feature = "rayon")]
+    jobs: usize,
+    #[cfg(feature = "rayon")]
+    pool: Option<Arc<rayon::ThreadPool>>,
+    progress: Option<Box<dyn FnMut(u64) + Send + 'a>>,
+    cancel: Option<CancelToken>,
+}
+
+impl<'a> HashOptions<'a> {
+    /// Returns the default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the size of the buffer used for reading.
+    ///
+    /// Defaults to 64 KiB. A size of zero is treated as one byte.
+    #[must_use]
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size.max(1);
+        self
+    }
+
+    /// Sets whether files are memory-mapped instead of read.
+    ///
+    /// Defaults to `false`. This takes precedence over direct I/O. See
+    /// [`hash_file_mmap`] for the caveats.
+    #[cfg(feature = "mmap")]
+    #[must_use]
+    pub const fn mmap(mut self, enabled: bool) -> Self {
+        self.mmap = enabled;
+        self
+    }
+
+    /// Sets whether files are read with direct I/O, bypassing the page
+    /// cache.
+    ///
+    /// Defaults to `false`. This avoids evicting other data from the page
+    /// cache when hashing large files once. It is only a hint: direct I/O is
+    /// only used on Linux with the `direct-io` feature and falls back to
+    /// buffered reads if the file system doesn't support it.
+    #[must_use]
+    pub const fn direct_io(mut self, enabled: bool) -> Self {
+        self.direct_io = enabled;
+        self
+    }
+
+    /// Sets the number of bytes to skip before hashing.
+    ///
+    /// Defaults to `0`. Files are seeked, other readers are read and the
+    /// data is discarded. If the data is shorter, nothing is hashed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::hash::{self, HashOptions};
+    /// use clap_digest::Digest;
+    ///
+    /// let data = b"barfoobaz";
+    /// let mut options = HashOptions::new().offset(3).max_bytes(3);
+    /// let output = hash::hash_reader_with(&data[..], Digest::MD5, &mut options)?;
+    ///
+    /// assert_eq!(output, Digest::MD5.hash(b"foo"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the maximum number of bytes to hash.
+    ///
+    /// Defaults to no limit, i.e. everything up to EOF is hashed.
+    #[must_use]
+    pub const fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets the number of threads used to hash a single input, `0` meaning
+    /// one per CPU core.
+    ///
+    /// Defaults to `1`. Only BLAKE3 supports hashing with multiple threads,
+    /// other digests ignore this. With multiple jobs, the buffer size is
+    /// raised to at least 1 MiB, because BLAKE3 only parallelizes large
+    /// chunks.
+    ///
+    /// See [`crate::arg::jobs`] for a ready-to-use argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::hash::{self, HashOptions};
+    /// use clap_digest::Digest;
+    ///
+    /// let mut options = HashOptions::new().jobs(0);
+    /// let output =
+    ///     hash::hash_file_with("Cargo.toml", Digest::BLAKE3, &mut options)?;
+    ///
+    /// assert_eq!(output, hash::hash_file("Cargo.toml", Digest::BLAKE3)?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+
+        // the global pool already has one thread per core
+        self.pool = if jobs > 1 {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build()
+                .ok()
+                .map(Arc::new)
+        } else {
+            None
+        };
+
+        if jobs != 1 {
+            self.buffer_size = self.buffer_size.max(PARALLEL_BUFFER_SIZE);
+        }
+
+        self
+    }
+
+    /// Sets a callback that is called with the total number of bytes hashed
+    /// so far, after each chunk.
+    ///
+    /// This can be used to drive progress bars. The callback is called at
+    /// least once for non-empty data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::hash::{self, HashOptions};
+    /// use clap_digest::Digest;
+    ///
+    /// let data = vec![0; 1000];
+    /// let mut hashed = 0;
+    ///
+    /// let mut options = HashOptions::new()
+    ///     .buffer_size(300)
+    ///     .progress(|total| hashed = total);
+    /// hash::hash_reader_with(&data[..], Digest::SHA256, &mut options)?;
+    /// drop(options);
+    ///
+    /// assert_eq!(hashed, 1000);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn progress(mut self, progress: impl FnMut(u64) + Send + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Sets a token to cancel hashing from another thread, e.g. a Ctrl-C
+    /// handler.
+    ///
+    /// Cancellation is checked before each chunk is read. Hashing then stops
+    /// with [`Error::Cancelled`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::hash::{self, CancelToken, Error, HashOptions};
+    /// use clap_digest::Digest;
+    ///
+    /// let token = CancelToken::new();
+    /// let mut options = HashOptions::new().cancel(token.clone());
+    ///
+    /// token.cancel();
+    /// let result =
+    ///     hash::hash_reader_with(&b"foo"[..], Digest::MD5, &mut options);
+    ///
+    /// assert!(matches!(result, Err(Error::Cancelled)));
+    /// ```
+    #[must_use]
+    pub fn cancel(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Returns the part of the data in the range of the options.
+    #[cfg(feature = "mmap")]
+    fn range_of<'d>(&self, data: &'d [u8]) -> &'d [u8] {
+        let start = usize::try_from(self.offset).unwrap_or(usize::MAX);
+        let data = data.get(start..).unwrap_or_default();
+
+        match self.max_bytes.and_then(|n| usize::try_from(n).ok()) {
+            Some(max_bytes) if max_bytes < data.len() => &data[..max_bytes],
+            _ => data,
+        }
+    }
+
+    /// Updates the hasher with the data, with multiple jobs if possible.
+    #[allow(clippy::unused_self)]
+    pub(crate) fn update(&self, hasher: &mut Hasher, data: &[u8]) {
+        #[cfg(all(feature = "rayon", feature = "blake3"))]
+        if let (Hasher::BLAKE3(hasher), true) = (&mut *hasher, self.jobs != 1)
+        {
+            match &self.pool {
+                Some(pool) => pool.install(|| hasher.update_rayon(data)),
+                None => hasher.update_rayon(data),
+            };
+
+            return;
+        }
+
+        digest::Update::update(hasher, data);
+    }
+
+    /// Calls the progress callback, if any.
+    pub(crate) fn report_progress(&mut self, total: u64) {
+        if let Some(progress) = &mut self.progress {
+            progress(total);
+        }
+    }
+
+    /// Returns an error if hashing has been cancelled.
+    pub(crate) fn check_cancelled(&self) -> Result<(), Error> {
+        match &self.cancel {
+            Some(token) if token.is_cancelled() => Err(Error::Cancelled),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for HashOptions<'_> {
+    fn default() -> Self {
+        Self {
+            buffer_size: BUFFER_SIZE,
+            #[cfg(feature = "mmap")]
+            mmap: false,
+            direct_io: false,
+            offset: 0,
+            max_bytes: None,
+            #[cfg(feature = "rayon")]
+            jobs: 1,
+            #[cfg(feature = "rayon")]
+            pool: None,
+            progress: None,
+            cancel: None,
+        }
+    }
+}
+
+impl fmt::Debug for HashOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("HashOptions");
+        s.field("buffer_size", &self.buffer_size);
+        #[cfg(feature = "mmap")]
+        s.field("mmap", &self.mmap);
+        s.field("direct_io", &self.direct_io)
+            .field("offset", &self.offset)
+            .field("max_bytes", &self.max_bytes);
+        #[cfg(feature = "rayon")]
+        s.field("jobs", &self.jobs);
+        s.field("progress", &self.progress.is_some())
+            .field("cancel", &self.cancel)
+            .finish()
+    }
+}
+
+/// Token to cancel hashing.
+///
+/// Clones share the same state, so one clone can be passed to
+/// [`HashOptions::cancel`] and another one be cancelled, e.g. from a signal
+/// handler.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Returns a new token that is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels hashing.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if hashing has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancelToken {
+    fn from(flag: Arc<AtomicBool>) -> Self {
+        Self(flag)
+    }
+}
+
+/// Error hashing with [`HashOptions`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Opening or reading the data failed.
+    Io(io::Error),
+
+    /// Hashing has been cancelled via [`CancelToken`].
+    Cancelled,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Cancelled => write!(f, "hashing cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Cancelled => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<Error> for io::Error {
+    /// Unwraps I/O errors and wraps cancellation, which can be recovered via
+    /// [`io::Error::get_ref`] and downcasting to [`Error`].
+    ///
+    /// Cancellation is not [`io::ErrorKind::Interrupted`], as read loops
+    /// retry on that kind.
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(error) => error,
+            Error::Cancelled => Self::new(io::ErrorKind::Other, error),
+        }
+    }
+}
+
+/// Input to hash, either a file or standard input.
+///
+/// Like with coreutils, `-` denotes standard input when converting from
+/// paths or strings. This also makes [`Input`] usable with
+/// [`clap::value_parser!`]:
+///
+/// ```
+/// use clap::{value_parser, Arg, Command};
+/// use clap_digest::hash::Input;
+///
+/// let cli = Command::new("myapp").arg(Arg::new("input").value_parser(value_parser!(Input)));
+/// let args = cli.get_matches_from(["myapp", "-"]);
+///
+/// assert_eq!(args.get_one::<Input>("input"), Some(&Input::Stdin));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Input {
+    /// A file.
+    Path(PathBuf),
+
+    /// Standard input.
+    Stdin,
+}
+
+impl Input {
+    /// Opens the input for reading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if opening the file fails.
+    pub fn open(&self) -> io::Result<Box<dyn Read>> {
+        Ok(match self {
+            Self::Path(path) => Box::new(File::open(path)?),
+            Self::Stdin => Box::new(io::stdin().lock()),
+        })
+    }
+}
+
+impl AsRef<Path> for Input {
+    /// Returns the path of the file, or `-` for standard input.
+    fn as_ref(&self) -> &Path {
+        match self {
+            Self::Path(path) => path,
+            Self::Stdin => Path::new("-"),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "-"),
+        }
+    }
+}
+
+impl From<PathBuf> for Input {
+    fn from(path: PathBuf) -> Self {
+        if path.as_os_str() == "-" {
+            Self::Stdin
+        } else {
+            Self::Path(path)
+        }
+    }
+}
+
+impl From<&Path> for Input {
+    fn from(path: &Path) -> Self {
+        path.to_path_buf().into()
+    }
+}
+
+impl From<OsString> for Input {
+    fn from(s: OsString) -> Self {
+        PathBuf::from(s).into()
+    }
+}
+
+impl From<&OsStr> for Input {
+    fn from(s: &OsStr) -> Self {
+        Path::new(s).into()
+    }
+}
+
+impl From<&str> for Input {
+    fn from(s: &str) -> Self {
+        Path::new(s).into()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sha2")]
+    use std::io;
+    use std::path::PathBuf;
+
+    use super::Input;
+    #[cfg(feature = "sha2")]
+    use super::{CancelToken, Error, HashOptions};
+    #[cfg(any(feature = "md5", feature = "sha1", feature = "sha2"))]
+    use crate::Digest;
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn hash_reader_chunks() {
+        let data = vec![42; 3 * super::BUFFER_SIZE + 7];
+
+        assert_eq!(
+            super::hash_reader(&data[..], Digest::SHA256).unwrap(),
+            super::hash_bytes(&data, Digest::SHA256)
+        );
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn hash_file_binary() {
+        let path = std::env::temp_dir().join("clap-digest-hash-file-binary");
+        let content = (0..=255).cycle().take(200_000).collect::<Vec<u8>>();
+        std::fs::write(&path, &content).unwrap();
+
+        let output = crate::hash_file(&path, Digest::SHA1);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(output.unwrap(), Digest::SHA1.hash(&content));
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn hash_file() {
+        let content = std::fs::read("Cargo.toml").unwrap();
+
+        assert_eq!(
+            super::hash_file("Cargo.toml", Digest::MD5).unwrap(),
+            Digest::MD5.hash(&content)
+        );
+    }
+
+    #[cfg(all(feature = "mmap", feature = "sha1"))]
+    #[test]
+    fn hash_file_mmap() {
+        let path = std::env::temp_dir().join("clap-digest-hash-file-mmap");
+        let content = (0..=255).cycle().take(200_000).collect::<Vec<u8>>();
+        std::fs::write(&path, &content).unwrap();
+
+        let output = super::hash_file_mmap(&path, Digest::SHA1);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(output.unwrap(), Digest::SHA1.hash(&content));
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn hash_file_with() {
+        let content = std::fs::read("Cargo.toml").unwrap();
+        let expected = Digest::SHA256.hash(&content);
+
+        for mut options in [
+            HashOptions::new().buffer_size(0),
+            HashOptions::new().buffer_size(100).direct_io(true),
+            HashOptions::new().direct_io(true),
+        ] {
+            let output = super::hash_file_with(
+                "Cargo.toml",
+                Digest::SHA256,
+                &mut options,
+            );
+
+            assert_eq!(output.unwrap(), expected, "{options:?}");
+        }
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn progress() {
+        let content = std::fs::read("Cargo.toml").unwrap();
+        let mut totals = Vec::new();
+
+        let mut options = HashOptions::new()
+            .buffer_size(100)
+            .progress(|total| totals.push(total));
+        super::hash_file_with("Cargo.toml", Digest::SHA256, &mut options)
+            .unwrap();
+        drop(options);
+
+        assert_eq!(totals.last(), Some(&(content.len() as u64)));
+        assert!(totals.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn range() {
+        let content = std::fs::read("Cargo.toml").unwrap();
+
+        for (offset, max_bytes) in [
+            (0, 0),
+            (10, 100),
+            (5000, 200),
+            (100, 100_000),
+            (100_000, 10),
+        ] {
+            let start = content.len().min(offset);
+            let end = content.len().min(offset + max_bytes);
+            let expected = Digest::SHA256.hash(&content[start..end]);
+
+            for options in [
+                HashOptions::new(),
+                HashOptions::new().buffer_size(7),
+                HashOptions::new().direct_io(true),
+                #[cfg(feature = "mmap")]
+                HashOptions::new().mmap(true),
+            ] {
+                let mut options =
+                    options.offset(offset as u64).max_bytes(max_bytes as u64);
+                let output = super::hash_file_with(
+                    "Cargo.toml",
+                    Digest::SHA256,
+                    &mut options,
+                );
+                assert_eq!(output.unwrap(), expected, "{options:?}");
+
+                let output = super::hash_reader_with(
+                    &content[..],
+                    Digest::SHA256,
+                    &mut options,
+                );
+                assert_eq!(output.unwrap(), expected, "{options:?}");
+            }
+        }
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn cancel() {
+        let data = vec![0; 1000];
+        let token = CancelToken::new();
+        let cancel = token.clone();
+
+        let mut options = HashOptions::new()
+            .buffer_size(100)
+            .cancel(token)
+            .progress(|total| {
+                if total >= 500 {
+                    cancel.cancel();
+                }
+            });
+        let result =
+            super::hash_reader_with(&data[..], Digest::SHA256, &mut options);
+
+        assert!(matches!(result, Err(Error::Cancelled)));
+
+        let error = io::Error::from(result.unwrap_err());
+        assert_eq!(error.kind(), io::ErrorKind::Other);
+        assert!(matches!(
+            error.get_ref().and_then(|error| error.downcast_ref()),
+            Some(Error::Cancelled)
+        ));
+    }
+
+    #[cfg(all(feature = "rayon", feature = "sha2"))]
+    #[test]
+    fn hash_files_parallel() {
+        let paths = ["Cargo.toml", "missing", "README.md", "Cargo.toml"];
+        let results = super::hash_files_parallel(&paths, Digest::SHA256);
+
+        assert_eq!(results.len(), paths.len());
+
+        for (path, result) in paths.iter().zip(results) {
+            match super::hash_file(path, Digest::SHA256) {
+                Ok(output) => assert_eq!(result.unwrap(), output),
+                Err(_) => assert!(result.is_err()),
+            }
+        }
+    }
+
+    #[cfg(all(feature = "rayon", feature = "blake3", feature = "sha2"))]
+    #[test]
+    fn jobs() {
+        let data = (0..=255).cycle().take(3_000_000).collect::<Vec<u8>>();
+
+        for digest in [Digest::SHA256, Digest::BLAKE3] {
+            for jobs in [0, 1, 3] {
+                let mut options = HashOptions::new().jobs(jobs);
+                let output =
+                    super::hash_reader_with(&data[..], digest, &mut options);
+
+                assert_eq!(output.unwrap(), digest.hash(&data), "{options:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn input_from() {
+        assert_eq!(Input::from("-"), Input::Stdin);
+        assert_eq!(Input::from("./-"), Input::Path(PathBuf::from("./-")));
+        assert_eq!(Input::from("foo").to_string(), "foo");
+        assert_eq!(Input::Stdin.to_string(), "-");
+    }
+}
@@ -0,0 +1,286 @@
+//! Contains the allocation-free [`Hasher`].
+
+use std::fmt;
+
+use digest::{InvalidBufferSize, Reset, Update};
+
+use crate::{Digest, DynDigest, Output};
+
+/// Applies an expression to the concrete hasher of every variant.
+macro_rules! dispatch {
+    ($self:expr, $hasher:ident => $expr:expr) => {
+        crate::table::for_each_digest!(dispatch_table [
+            $self, $hasher => $expr
+        ])
+    };
+}
+
+/// Expands [`dispatch!`] with the table.
+macro_rules! dispatch_table {
+    (
+        [$self:expr, $binding:ident => $expr:expr]
+        $(
+            #[cfg(feature = $feature:literal)]
+            $variant:ident {
+                name: $name:literal,
+                description: $description:literal,
+                cryptographic: $cryptographic:literal,
+                hasher: $hasher:ty,
+                output_size: $output_size:literal,
+                extension: $extension:literal,
+            }
+        )*
+    ) => {
+        match $self {
+            $(
+                #[cfg(feature = $feature)]
+                Hasher::$variant($binding) => $expr,
+            )*
+        }
+    };
+}
+
+/// Defines the [`Hasher`] enum and its per-variant methods from the table.
+macro_rules! define_hasher {
+    (
+        []
+        $(
+            #[cfg(feature = $feature:literal)]
+            $variant:ident {
+                name: $name:literal,
+                description: $description:literal,
+                cryptographic: $cryptographic:literal,
+                hasher: $hasher:ty,
+                output_size: $output_size:literal,
+                extension: $extension:literal,
+            }
+        )*
+    ) => {
+        /// Stack-allocated hasher for a [`Digest`].
+        ///
+        /// Unlike `Box<dyn DynDigest>`, converting a [`Digest`] to a [`Hasher`]
+        /// does not allocate, and hashing dispatches via `match` instead of a
+        /// vtable.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use clap_digest::{Digest, DynDigest, Hasher};
+        ///
+        /// let mut hasher = Hasher::new(Digest::MD5);
+        /// hasher.update(b"foo");
+        /// let hash = hasher.finalize_reset();
+        ///
+        /// assert_eq!(hash[..4], [0xac, 0xbd, 0x18, 0xdb]);
+        /// ```
+        ///
+        /// Hashers implement [`Clone`], so the hashing state can be forked
+        /// mid-stream:
+        ///
+        /// ```
+        /// use clap_digest::{Digest, DynDigest, Hasher};
+        ///
+        /// let mut hasher = Hasher::new(Digest::MD5);
+        /// hasher.update(b"foo");
+        ///
+        /// let mut fork = hasher.clone();
+        /// fork.update(b"bar");
+        ///
+        /// assert_ne!(hasher.finalize_reset(), fork.finalize_reset());
+        /// ```
+        #[allow(missing_docs)] // no docs for the variants
+        #[allow(clippy::large_enum_variant)] // not boxing is the whole point
+        #[derive(Clone)]
+        #[non_exhaustive]
+        pub enum Hasher {
+            $(
+                #[cfg(feature = $feature)]
+                $variant($hasher),
+            )*
+        }
+
+        impl Hasher {
+            /// Returns a new hasher for the digest algorithm.
+            #[must_use]
+            pub fn new(digest: Digest) -> Self {
+                match digest {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Digest::$variant => {
+                            Self::$variant(<$hasher>::default())
+                        }
+                    )*
+                }
+            }
+
+            /// Returns the digest algorithm of this hasher.
+            #[must_use]
+            pub const fn digest(&self) -> Digest {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant(_) => Digest::$variant,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+crate::table::for_each_digest!(define_hasher);
+
+impl Hasher {
+    /// Returns the digest of the data hashed so far, without finalizing
+    /// this hasher.
+    ///
+    /// This finalizes a clone of the hashing state, so hashing can continue
+    /// afterwards, e.g. to compute a running digest at checkpoints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::{Digest, DynDigest, Hasher};
+    ///
+    /// let mut hasher = Hasher::new(Digest::MD5);
+    ///
+    /// hasher.update(b"foo");
+    /// let foo = hasher.checkpoint();
+    ///
+    /// hasher.update(b"bar");
+    /// let foobar = hasher.finalize_reset();
+    ///
+    /// assert_eq!(foo.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+    /// assert_eq!(foobar[..4], [0x38, 0x58, 0xf6, 0x22]);
+    /// ```
+    #[must_use]
+    pub fn checkpoint(&self) -> Output {
+        self.clone().finalize_output()
+    }
+
+    /// Returns the digest of the data hashed, consuming the hasher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::{Digest, DynDigest, Hasher};
+    ///
+    /// let mut hasher = Hasher::new(Digest::MD5);
+    /// hasher.update(b"foo");
+    ///
+    /// assert_eq!(
+    ///     hasher.finalize_output().to_string(),
+    ///     "acbd18db4cc2f85cedef654fccc4a4d8"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn finalize_output(mut self) -> Output {
+        self.finalize_reset().into()
+    }
+}
+
+impl From<Digest> for Hasher {
+    fn from(digest: Digest) -> Self {
+        Self::new(digest)
+    }
+}
+
+impl fmt::Debug for Hasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Hasher").field(&self.digest()).finish()
+    }
+}
+
+impl Update for Hasher {
+    fn update(&mut self, data: &[u8]) {
+        dispatch!(self, hasher => Update::update(hasher, data));
+    }
+}
+
+impl Reset for Hasher {
+    fn reset(&mut self) {
+        dispatch!(self, hasher => Reset::reset(hasher));
+    }
+}
+
+impl DynDigest for Hasher {
+    fn update(&mut self, data: &[u8]) {
+        Update::update(self, data);
+    }
+
+    fn finalize_into(
+        mut self,
+        buf: &mut [u8],
+    ) -> Result<(), InvalidBufferSize> {
+        // not moving out of the variants, as `Drop` is implemented with the
+        // `zeroize` feature
+        self.finalize_into_reset(buf)
+    }
+
+    fn finalize_into_reset(
+        &mut self,
+        buf: &mut [u8],
+    ) -> Result<(), InvalidBufferSize> {
+        dispatch!(self, hasher => hasher.finalize_into_reset(buf))
+    }
+
+    fn reset(&mut self) {
+        Reset::reset(self);
+    }
+
+    fn output_size(&self) -> usize {
+        dispatch!(self, hasher => DynDigest::output_size(hasher))
+    }
+
+    fn box_clone(&self) -> Box<dyn DynDigest> {
+        Box::new(self.clone())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::Hasher;
+    use crate::{Digest, DynDigest};
+
+    #[test]
+    fn same_as_dyn() {
+        for digest in Digest::value_variants() {
+            let mut hasher = Hasher::new(*digest);
+            let mut dyn_hasher: Box<dyn DynDigest> = (*digest).into();
+
+            assert_eq!(hasher.digest(), *digest);
+            assert_eq!(hasher.output_size(), dyn_hasher.output_size());
+
+            DynDigest::update(&mut hasher, b"foo");
+            dyn_hasher.update(b"foo");
+
+            assert_eq!(hasher.finalize_reset(), dyn_hasher.finalize_reset());
+        }
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn checkpoint() {
+        let mut hasher = Hasher::new(Digest::SHA256);
+        let mut expected = Hasher::new(Digest::SHA256);
+
+        DynDigest::update(&mut hasher, b"foo");
+        DynDigest::update(&mut expected, b"foo");
+        assert_eq!(
+            hasher.checkpoint().as_bytes(),
+            &*expected.finalize_reset()
+        );
+
+        DynDigest::update(&mut hasher, b"bar");
+        DynDigest::update(&mut expected, b"foobar");
+        assert_eq!(
+            hasher.checkpoint().as_bytes(),
+            &*expected.finalize_reset()
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Contains I/O adapters that hash the data passing through them.
+//!
+//! [`HashingReader`] and [`HashingWriter`] wrap a reader or writer, so the
+//! data can be processed and hashed in one pass. [`hash_copy`] covers the
+//! common case of copying data while hashing it.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::Read;
+//!
+//! use clap_digest::io::HashingReader;
+//! use clap_digest::Digest;
+//!
+//! let mut reader = HashingReader::new(&b"foo"[..], Digest::MD5);
+//!
+//! let mut content = String::new();
+//! reader.read_to_string(&mut content)?;
+//!
+//! assert_eq!(content, "foo");
+//! let output = reader.finalize();
+//! assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{self, Read, Write};
+
+use digest::Update;
+
+use crate::{Digest, Hasher, Output};
+
+/// Copies everything from the reader to the writer, while hashing it.
+///
+/// This is [`io::copy`] with a checksum of the copied data. Returns the
+/// number of bytes copied and the output.
+///
+/// # Errors
+///
+/// Returns an error if reading or writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::{io, Digest};
+///
+/// let mut content = Vec::new();
+/// let (len, output) = io::hash_copy(&b"foo"[..], &mut content, Digest::MD5)?;
+///
+/// assert_eq!(len, 3);
+/// assert_eq!(content, b"foo");
+/// assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn hash_copy(
+    mut reader: impl Read,
+    writer: impl Write,
+    digest: Digest,
+) -> io::Result<(u64, Output)> {
+    let mut writer = HashingWriter::new(writer, digest);
+    let len = io::copy(&mut reader, &mut writer)?;
+
+    Ok((len, writer.finalize()))
+}
+
+/// Reader that hashes everything read through it.
+///
+/// Only the bytes actually returned by the inner reader are hashed, so the
+/// data can be parsed or decompressed while it is hashed, without a second
+/// pass.
+#[derive(Clone, Debug)]
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R> HashingReader<R> {
+    /// Wraps the reader, hashing with the digest.
+    pub fn new(inner: R, digest: Digest) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(digest),
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// Data read directly from the inner reader is not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the hasher, e.g. to get an intermediate output via
+    /// [`Hasher::checkpoint`].
+    pub const fn hasher(&self) -> &Hasher {
+        &self.hasher
+    }
+
+    /// Returns the output of the data read so far.
+    #[must_use]
+    pub fn finalize(self) -> Output {
+        self.hasher.finalize_output()
+    }
+
+    /// Returns the inner reader and the output of the data read so far.
+    pub fn into_parts(self) -> (R, Output) {
+        (self.inner, self.hasher.finalize_output())
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Writer that hashes everything written through it.
+///
+/// Only the bytes actually accepted by the inner writer are hashed, so the
+/// output matches the written content even with partial writes.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// use clap_digest::io::HashingWriter;
+/// use clap_digest::Digest;
+///
+/// let mut writer = HashingWriter::new(Vec::new(), Digest::MD5);
+/// writer.write_all(b"foo")?;
+///
+/// let (content, output) = writer.into_parts();
+///
+/// assert_eq!(content, b"foo");
+/// assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashingWriter<W> {
+    inner: W,
+    hasher: Hasher,
+}
+
+impl<W> HashingWriter<W> {
+    /// Wraps the writer, hashing with the digest.
+    pub fn new(inner: W, digest: Digest) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(digest),
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Data written directly to the inner writer is not hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the hasher, e.g. to get an intermediate output via
+    /// [`Hasher::checkpoint`].
+    pub const fn hasher(&self) -> &Hasher {
+        &self.hasher
+    }
+
+    /// Returns the output of the data written so far.
+    ///
+    /// The inner writer is dropped without flushing it.
+    #[must_use]
+    pub fn finalize(self) -> Output {
+        self.hasher.finalize_output()
+    }
+
+    /// Returns the inner writer and the output of the data written so far.
+    pub fn into_parts(self) -> (W, Output) {
+        (self.inner, self.hasher.finalize_output())
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, any(feature = "sha1", feature = "sha2")))]
+mod tests {
+    #[cfg(feature = "sha2")]
+    use std::io::Read;
+    use std::io::{self, Write};
+
+    #[cfg(feature = "sha1")]
+    use super::HashingWriter;
+    #[cfg(feature = "sha2")]
+    use super::{hash_copy, HashingReader};
+    use crate::Digest;
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn reader_partial() {
+        let data = (0..=255).cycle().take(10_000).collect::<Vec<u8>>();
+        let mut reader = HashingReader::new(&data[..], Digest::SHA256);
+
+        let mut head = [0; 1000];
+        reader.read_exact(&mut head).unwrap();
+        assert_eq!(reader.hasher().checkpoint(), Digest::SHA256.hash(&head));
+
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+
+        let (inner, output) = reader.into_parts();
+        assert!(inner.is_empty());
+        assert_eq!(output, Digest::SHA256.hash(&data));
+    }
+
+    /// Writer accepting at most three bytes per write.
+    struct Partial(Vec<u8>);
+
+    impl Write for Partial {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let n = buf.len().min(3);
+            self.0.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn writer_partial() {
+        let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+        let mut writer = HashingWriter::new(Partial(Vec::new()), Digest::SHA1);
+
+        assert_eq!(writer.write(&data).unwrap(), 3);
+        assert_eq!(
+            writer.hasher().checkpoint(),
+            Digest::SHA1.hash(&data[..3])
+        );
+
+        writer.write_all(&data[3..]).unwrap();
+
+        let (inner, output) = writer.into_parts();
+        assert_eq!(inner.0, data);
+        assert_eq!(output, Digest::SHA1.hash(&data));
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn copy() {
+        let data = (0..=255).cycle().take(100_000).collect::<Vec<u8>>();
+        let mut writer = Partial(Vec::new());
+
+        let (len, output) =
+            hash_copy(&data[..], &mut writer, Digest::SHA256).unwrap();
+
+        assert_eq!(len, 100_000);
+        assert_eq!(writer.0, data);
+        assert_eq!(output, Digest::SHA256.hash(&data));
+    }
+}
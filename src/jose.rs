@@ -0,0 +1,148 @@
+//! Contains the mapping of digest algorithms to JOSE and COSE identifiers.
+
+use crate::Digest;
+
+impl Digest {
+    /// Returns the JOSE hash name, as used e.g. in `x5t#S256` or the PKCE
+    /// `S256` code challenge method, if there is one for this digest
+    /// algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA256.jose_hash_name(), Some("S256"));
+    /// ```
+    #[must_use]
+    pub const fn jose_hash_name(&self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => Some("S256"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => Some("S384"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => Some("S512"),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the digest algorithm for a JOSE hash name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_jose_hash_name("S512"), Some(Digest::SHA512));
+    /// ```
+    #[cfg_attr(not(feature = "sha2"), allow(clippy::missing_const_for_fn))]
+    #[must_use]
+    pub fn from_jose_hash_name(name: &str) -> Option<Self> {
+        match name {
+            #[cfg(feature = "sha2")]
+            "S256" => Some(Self::SHA256),
+
+            #[cfg(feature = "sha2")]
+            "S384" => Some(Self::SHA384),
+
+            #[cfg(feature = "sha2")]
+            "S512" => Some(Self::SHA512),
+
+            _ => None,
+        }
+    }
+
+    /// Returns the identifier from the IANA COSE Algorithms registry, if
+    /// this digest algorithm is registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA256.cose_hash_id(), Some(-16));
+    /// ```
+    #[must_use]
+    pub const fn cose_hash_id(&self) -> Option<i64> {
+        match self {
+            #[cfg(feature = "sha1")]
+            Self::SHA1 => Some(-14),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => Some(-16),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => Some(-43),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => Some(-44),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512_256 => Some(-17),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the digest algorithm for an identifier from the IANA COSE
+    /// Algorithms registry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_cose_hash_id(-14), Some(Digest::SHA1));
+    /// ```
+    #[must_use]
+    pub const fn from_cose_hash_id(id: i64) -> Option<Self> {
+        match id {
+            #[cfg(feature = "sha1")]
+            -14 => Some(Self::SHA1),
+
+            #[cfg(feature = "sha2")]
+            -16 => Some(Self::SHA256),
+
+            #[cfg(feature = "sha2")]
+            -43 => Some(Self::SHA384),
+
+            #[cfg(feature = "sha2")]
+            -44 => Some(Self::SHA512),
+
+            #[cfg(feature = "sha2")]
+            -17 => Some(Self::SHA512_256),
+
+            _ => None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use crate::Digest;
+
+    #[test]
+    fn roundtrip() {
+        for digest in Digest::value_variants() {
+            if let Some(name) = digest.jose_hash_name() {
+                assert_eq!(Digest::from_jose_hash_name(name), Some(*digest));
+            }
+
+            if let Some(id) = digest.cose_hash_id() {
+                assert_eq!(Digest::from_cose_hash_id(id), Some(*digest));
+            }
+        }
+    }
+}
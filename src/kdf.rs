@@ -0,0 +1,229 @@
+//! Contains key derivation functions.
+//!
+//! [`derive_key`] is BLAKE3 in its key derivation mode, `hkdf` (feature
+//! `hkdf`) is HKDF (RFC 5869) over HMAC with any [`crate::Digest`].
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::kdf;
+//!
+//! let key = kdf::derive_key("myapp 2024-01-01 session keys", b"secret", 16);
+//!
+//! assert_eq!(key.len(), 16);
+//! ```
+
+#[cfg(feature = "hkdf")]
+use std::fmt;
+
+#[cfg(feature = "hkdf")]
+use crate::Digest;
+use crate::Output;
+
+/// Returns `len` bytes of key material derived from the input key material
+/// with BLAKE3 in its key derivation mode.
+///
+/// The context string should be hardcoded, globally unique and
+/// application-specific, e.g. `"myapp 2024-01-01 session keys"`. Different
+/// contexts give unrelated keys for the same key material.
+#[cfg(feature = "blake3")]
+#[must_use]
+pub fn derive_key(context: &str, key_material: &[u8], len: usize) -> Output {
+    let mut output = vec![0; len];
+
+    blake3::Hasher::new_derive_key(context)
+        .update(key_material)
+        .finalize_xof()
+        .fill(&mut output);
+
+    output.into()
+}
+
+/// Returns the pseudorandom key of HKDF-Extract with HMAC over the digest.
+///
+/// The key has the digest output size. An empty salt is the same as no salt,
+/// as in RFC 5869.
+#[cfg(feature = "hkdf")]
+#[must_use]
+pub fn hkdf_extract(digest: Digest, salt: &[u8], ikm: &[u8]) -> Output {
+    with_digest!(digest, |D| {
+        let (prk, _) = hkdf::SimpleHkdf::<D>::extract(Some(salt), ikm);
+        prk.to_vec().into()
+    })
+}
+
+/// Returns `len` bytes of output keying material of HKDF-Expand with HMAC
+/// over the digest.
+///
+/// The info string binds the output to an application-specific context, so
+/// that different contexts give unrelated keys.
+///
+/// # Errors
+///
+/// Returns an error if the pseudorandom key is shorter than the digest output
+/// size, or if the length exceeds 255 times the digest output size.
+#[cfg(feature = "hkdf")]
+pub fn hkdf_expand(
+    digest: Digest,
+    prk: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Result<Output, Error> {
+    let mut okm = vec![0; len];
+
+    with_digest!(digest, |D| {
+        hkdf::SimpleHkdf::<D>::from_prk(prk)
+            .map_err(|_| Error::PrkLength(digest, prk.len()))?
+            .expand(info, &mut okm)
+            .map_err(|_| Error::OutputLength(digest, len))?;
+    });
+
+    Ok(okm.into())
+}
+
+/// Returns `len` bytes of output keying material derived from the input
+/// keying material with HKDF, i.e. HKDF-Extract followed by HKDF-Expand,
+/// with HMAC over the digest.
+///
+/// # Errors
+///
+/// Returns an error if the length exceeds 255 times the digest output size.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::{kdf, Digest};
+///
+/// let key = kdf::hkdf(Digest::SHA256, b"salt", b"secret", b"session", 42)
+///     .unwrap();
+///
+/// assert_eq!(key.len(), 42);
+/// assert!(kdf::hkdf(Digest::SHA256, b"", b"", b"", 255 * 32 + 1).is_err());
+/// ```
+#[cfg(feature = "hkdf")]
+pub fn hkdf(
+    digest: Digest,
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Result<Output, Error> {
+    let prk = hkdf_extract(digest, salt, ikm);
+
+    hkdf_expand(digest, prk.as_bytes(), info, len)
+}
+
+/// Error deriving a key with HKDF.
+#[cfg(feature = "hkdf")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The pseudorandom key in bytes is shorter than the digest output size.
+    PrkLength(Digest, usize),
+
+    /// The output length in bytes exceeds 255 times the digest output size.
+    OutputLength(Digest, usize),
+}
+
+#[cfg(feature = "hkdf")]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PrkLength(digest, len) => write!(
+                f,
+                "{digest} HKDF pseudorandom key must be at least {} bytes, \
+                 not {len}",
+                digest.output_size()
+            ),
+            Self::OutputLength(digest, len) => write!(
+                f,
+                "{digest} HKDF output length must be at most {} bytes, not \
+                 {len}",
+                255 * digest.output_size()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "hkdf")]
+impl std::error::Error for Error {}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "hkdf", feature = "sha1", feature = "sha2"))]
+    use super::Error;
+    #[cfg(all(feature = "hkdf", feature = "sha2"))]
+    use crate::Digest;
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn derive_key() {
+        let context = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+        let key = super::derive_key(context, b"", 64);
+
+        assert_eq!(
+            key.to_string(),
+            "2cc39783c223154fea8dfb7c1b1660f2\
+             ac2dcbd1c1de8277b0b0dd39b7e50d7d\
+             905630c8be290dfcf3e6842f13bddd57\
+             3c098c3f17361f1f206b8cad9d088aa4"
+        );
+        assert_eq!(
+            super::derive_key(context, b"", 32).as_bytes(),
+            &key.as_bytes()[..32]
+        );
+        assert_ne!(super::derive_key("other", b"", 64), key);
+    }
+
+    #[cfg(all(feature = "hkdf", feature = "sha2"))]
+    #[test]
+    fn hkdf() {
+        // RFC 5869 test cases 1 and 3
+        let ikm = [0x0b; 22];
+        let salt = (0x00..=0x0c).collect::<Vec<u8>>();
+        let info = (0xf0..=0xf9).collect::<Vec<u8>>();
+
+        let prk = super::hkdf_extract(Digest::SHA256, &salt, &ikm);
+        assert_eq!(
+            prk.to_string(),
+            "077709362c2e32df0ddc3f0dc47bba63\
+             90b6c73bb50f9c3122ec844ad7c2b3e5"
+        );
+        assert_eq!(
+            super::hkdf_expand(Digest::SHA256, prk.as_bytes(), &info, 42)
+                .unwrap()
+                .to_string(),
+            "3cb25f25faacd57a90434f64d0362f2a\
+             2d2d0a90cf1a5a4c5db02d56ecc4c5bf\
+             34007208d5b887185865"
+        );
+        assert_eq!(
+            super::hkdf(Digest::SHA256, b"", &ikm, b"", 42)
+                .unwrap()
+                .to_string(),
+            "8da4e775a563c18f715f802a063c5a31\
+             b8a11f5c5ee1879ec3454e5f3c738d2d\
+             9d201395faa4b61a96c8"
+        );
+    }
+
+    #[cfg(all(feature = "hkdf", feature = "sha1", feature = "sha2"))]
+    #[test]
+    fn hkdf_errors() {
+        assert_eq!(
+            super::hkdf_expand(Digest::SHA256, &[0; 31], b"", 32),
+            Err(Error::PrkLength(Digest::SHA256, 31))
+        );
+        assert!(super::hkdf(Digest::SHA1, b"", b"", b"", 255 * 20).is_ok());
+        assert_eq!(
+            super::hkdf(Digest::SHA1, b"", b"", b"", 255 * 20 + 1)
+                .unwrap_err()
+                .to_string(),
+            "SHA1 HKDF output length must be at most 5100 bytes, not 5101"
+        );
+    }
+}
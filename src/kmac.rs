@@ -0,0 +1,144 @@
+//! Contains KMAC (NIST SP 800-185) on top of cSHAKE.
+
+use digest::consts::{U32, U64};
+use digest::{
+    ExtendableOutput, FixedOutput, FixedOutputReset, MacMarker, Output,
+    OutputSizeUser, Reset, Update, XofReader,
+};
+
+/// Defines a KMAC type over a cSHAKE type with its rate in bytes, outputting
+/// the given number of bytes.
+macro_rules! kmac {
+    ($name:ident, $cshake:ident, $core:ident, $rate:literal, $size:ty) => {
+        /// KMAC with fixed output length.
+        #[derive(Clone)]
+        pub struct $name {
+            hasher: sha3::$cshake,
+            keyed: sha3::$cshake,
+        }
+
+        impl $name {
+            /// Returns a new instance with the key and customization string.
+            pub fn new(key: &[u8], customization: &[u8]) -> Self {
+                let mut keyed = sha3::$cshake::from_core(
+                    sha3::$core::new_with_function_name(
+                        b"KMAC",
+                        customization,
+                    ),
+                );
+                absorb_key(&mut keyed, key, $rate);
+
+                Self {
+                    hasher: keyed.clone(),
+                    keyed,
+                }
+            }
+        }
+
+        impl Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                self.hasher.update(data);
+            }
+        }
+
+        impl OutputSizeUser for $name {
+            type OutputSize = $size;
+        }
+
+        impl FixedOutput for $name {
+            fn finalize_into(self, out: &mut Output<Self>) {
+                finalize_into(self.hasher, out);
+            }
+        }
+
+        impl FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                let hasher =
+                    std::mem::replace(&mut self.hasher, self.keyed.clone());
+                finalize_into(hasher, out);
+            }
+        }
+
+        impl Reset for $name {
+            fn reset(&mut self) {
+                self.hasher = self.keyed.clone();
+            }
+        }
+
+        impl MacMarker for $name {}
+    };
+}
+
+kmac!(Kmac128, CShake128, CShake128Core, 168, U32);
+kmac!(Kmac256, CShake256, CShake256Core, 136, U64);
+
+/// Absorbs `bytepad(encode_string(key), rate)`.
+fn absorb_key(hasher: &mut impl Update, key: &[u8], rate: usize) {
+    let mut rate_buffer = [0; 9];
+    let mut len_buffer = [0; 9];
+    let encoded_rate = left_encode(rate as u64, &mut rate_buffer);
+    let encoded_len = left_encode(key.len() as u64 * 8, &mut len_buffer);
+
+    hasher.update(encoded_rate);
+    hasher.update(encoded_len);
+    hasher.update(key);
+
+    let len = encoded_rate.len() + encoded_len.len() + key.len();
+    hasher.update(&vec![0; (rate - len % rate) % rate]);
+}
+
+/// Absorbs `right_encode(L)` and reads the output of length `L`.
+fn finalize_into<X: Update + ExtendableOutput>(mut hasher: X, out: &mut [u8]) {
+    let mut buffer = [0; 9];
+    hasher.update(right_encode(out.len() as u64 * 8, &mut buffer));
+    hasher.finalize_xof().read(out);
+}
+
+/// Returns the big-endian bytes of the value, with their count prepended.
+fn left_encode(value: u64, buffer: &mut [u8; 9]) -> &[u8] {
+    let n = encoded_len(value);
+    let start = 8 - usize::from(n);
+    buffer[1..].copy_from_slice(&value.to_be_bytes());
+    buffer[start] = n;
+
+    &buffer[start..]
+}
+
+/// Returns the big-endian bytes of the value, with their count appended.
+fn right_encode(value: u64, buffer: &mut [u8; 9]) -> &[u8] {
+    let n = encoded_len(value);
+    buffer[..8].copy_from_slice(&value.to_be_bytes());
+    buffer[8] = n;
+
+    &buffer[8 - usize::from(n)..]
+}
+
+/// Returns the number of bytes to encode the value, at least 1.
+#[allow(clippy::cast_possible_truncation)] // at most 8
+const fn encoded_len(value: u64) -> u8 {
+    let bytes = (u64::BITS - value.leading_zeros() + 7) / 8;
+
+    if bytes == 0 {
+        1
+    } else {
+        bytes as u8
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn encode() {
+        let mut buffer = [0; 9];
+
+        assert_eq!(super::left_encode(0, &mut buffer), [1, 0]);
+        assert_eq!(super::left_encode(168, &mut buffer), [1, 168]);
+        assert_eq!(super::left_encode(256, &mut buffer), [2, 1, 0]);
+        assert_eq!(super::right_encode(0, &mut buffer), [0, 1]);
+        assert_eq!(super::right_encode(512, &mut buffer), [2, 0, 2]);
+    }
+}
@@ -61,8 +61,9 @@
 //!
 //! 1.  Digest algorithm groups are feature-gated. Use `cargo feature
 //!     clap-digest` for a complete listing. At least one digest algorithm
-//!     group feature must be chosen. To limit the digest algorithm families
-//!     you want to support in your crate, define your own features, e.g.:
+//!     group feature must be chosen, or `all` for every group. To limit the
+//!     digest algorithm families you want to support in your crate, define
+//!     your own features, e.g.:
 //!
 //!     ```toml
 //!     [features]
@@ -128,309 +129,508 @@
 #![deny(clippy::all, missing_docs, unused_must_use)]
 #![warn(clippy::pedantic, clippy::nursery, clippy::cargo)]
 
+#[macro_use]
+mod macros;
+
+#[cfg(any(
+    feature = "adler",
+    feature = "blake2",
+    feature = "cityhash",
+    feature = "crc",
+    feature = "farmhash",
+    feature = "fnv",
+    feature = "metrohash",
+    feature = "murmur3",
+    feature = "seahash",
+    feature = "sum",
+    feature = "wyhash",
+    feature = "xxhash"
+))]
+mod adapter;
 pub mod arg;
+pub mod bench;
+#[cfg(feature = "blake2")]
+pub mod blake2var;
+mod bsd;
+pub mod command;
+#[cfg(feature = "completions")]
+pub mod completions;
+mod coreutils;
+pub mod detect;
+#[cfg(all(feature = "direct-io", target_os = "linux"))]
+mod direct;
+pub mod encode;
+mod file;
+pub mod format;
+#[cfg(feature = "futures")]
+pub mod futures;
+#[cfg(any(feature = "sha1", feature = "sha2"))]
+pub mod git;
+mod glob;
+pub mod hash;
+mod hasher;
+pub mod io;
+mod jose;
+#[cfg(any(feature = "blake3", feature = "hkdf"))]
+pub mod kdf;
+#[cfg(feature = "sha3")]
+mod kmac;
+#[cfg(any(
+    feature = "cmac",
+    feature = "hmac",
+    feature = "poly1305",
+    feature = "sha3",
+    feature = "siphash"
+))]
+pub mod mac;
+#[cfg(feature = "mangen")]
+pub mod man;
+pub mod manifest;
+pub mod merkle;
+mod multi;
+#[cfg(feature = "multihash")]
+pub mod multihash;
+pub mod ni;
+mod oid;
+mod output;
+#[cfg(any(
+    feature = "argon2",
+    feature = "bcrypt",
+    feature = "pbkdf2",
+    feature = "scrypt"
+))]
+pub mod password;
+pub mod pieces;
+mod recommend;
+pub mod registry;
+pub mod s3;
+pub mod selftest;
+#[cfg(feature = "serde")]
+mod serde;
+pub mod sri;
+mod table;
+mod tls;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+mod uring;
+pub mod verify;
+mod visit;
+pub mod walk;
+#[cfg(any(
+    feature = "ascon",
+    feature = "blake3",
+    feature = "k12",
+    feature = "sha3"
+))]
+pub mod xof;
+#[cfg(feature = "zeroize")]
+mod zeroize;
 
 use std::fmt;
 
 use clap::{builder::PossibleValue, ValueEnum};
 pub use digest::DynDigest;
+pub use hash::hash_file;
+pub use hasher::Hasher;
+pub use multi::MultiDigest;
+pub use output::Output;
+pub use registry::DigestChoice;
+pub use visit::DigestVisitor;
+
+/// Defines the hasher types [`with_digest!`] refers to, named like the
+/// [`Digest`] variants.
+macro_rules! define_private {
+    (
+        []
+        $(
+            #[cfg(feature = $feature:literal)]
+            $variant:ident {
+                name: $name:literal,
+                description: $description:literal,
+                cryptographic: $cryptographic:literal,
+                hasher: $hasher:ty,
+                output_size: $output_size:literal,
+                extension: $extension:literal,
+            }
+        )*
+    ) => {
+        $(
+            #[cfg(feature = $feature)]
+            pub type $variant = $hasher;
+        )*
+    };
+}
 
-#[cfg(not(any(
-    feature = "blake2",
-    feature = "blake3",
-    feature = "fsb",
-    feature = "gost94",
-    feature = "groestl",
-    feature = "md2",
-    feature = "md4",
-    feature = "md5",
-    feature = "ripemd",
-    feature = "sha1",
-    feature = "sha2",
-    feature = "sha3",
-    feature = "shabal",
-    feature = "sm3",
-    feature = "streebog",
-    feature = "tiger",
-    feature = "whirlpool"
-)))]
-compile_error!(
-    "at least one digest algorithm family feature needs to be enabled"
-);
-
-/// Supported digest algorithms.
-#[allow(missing_docs)] // no docs for the variants
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[non_exhaustive]
-pub enum Digest {
-    #[cfg(feature = "blake2")]
-    BLAKE2b512,
-
-    #[cfg(feature = "blake2")]
-    BLAKE2s256,
-
-    #[cfg(feature = "blake3")]
-    BLAKE3,
-
-    #[cfg(feature = "fsb")]
-    FSB160,
-
-    #[cfg(feature = "fsb")]
-    FSB224,
-
-    #[cfg(feature = "fsb")]
-    FSB256,
-
-    #[cfg(feature = "fsb")]
-    FSB384,
-
-    #[cfg(feature = "fsb")]
-    FSB512,
-
-    #[cfg(feature = "gost94")]
-    GOST94CryptoPro,
-
-    #[cfg(feature = "gost94")]
-    GOST94UA,
-
-    #[cfg(feature = "gost94")]
-    GOST94s2015,
-
-    #[cfg(feature = "groestl")]
-    Groestl224,
-
-    #[cfg(feature = "groestl")]
-    Groestl256,
-
-    #[cfg(feature = "groestl")]
-    Groestl384,
-
-    #[cfg(feature = "groestl")]
-    Groestl512,
-
-    #[cfg(feature = "md2")]
-    MD2,
-
-    #[cfg(feature = "md4")]
-    MD4,
-
-    #[cfg(feature = "md5")]
-    MD5,
-
-    #[cfg(feature = "ripemd")]
-    RIPEMD160,
-
-    #[cfg(feature = "ripemd")]
-    RIPEMD256,
-
-    #[cfg(feature = "ripemd")]
-    RIPEMD320,
-
-    #[cfg(feature = "sha1")]
-    SHA1,
-
-    #[cfg(feature = "sha2")]
-    SHA224,
-
-    #[cfg(feature = "sha2")]
-    SHA256,
-
-    #[cfg(feature = "sha2")]
-    SHA384,
-
-    #[cfg(feature = "sha2")]
-    SHA512,
-
-    #[cfg(feature = "sha2")]
-    SHA512_224,
-
-    #[cfg(feature = "sha2")]
-    SHA512_256,
-
-    #[cfg(feature = "sha3")]
-    SHA3_224,
-
-    #[cfg(feature = "sha3")]
-    SHA3_256,
+#[doc(hidden)]
+pub mod __private {
+    crate::table::for_each_digest!(define_private);
+}
 
-    #[cfg(feature = "sha3")]
-    SHA3_384,
+/// Fails the build if no digest algorithm family is enabled.
+macro_rules! define_feature_check {
+    (
+        []
+        $(
+            #[cfg(feature = $feature:literal)]
+            $variant:ident { $($fields:tt)* }
+        )*
+    ) => {
+        #[cfg(not(any($(feature = $feature),*)))]
+        compile_error!(
+            "at least one digest algorithm family feature needs to be enabled"
+        );
+    };
+}
 
-    #[cfg(feature = "sha3")]
-    SHA3_512,
+crate::table::for_each_digest!(define_feature_check);
+
+/// Defines the [`Digest`] enum and its per-variant methods from the table.
+macro_rules! define_digest {
+    (
+        []
+        $(
+            #[cfg(feature = $feature:literal)]
+            $variant:ident {
+                name: $name:literal,
+                description: $description:literal,
+                cryptographic: $cryptographic:literal,
+                hasher: $hasher:ty,
+                output_size: $output_size:literal,
+                extension: $extension:literal,
+            }
+        )*
+    ) => {
+        /// Supported digest algorithms.
+        ///
+        /// With the `serde` feature, digest algorithms are serialized by
+        /// [`name`](Self::name) and deserialized via
+        /// [`from_name`](Self::from_name).
+        #[allow(missing_docs)] // no docs for the variants
+        #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+        #[non_exhaustive]
+        pub enum Digest {
+            $(
+                #[cfg(feature = $feature)]
+                $variant,
+            )*
+        }
 
-    #[cfg(feature = "shabal")]
-    SHABAL192,
+        impl Digest {
+            /// Returns the digest algorithm name.
+            ///
+            /// This is used for both [`std::fmt::Display`] as well as
+            /// [`clap::ValueEnum::to_possible_value`].
+            #[must_use]
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant => $name,
+                    )*
+                }
+            }
 
-    #[cfg(feature = "shabal")]
-    SHABAL224,
+            /// Returns the identifier of the enum variant.
+            const fn identifier(&self) -> &'static str {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant => stringify!($variant),
+                    )*
+                }
+            }
 
-    #[cfg(feature = "shabal")]
-    SHABAL256,
+            /// Returns the names of all enabled digest algorithms, in the
+            /// order of [`ValueEnum::value_variants`].
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use clap_digest::Digest;
+            ///
+            /// assert!(Digest::all_names().contains(&"SHA256"));
+            /// assert_eq!(Digest::all_names().len(), Digest::count());
+            /// ```
+            #[must_use]
+            pub const fn all_names() -> &'static [&'static str] {
+                &[
+                    $(
+                        #[cfg(feature = $feature)]
+                        $name,
+                    )*
+                ]
+            }
 
-    #[cfg(feature = "shabal")]
-    SHABAL384,
+            /// Returns a short description of the digest algorithm.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use clap_digest::Digest;
+            ///
+            /// assert_eq!(
+            ///     Digest::SHA256.description(),
+            ///     "SHA-2 with 256 bit output"
+            /// );
+            /// ```
+            #[must_use]
+            pub const fn description(&self) -> &'static str {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant => $description,
+                    )*
+                }
+            }
 
-    #[cfg(feature = "shabal")]
-    SHABAL512,
+            /// Returns the Cargo feature that enables the digest algorithm.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use clap_digest::Digest;
+            ///
+            /// assert_eq!(Digest::SHA512_256.feature(), "sha2");
+            /// ```
+            #[must_use]
+            pub const fn feature(&self) -> &'static str {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant => $feature,
+                    )*
+                }
+            }
 
-    #[cfg(feature = "sm3")]
-    SM3,
+            /// Returns whether the digest algorithm is meant to be
+            /// cryptographic, i.e. collision and preimage resistant.
+            ///
+            /// Broken ones like MD5 count as cryptographic, checksums and
+            /// hash table functions like CRC-32 or xxHash don't.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use clap_digest::Digest;
+            ///
+            /// assert!(Digest::SHA256.is_cryptographic());
+            /// assert!(Digest::MD5.is_cryptographic());
+            /// ```
+            #[must_use]
+            pub const fn is_cryptographic(&self) -> bool {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant => $cryptographic,
+                    )*
+                }
+            }
 
-    #[cfg(feature = "streebog")]
-    Streebog256,
+            /// Returns the digest output size in bytes.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use clap_digest::Digest;
+            ///
+            /// assert_eq!(Digest::SHA256.output_size(), 32);
+            /// ```
+            #[must_use]
+            pub const fn output_size(&self) -> usize {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant => $output_size,
+                    )*
+                }
+            }
 
-    #[cfg(feature = "streebog")]
-    Streebog512,
+            /// Returns a new boxed hasher for the digest algorithm.
+            ///
+            /// This is the same as the [`From`] conversion to
+            /// `Box<dyn DynDigest>`. The hasher is [`Send`] and [`Sync`], so
+            /// it can be moved to other threads.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use clap_digest::Digest;
+            ///
+            /// let mut hasher = Digest::MD5.new_dyn();
+            /// hasher.update(b"foo");
+            /// let hash = hasher.finalize();
+            ///
+            /// assert_eq!(hash[..4], [0xac, 0xbd, 0x18, 0xdb]);
+            /// ```
+            #[must_use]
+            pub fn new_dyn(&self) -> Box<dyn DynDigest + Send + Sync> {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant => Box::<$hasher>::default(),
+                    )*
+                }
+            }
+        }
 
-    #[cfg(feature = "tiger")]
-    Tiger,
+        impl ValueEnum for Digest {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$variant,
+                    )*
+                ]
+            }
 
-    #[cfg(feature = "tiger")]
-    Tiger2,
+            fn to_possible_value(&self) -> Option<PossibleValue> {
+                Some(PossibleValue::new(self.name()))
+            }
+        }
 
-    #[cfg(feature = "whirlpool")]
-    Whirlpool,
+        // the output sizes in the table must match the hasher types
+        $(
+            #[cfg(feature = $feature)]
+            const _: () = assert!(
+                $output_size
+                    == <<$hasher as digest::OutputSizeUser>::OutputSize
+                        as digest::typenum::Unsigned>::USIZE
+            );
+        )*
+    };
 }
 
+table::for_each_digest!(define_digest);
+
 impl Digest {
-    /// Returns the digest algorithm name.
+    /// Returns the digest of the data.
+    ///
+    /// # Examples
     ///
-    /// This is used for both [`std::fmt::Display`] as well as
-    /// [`clap::ValueEnum::to_possible_value`].
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// let output = Digest::MD5.hash(b"foo");
+    ///
+    /// assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+    /// ```
     #[must_use]
-    pub const fn name(&self) -> &'static str {
-        match self {
-            #[cfg(feature = "blake2")]
-            Self::BLAKE2b512 => "BLAKE2b512",
-
-            #[cfg(feature = "blake2")]
-            Self::BLAKE2s256 => "BLAKE2s256",
-
-            #[cfg(feature = "blake3")]
-            Self::BLAKE3 => "BLAKE3",
-
-            #[cfg(feature = "fsb")]
-            Self::FSB160 => "FSB160",
-
-            #[cfg(feature = "fsb")]
-            Self::FSB224 => "FSB224",
-
-            #[cfg(feature = "fsb")]
-            Self::FSB256 => "FSB256",
-
-            #[cfg(feature = "fsb")]
-            Self::FSB384 => "FSB384",
-
-            #[cfg(feature = "fsb")]
-            Self::FSB512 => "FSB512",
-
-            #[cfg(feature = "gost94")]
-            Self::GOST94CryptoPro => "GOST94CryptoPro",
-
-            #[cfg(feature = "gost94")]
-            Self::GOST94UA => "GOST94UA",
-
-            #[cfg(feature = "gost94")]
-            Self::GOST94s2015 => "GOST94s2015",
-
-            #[cfg(feature = "groestl")]
-            Self::Groestl224 => "Groestl224",
-
-            #[cfg(feature = "groestl")]
-            Self::Groestl256 => "Groestl256",
-
-            #[cfg(feature = "groestl")]
-            Self::Groestl384 => "Groestl384",
-
-            #[cfg(feature = "groestl")]
-            Self::Groestl512 => "Groestl512",
-
-            #[cfg(feature = "md2")]
-            Self::MD2 => "MD2",
-
-            #[cfg(feature = "md4")]
-            Self::MD4 => "MD4",
-
-            #[cfg(feature = "md5")]
-            Self::MD5 => "MD5",
-
-            #[cfg(feature = "ripemd")]
-            Self::RIPEMD160 => "RIPEMD160",
-
-            #[cfg(feature = "ripemd")]
-            Self::RIPEMD256 => "RIPEMD256",
-
-            #[cfg(feature = "ripemd")]
-            Self::RIPEMD320 => "RIPEMD320",
-
-            #[cfg(feature = "sha1")]
-            Self::SHA1 => "SHA1",
-
-            #[cfg(feature = "sha2")]
-            Self::SHA224 => "SHA224",
-
-            #[cfg(feature = "sha2")]
-            Self::SHA256 => "SHA256",
-
-            #[cfg(feature = "sha2")]
-            Self::SHA384 => "SHA384",
-
-            #[cfg(feature = "sha2")]
-            Self::SHA512 => "SHA512",
-
-            #[cfg(feature = "sha2")]
-            Self::SHA512_224 => "SHA512/224",
-
-            #[cfg(feature = "sha2")]
-            Self::SHA512_256 => "SHA512/256",
-
-            #[cfg(feature = "sha3")]
-            Self::SHA3_224 => "SHA3-224",
-
-            #[cfg(feature = "sha3")]
-            Self::SHA3_256 => "SHA3-256",
-
-            #[cfg(feature = "sha3")]
-            Self::SHA3_384 => "SHA3-384",
-
-            #[cfg(feature = "sha3")]
-            Self::SHA3_512 => "SHA3-512",
-
-            #[cfg(feature = "shabal")]
-            Self::SHABAL192 => "Shabal-192",
-
-            #[cfg(feature = "shabal")]
-            Self::SHABAL224 => "Shabal-224",
-
-            #[cfg(feature = "shabal")]
-            Self::SHABAL256 => "Shabal-256",
-
-            #[cfg(feature = "shabal")]
-            Self::SHABAL384 => "Shabal-384",
+    pub fn hash(&self, data: &[u8]) -> Output {
+        let mut hasher = Hasher::new(*self);
+        hasher.update(data);
+        hasher.finalize_output()
+    }
 
-            #[cfg(feature = "shabal")]
-            Self::SHABAL512 => "Shabal-512",
+    /// Returns all digest algorithms with the output size in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert!(Digest::candidates_for_len(16).contains(&Digest::MD5));
+    /// ```
+    #[must_use]
+    pub fn candidates_for_len(len: usize) -> Vec<Self> {
+        Self::value_variants()
+            .iter()
+            .filter(|digest| digest.output_size() == len)
+            .copied()
+            .collect()
+    }
 
-            #[cfg(feature = "sm3")]
-            Self::SM3 => "SM3",
+    /// Returns all digest algorithms whose output is encoded as hex with
+    /// this number of characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// let hash = "acbd18db4cc2f85cedef654fccc4a4d8";
+    ///
+    /// assert!(Digest::candidates_for_hex_len(hash.len()).contains(&Digest::MD5));
+    /// assert!(Digest::candidates_for_hex_len(31).is_empty());
+    /// ```
+    #[must_use]
+    pub fn candidates_for_hex_len(len: usize) -> Vec<Self> {
+        if len % 2 == 0 {
+            Self::candidates_for_len(len / 2)
+        } else {
+            Vec::new()
+        }
+    }
 
-            #[cfg(feature = "streebog")]
-            Self::Streebog256 => "Streebog-256",
+    /// Returns the digest algorithm with the name, allowing common
+    /// spellings.
+    ///
+    /// The name is matched ignoring ASCII case and the `-`, `_` and `/`
+    /// separators, against both the [`name`](Self::name) and the enum
+    /// variant. This is how the CLI arguments and the `serde`
+    /// deserialization look up digest algorithms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_name("SHA256"), Some(Digest::SHA256));
+    /// assert_eq!(Digest::from_name("sha-256"), Some(Digest::SHA256));
+    /// assert_eq!(Digest::from_name("sha512/256"), Some(Digest::SHA512_256));
+    /// assert_eq!(Digest::from_name("sha-257"), None);
+    /// ```
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::iter().find(|digest| {
+            Self::name_eq(digest.name(), name)
+                || Self::name_eq(digest.identifier(), name)
+        })
+    }
 
-            #[cfg(feature = "streebog")]
-            Self::Streebog512 => "Streebog-512",
+    /// Returns `true` if the names are equal the way [`Self::from_name`]
+    /// matches them.
+    pub(crate) fn name_eq(a: &str, b: &str) -> bool {
+        fn normalized(name: &str) -> impl Iterator<Item = u8> + '_ {
+            name.bytes()
+                .filter(|byte| !matches!(byte, b'-' | b'_' | b'/'))
+                .map(|byte| byte.to_ascii_lowercase())
+        }
 
-            #[cfg(feature = "tiger")]
-            Self::Tiger => "Tiger",
+        normalized(a).eq(normalized(b))
+    }
 
-            #[cfg(feature = "tiger")]
-            Self::Tiger2 => "Tiger2",
+    /// Returns an iterator over all enabled digest algorithms, in the order
+    /// of [`ValueEnum::value_variants`].
+    ///
+    /// Unlike [`ValueEnum::value_variants`], this does not need the trait in
+    /// scope. With the `strum` feature, `strum::IntoEnumIterator` is
+    /// implemented as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert!(Digest::iter().any(|digest| digest == Digest::SHA256));
+    /// ```
+    pub fn iter() -> std::iter::Copied<std::slice::Iter<'static, Self>> {
+        Self::value_variants().iter().copied()
+    }
 
-            #[cfg(feature = "whirlpool")]
-            Self::Whirlpool => "Whirlpool",
-        }
+    /// Returns the number of enabled digest algorithms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap::ValueEnum;
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::count(), Digest::value_variants().len());
+    /// ```
+    #[must_use]
+    pub fn count() -> usize {
+        Self::value_variants().len()
     }
 }
 
@@ -440,257 +640,145 @@ impl fmt::Display for Digest {
     }
 }
 
-impl ValueEnum for Digest {
-    fn value_variants<'a>() -> &'a [Self] {
-        &[
-            #[cfg(feature = "blake2")]
-            Self::BLAKE2b512,
-            #[cfg(feature = "blake2")]
-            Self::BLAKE2s256,
-            #[cfg(feature = "blake3")]
-            Self::BLAKE3,
-            #[cfg(feature = "fsb")]
-            Self::FSB160,
-            #[cfg(feature = "fsb")]
-            Self::FSB224,
-            #[cfg(feature = "fsb")]
-            Self::FSB256,
-            #[cfg(feature = "fsb")]
-            Self::FSB384,
-            #[cfg(feature = "fsb")]
-            Self::FSB512,
-            #[cfg(feature = "gost94")]
-            Self::GOST94CryptoPro,
-            #[cfg(feature = "gost94")]
-            Self::GOST94UA,
-            #[cfg(feature = "gost94")]
-            Self::GOST94s2015,
-            #[cfg(feature = "groestl")]
-            Self::Groestl224,
-            #[cfg(feature = "groestl")]
-            Self::Groestl256,
-            #[cfg(feature = "groestl")]
-            Self::Groestl384,
-            #[cfg(feature = "groestl")]
-            Self::Groestl512,
-            #[cfg(feature = "md2")]
-            Self::MD2,
-            #[cfg(feature = "md4")]
-            Self::MD4,
-            #[cfg(feature = "md5")]
-            Self::MD5,
-            #[cfg(feature = "ripemd")]
-            Self::RIPEMD160,
-            #[cfg(feature = "ripemd")]
-            Self::RIPEMD256,
-            #[cfg(feature = "ripemd")]
-            Self::RIPEMD320,
-            #[cfg(feature = "sha1")]
-            Self::SHA1,
-            #[cfg(feature = "sha2")]
-            Self::SHA224,
-            #[cfg(feature = "sha2")]
-            Self::SHA256,
-            #[cfg(feature = "sha2")]
-            Self::SHA384,
-            #[cfg(feature = "sha2")]
-            Self::SHA512,
-            #[cfg(feature = "sha2")]
-            Self::SHA512_224,
-            #[cfg(feature = "sha2")]
-            Self::SHA512_256,
-            #[cfg(feature = "sha3")]
-            Self::SHA3_224,
-            #[cfg(feature = "sha3")]
-            Self::SHA3_256,
-            #[cfg(feature = "sha3")]
-            Self::SHA3_384,
-            #[cfg(feature = "sha3")]
-            Self::SHA3_512,
-            #[cfg(feature = "shabal")]
-            Self::SHABAL192,
-            #[cfg(feature = "shabal")]
-            Self::SHABAL224,
-            #[cfg(feature = "shabal")]
-            Self::SHABAL256,
-            #[cfg(feature = "shabal")]
-            Self::SHABAL384,
-            #[cfg(feature = "shabal")]
-            Self::SHABAL512,
-            #[cfg(feature = "sm3")]
-            Self::SM3,
-            #[cfg(feature = "streebog")]
-            Self::Streebog256,
-            #[cfg(feature = "streebog")]
-            Self::Streebog512,
-            #[cfg(feature = "tiger")]
-            Self::Tiger,
-            #[cfg(feature = "tiger")]
-            Self::Tiger2,
-            #[cfg(feature = "whirlpool")]
-            Self::Whirlpool,
-        ]
+impl From<Digest> for Box<dyn DynDigest> {
+    fn from(digest: Digest) -> Self {
+        digest.new_dyn()
     }
+}
 
-    fn to_possible_value(&self) -> Option<PossibleValue> {
-        Some(PossibleValue::new(self.name()))
+impl From<&Digest> for Box<dyn DynDigest> {
+    fn from(digest: &Digest) -> Self {
+        digest.new_dyn()
     }
 }
 
-impl From<Digest> for Box<dyn DynDigest> {
+impl From<Digest> for Box<dyn DynDigest + Send> {
     fn from(digest: Digest) -> Self {
-        match digest {
-            #[cfg(feature = "blake2")]
-            Digest::BLAKE2b512 => Box::<blake2::Blake2b512>::default(),
-
-            #[cfg(feature = "blake2")]
-            Digest::BLAKE2s256 => Box::<blake2::Blake2s256>::default(),
-
-            #[cfg(feature = "blake3")]
-            Digest::BLAKE3 => Box::<blake3::Hasher>::default(),
-
-            #[cfg(feature = "fsb")]
-            Digest::FSB160 => Box::<fsb::Fsb160>::default(),
-
-            #[cfg(feature = "fsb")]
-            Digest::FSB224 => Box::<fsb::Fsb224>::default(),
-
-            #[cfg(feature = "fsb")]
-            Digest::FSB256 => Box::<fsb::Fsb256>::default(),
-
-            #[cfg(feature = "fsb")]
-            Digest::FSB384 => Box::<fsb::Fsb384>::default(),
-
-            #[cfg(feature = "fsb")]
-            Digest::FSB512 => Box::<fsb::Fsb512>::default(),
-
-            #[cfg(feature = "gost94")]
-            Digest::GOST94CryptoPro => {
-                Box::<gost94::Gost94CryptoPro>::default()
-            }
-
-            #[cfg(feature = "gost94")]
-            Digest::GOST94UA => Box::<gost94::Gost94UA>::default(),
-
-            #[cfg(feature = "gost94")]
-            Digest::GOST94s2015 => Box::<gost94::Gost94s2015>::default(),
-
-            #[cfg(feature = "groestl")]
-            Digest::Groestl224 => Box::<groestl::Groestl224>::default(),
-
-            #[cfg(feature = "groestl")]
-            Digest::Groestl256 => Box::<groestl::Groestl256>::default(),
-
-            #[cfg(feature = "groestl")]
-            Digest::Groestl384 => Box::<groestl::Groestl384>::default(),
-
-            #[cfg(feature = "groestl")]
-            Digest::Groestl512 => Box::<groestl::Groestl512>::default(),
-
-            #[cfg(feature = "md2")]
-            Digest::MD2 => Box::<md2::Md2>::default(),
-
-            #[cfg(feature = "md4")]
-            Digest::MD4 => Box::<md4::Md4>::default(),
-
-            #[cfg(feature = "md5")]
-            Digest::MD5 => Box::<md5::Md5>::default(),
-
-            #[cfg(feature = "ripemd")]
-            Digest::RIPEMD160 => Box::<ripemd::Ripemd160>::default(),
-
-            #[cfg(feature = "ripemd")]
-            Digest::RIPEMD256 => Box::<ripemd::Ripemd256>::default(),
-
-            #[cfg(feature = "ripemd")]
-            Digest::RIPEMD320 => Box::<ripemd::Ripemd320>::default(),
-
-            #[cfg(feature = "sha1")]
-            Digest::SHA1 => Box::<sha1::Sha1>::default(),
-
-            #[cfg(feature = "sha2")]
-            Digest::SHA224 => Box::<sha2::Sha224>::default(),
-
-            #[cfg(feature = "sha2")]
-            Digest::SHA256 => Box::<sha2::Sha256>::default(),
-
-            #[cfg(feature = "sha2")]
-            Digest::SHA384 => Box::<sha2::Sha384>::default(),
-
-            #[cfg(feature = "sha2")]
-            Digest::SHA512 => Box::<sha2::Sha512>::default(),
-
-            #[cfg(feature = "sha2")]
-            Digest::SHA512_224 => Box::<sha2::Sha512_224>::default(),
-
-            #[cfg(feature = "sha2")]
-            Digest::SHA512_256 => Box::<sha2::Sha512_256>::default(),
+        digest.new_dyn()
+    }
+}
 
-            #[cfg(feature = "sha3")]
-            Digest::SHA3_224 => Box::<sha3::Sha3_224>::default(),
+impl From<Digest> for Box<dyn DynDigest + Send + Sync> {
+    fn from(digest: Digest) -> Self {
+        digest.new_dyn()
+    }
+}
 
-            #[cfg(feature = "sha3")]
-            Digest::SHA3_256 => Box::<sha3::Sha3_256>::default(),
+#[cfg(feature = "strum")]
+impl strum::IntoEnumIterator for Digest {
+    type Iterator = std::iter::Copied<std::slice::Iter<'static, Self>>;
 
-            #[cfg(feature = "sha3")]
-            Digest::SHA3_384 => Box::<sha3::Sha3_384>::default(),
+    fn iter() -> Self::Iterator {
+        Self::iter()
+    }
+}
 
-            #[cfg(feature = "sha3")]
-            Digest::SHA3_512 => Box::<sha3::Sha3_512>::default(),
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
 
-            #[cfg(feature = "shabal")]
-            Digest::SHABAL192 => Box::<shabal::Shabal192>::default(),
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            #[cfg(feature = "shabal")]
-            Digest::SHABAL224 => Box::<shabal::Shabal224>::default(),
+    #[test]
+    const fn test_send() {
+        const fn assert_send<T: Send + ?Sized>() {}
+        assert_send::<Digest>();
+        assert_send::<Hasher>();
+        assert_send::<Box<dyn DynDigest + Send>>();
+    }
 
-            #[cfg(feature = "shabal")]
-            Digest::SHABAL256 => Box::<shabal::Shabal256>::default(),
+    #[test]
+    const fn test_sync() {
+        const fn assert_sync<T: Sync + ?Sized>() {}
+        assert_sync::<Digest>();
+        assert_sync::<Hasher>();
+        assert_sync::<Box<dyn DynDigest + Send + Sync>>();
+    }
 
-            #[cfg(feature = "shabal")]
-            Digest::SHABAL384 => Box::<shabal::Shabal384>::default(),
+    #[cfg(feature = "md5")]
+    #[test]
+    fn from_ref() {
+        struct Config {
+            digest: Digest,
+        }
 
-            #[cfg(feature = "shabal")]
-            Digest::SHABAL512 => Box::<shabal::Shabal512>::default(),
+        let config = Config {
+            digest: Digest::MD5,
+        };
 
-            #[cfg(feature = "sm3")]
-            Digest::SM3 => Box::<sm3::Sm3>::default(),
+        let hasher: Box<dyn DynDigest> = (&config.digest).into();
+        assert_eq!(hasher.output_size(), 16);
+    }
 
-            #[cfg(feature = "streebog")]
-            Digest::Streebog256 => Box::<streebog::Streebog256>::default(),
+    #[cfg(all(feature = "blake3", feature = "sha2"))]
+    #[test]
+    fn candidates() {
+        let candidates = Digest::candidates_for_hex_len(64);
 
-            #[cfg(feature = "streebog")]
-            Digest::Streebog512 => Box::<streebog::Streebog512>::default(),
+        assert!(candidates.contains(&Digest::SHA256));
+        assert!(candidates.contains(&Digest::BLAKE3));
+        assert!(!candidates.contains(&Digest::SHA512));
+        assert!(candidates.iter().all(|digest| digest.output_size() == 32));
+    }
 
-            #[cfg(feature = "tiger")]
-            Digest::Tiger => Box::<tiger::Tiger>::default(),
+    #[cfg(feature = "md5")]
+    #[test]
+    fn send_to_thread() {
+        let mut hasher: Box<dyn DynDigest + Send> = Digest::MD5.into();
 
-            #[cfg(feature = "tiger")]
-            Digest::Tiger2 => Box::<tiger::Tiger2>::default(),
+        let hash = std::thread::spawn(move || {
+            hasher.update(b"foo");
+            hasher.finalize()
+        })
+        .join()
+        .unwrap();
 
-            #[cfg(feature = "whirlpool")]
-            Digest::Whirlpool => Box::<whirlpool::Whirlpool>::default(),
-        }
+        assert_eq!(hash[..4], [0xac, 0xbd, 0x18, 0xdb]);
     }
-}
 
-// ----------------------------------------------------------------------------
-// tests
-// ----------------------------------------------------------------------------
+    #[test]
+    fn coverage() {
+        let names = Digest::all_names();
+        let unique = names.iter().collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(names.len(), Digest::count());
+        assert_eq!(unique.len(), Digest::count());
+
+        for (digest, &name) in Digest::value_variants().iter().zip(names) {
+            assert_eq!(Digest::from_str(name, false), Ok(*digest));
+            assert_eq!(Digest::from_name(name), Some(*digest));
+            assert_eq!(digest.to_string(), name);
+            assert_eq!(digest.to_possible_value().unwrap().get_name(), name);
+            assert_eq!(Hasher::new(*digest).digest(), *digest);
+            assert_eq!(
+                Box::<dyn DynDigest>::from(digest).output_size(),
+                digest.output_size()
+            );
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // bump when adding digest algorithms, so none is left out
+        #[cfg(feature = "all")]
+        assert_eq!(Digest::count(), 82);
+    }
 
     #[test]
-    const fn test_send() {
-        const fn assert_send<T: Send>() {}
-        assert_send::<Digest>();
+    fn iter() {
+        assert!(Digest::iter().eq(Digest::value_variants().iter().copied()));
+        assert_eq!(Digest::iter().len(), Digest::count());
+
+        #[cfg(feature = "strum")]
+        assert!(<Digest as strum::IntoEnumIterator>::iter().eq(Digest::iter()));
     }
 
+    #[cfg(feature = "sha3")]
     #[test]
-    const fn test_sync() {
-        const fn assert_sync<T: Sync>() {}
-        assert_sync::<Digest>();
+    fn keccak_is_not_sha3() {
+        assert_eq!(
+            Digest::Keccak256.hash(b"").to_string(),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_ne!(Digest::Keccak256.hash(b""), Digest::SHA3_256.hash(b""));
     }
 }
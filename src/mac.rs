@@ -0,0 +1,876 @@
+//! Contains message authentication codes ([`Mac`]).
+//!
+//! A MAC is a keyed digest: only holders of the key can compute or verify it.
+//! Use [`arg::mac`](crate::arg::mac) and [`arg::mac_key`](crate::arg::mac_key)
+//! to build authenticated-checksum CLIs the same way as plain-digest ones.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::mac::Mac;
+//!
+//! let mac = Mac::KMAC128;
+//! let tag = mac.compute(b"key", b"foo").unwrap();
+//!
+//! assert!(mac.verify(b"key", b"foo", tag.as_bytes()).unwrap());
+//! assert!(!mac.verify(b"other key", b"foo", tag.as_bytes()).unwrap());
+//! ```
+
+use std::fmt;
+
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use digest::FixedOutputReset;
+#[cfg(any(feature = "cmac", feature = "hmac", feature = "poly1305"))]
+use digest::KeyInit;
+#[cfg(feature = "siphash")]
+use std::hash::Hasher;
+
+#[cfg(feature = "hmac")]
+use crate::Digest;
+use crate::Output;
+
+/// Supported message authentication codes.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Mac {
+    /// CMAC over AES with 128 bit keys.
+    #[cfg(feature = "cmac")]
+    CmacAes128,
+
+    /// CMAC over AES with 192 bit keys.
+    #[cfg(feature = "cmac")]
+    CmacAes192,
+
+    /// CMAC over AES with 256 bit keys.
+    #[cfg(feature = "cmac")]
+    CmacAes256,
+
+    /// HMAC over the digest algorithm.
+    ///
+    /// Only [cryptographic](Digest::is_cryptographic) digest algorithms are
+    /// among the [`ValueEnum::value_variants`], as HMAC over checksums like
+    /// CRC-32 is not secure.
+    #[cfg(feature = "hmac")]
+    Hmac(Digest),
+
+    /// KMAC with 128 bit security and 256 bit output.
+    #[cfg(feature = "sha3")]
+    KMAC128,
+
+    /// KMAC with 256 bit security and 512 bit output.
+    #[cfg(feature = "sha3")]
+    KMAC256,
+
+    /// Poly1305 one-time authenticator with 256 bit keys.
+    ///
+    /// A key must never authenticate more than one message.
+    #[cfg(feature = "poly1305")]
+    Poly1305,
+
+    /// SipHash-2-4 with 128 bit keys and 64 bit output.
+    #[cfg(feature = "siphash")]
+    SipHash24,
+}
+
+/// Defines [`hmac_name`] from the table.
+#[cfg(feature = "hmac")]
+macro_rules! define_hmac_name {
+    (
+        []
+        $(
+            #[cfg(feature = $feature:literal)]
+            $variant:ident {
+                name: $name:literal,
+                description: $description:literal,
+                cryptographic: $cryptographic:literal,
+                hasher: $hasher:ty,
+                output_size: $output_size:literal,
+                extension: $extension:literal,
+            }
+        )*
+    ) => {
+        /// Returns the name of HMAC over the digest algorithm.
+        const fn hmac_name(digest: Digest) -> &'static str {
+            match digest {
+                $(
+                    #[cfg(feature = $feature)]
+                    Digest::$variant => concat!("HMAC-", stringify!($variant)),
+                )*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hmac")]
+crate::table::for_each_digest!(define_hmac_name);
+
+impl Mac {
+    /// Returns the name of the MAC.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "cmac")]
+            Self::CmacAes128 => "CMAC-AES128",
+
+            #[cfg(feature = "cmac")]
+            Self::CmacAes192 => "CMAC-AES192",
+
+            #[cfg(feature = "cmac")]
+            Self::CmacAes256 => "CMAC-AES256",
+
+            #[cfg(feature = "hmac")]
+            Self::Hmac(digest) => hmac_name(*digest),
+
+            #[cfg(feature = "sha3")]
+            Self::KMAC128 => "KMAC128",
+
+            #[cfg(feature = "sha3")]
+            Self::KMAC256 => "KMAC256",
+
+            #[cfg(feature = "poly1305")]
+            Self::Poly1305 => "Poly1305",
+
+            #[cfg(feature = "siphash")]
+            Self::SipHash24 => "SipHash-2-4",
+        }
+    }
+
+    /// Returns `true` if the MAC takes a customization string, see
+    /// [`Mac::new_customized_mac`].
+    #[must_use]
+    pub const fn is_customizable(&self) -> bool {
+        match self {
+            #[cfg(feature = "sha3")]
+            Self::KMAC128 | Self::KMAC256 => true,
+
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Returns the key length in bytes the MAC requires, or `None` if it
+    /// supports keys of any length.
+    #[must_use]
+    pub const fn key_len(&self) -> Option<usize> {
+        match self {
+            #[cfg(feature = "cmac")]
+            Self::CmacAes128 => Some(16),
+
+            #[cfg(feature = "cmac")]
+            Self::CmacAes192 => Some(24),
+
+            #[cfg(feature = "cmac")]
+            Self::CmacAes256 => Some(32),
+
+            #[cfg(feature = "poly1305")]
+            Self::Poly1305 => Some(poly1305::KEY_SIZE),
+
+            #[cfg(feature = "siphash")]
+            Self::SipHash24 => Some(16),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns a new MAC instance keyed with the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the MAC doesn't support the key length, see
+    /// [`Mac::key_len`].
+    pub fn new_mac(self, key: &[u8]) -> Result<Box<dyn DynMac>, Error> {
+        self.new_customized_mac(key, b"")
+    }
+
+    /// Returns a new MAC instance keyed with the key and customized with the
+    /// string, for domain separation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the MAC doesn't support the key length, or if the
+    /// customization is not empty and the MAC doesn't take one, see
+    /// [`Mac::is_customizable`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::mac::Mac;
+    ///
+    /// let mut mac = Mac::KMAC128
+    ///     .new_customized_mac(b"key", b"My Tagged Application")
+    ///     .unwrap();
+    /// mac.update(b"foo");
+    ///
+    /// assert_eq!(mac.finalize().len(), 32);
+    /// ```
+    pub fn new_customized_mac(
+        self,
+        key: &[u8],
+        customization: &[u8],
+    ) -> Result<Box<dyn DynMac>, Error> {
+        if !customization.is_empty() && !self.is_customizable() {
+            return Err(Error::Customization(self));
+        }
+
+        #[cfg(any(
+            feature = "cmac",
+            feature = "hmac",
+            feature = "poly1305",
+            feature = "siphash"
+        ))]
+        let key_length = |_| Error::KeyLength(self, key.len());
+
+        match self {
+            #[cfg(feature = "cmac")]
+            Self::CmacAes128 => Ok(Box::new(
+                cmac::Cmac::<aes::Aes128>::new_from_slice(key)
+                    .map_err(key_length)?,
+            )),
+
+            #[cfg(feature = "cmac")]
+            Self::CmacAes192 => Ok(Box::new(
+                cmac::Cmac::<aes::Aes192>::new_from_slice(key)
+                    .map_err(key_length)?,
+            )),
+
+            #[cfg(feature = "cmac")]
+            Self::CmacAes256 => Ok(Box::new(
+                cmac::Cmac::<aes::Aes256>::new_from_slice(key)
+                    .map_err(key_length)?,
+            )),
+
+            #[cfg(feature = "hmac")]
+            Self::Hmac(digest) => with_digest!(digest, |D| {
+                let mac = hmac::SimpleHmac::<D>::new_from_slice(key)
+                    .map_err(key_length)?;
+                Ok(Box::new(mac) as Box<dyn DynMac>)
+            }),
+
+            #[cfg(feature = "sha3")]
+            Self::KMAC128 => {
+                Ok(Box::new(crate::kmac::Kmac128::new(key, customization)))
+            }
+
+            #[cfg(feature = "sha3")]
+            Self::KMAC256 => {
+                Ok(Box::new(crate::kmac::Kmac256::new(key, customization)))
+            }
+
+            #[cfg(feature = "poly1305")]
+            Self::Poly1305 => {
+                Ok(Box::new(Poly1305::new(key).map_err(key_length)?))
+            }
+
+            #[cfg(feature = "siphash")]
+            Self::SipHash24 => {
+                Ok(Box::new(SipHash24::new(key).map_err(key_length)?))
+            }
+        }
+    }
+
+    /// Returns the MAC of the data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the MAC doesn't support the key length.
+    pub fn compute(self, key: &[u8], data: &[u8]) -> Result<Output, Error> {
+        let mut mac = self.new_mac(key)?;
+        mac.update(data);
+        Ok(mac.finalize())
+    }
+
+    /// Returns `true` if the tag is the MAC of the data, comparing in
+    /// constant time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the MAC doesn't support the key length.
+    pub fn verify(
+        self,
+        key: &[u8],
+        data: &[u8],
+        tag: &[u8],
+    ) -> Result<bool, Error> {
+        Ok(self.compute(key, data)?.ct_eq(tag))
+    }
+}
+
+impl fmt::Display for Mac {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Defines [`ValueEnum::value_variants`] for [`Mac`] from the table.
+///
+/// HMAC is only offered over cryptographic digest algorithms, so the other
+/// entries are dropped one by one.
+macro_rules! define_value_variants {
+    (@munch [$($hmac:tt)*]) => {
+        fn value_variants<'a>() -> &'a [Self] {
+            &[
+                #[cfg(feature = "cmac")]
+                Self::CmacAes128,
+                #[cfg(feature = "cmac")]
+                Self::CmacAes192,
+                #[cfg(feature = "cmac")]
+                Self::CmacAes256,
+                $($hmac)*
+                #[cfg(feature = "sha3")]
+                Self::KMAC128,
+                #[cfg(feature = "sha3")]
+                Self::KMAC256,
+                #[cfg(feature = "poly1305")]
+                Self::Poly1305,
+                #[cfg(feature = "siphash")]
+                Self::SipHash24,
+            ]
+        }
+    };
+
+    (
+        @munch [$($hmac:tt)*]
+        #[cfg(feature = $feature:literal)]
+        $variant:ident {
+            name: $name:literal,
+            description: $description:literal,
+            cryptographic: true,
+            $($fields:tt)*
+        }
+        $($rest:tt)*
+    ) => {
+        define_value_variants! {
+            @munch [
+                $($hmac)*
+                #[cfg(all(feature = "hmac", feature = $feature))]
+                Self::Hmac(Digest::$variant),
+            ]
+            $($rest)*
+        }
+    };
+
+    (
+        @munch [$($hmac:tt)*]
+        #[cfg(feature = $feature:literal)]
+        $variant:ident { $($fields:tt)* }
+        $($rest:tt)*
+    ) => {
+        define_value_variants! { @munch [$($hmac)*] $($rest)* }
+    };
+
+    ([] $($table:tt)*) => {
+        define_value_variants! { @munch [] $($table)* }
+    };
+}
+
+impl ValueEnum for Mac {
+    crate::table::for_each_digest!(define_value_variants);
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+/// Error constructing a MAC instance.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The MAC doesn't support the key length in bytes.
+    KeyLength(Mac, usize),
+
+    /// The MAC doesn't take a customization string.
+    Customization(Mac),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyLength(mac, len) => {
+                write!(f, "{mac} doesn't support {len} byte keys")
+            }
+            Self::Customization(mac) => {
+                write!(f, "{mac} doesn't take a customization string")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Object-safe MAC instance, like [`DynDigest`](crate::DynDigest) for
+/// digests.
+pub trait DynMac {
+    /// Digests input data.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the MAC, consuming the instance.
+    fn finalize(self: Box<Self>) -> Output;
+
+    /// Returns the MAC and resets the instance to its keyed initial state.
+    fn finalize_reset(&mut self) -> Output;
+
+    /// Resets the instance to its keyed initial state.
+    fn reset(&mut self);
+
+    /// Returns the MAC length in bytes.
+    fn output_size(&self) -> usize;
+
+    /// Clones the instance into a boxed trait object.
+    fn box_clone(&self) -> Box<dyn DynMac>;
+}
+
+impl<T> DynMac for T
+where
+    T: digest::Mac + FixedOutputReset + Clone + 'static,
+{
+    fn update(&mut self, data: &[u8]) {
+        digest::Mac::update(self, data);
+    }
+
+    fn finalize(self: Box<Self>) -> Output {
+        digest::Mac::finalize(*self).into_bytes().to_vec().into()
+    }
+
+    fn finalize_reset(&mut self) -> Output {
+        digest::Mac::finalize_reset(self)
+            .into_bytes()
+            .to_vec()
+            .into()
+    }
+
+    fn reset(&mut self) {
+        digest::Mac::reset(self);
+    }
+
+    fn output_size(&self) -> usize {
+        <T as digest::OutputSizeUser>::output_size()
+    }
+
+    fn box_clone(&self) -> Box<dyn DynMac> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynMac> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Poly1305 buffering partial blocks, so it can be updated incrementally
+/// like any other MAC.
+#[cfg(feature = "poly1305")]
+#[derive(Clone)]
+struct Poly1305 {
+    hasher: poly1305::Poly1305,
+    keyed: poly1305::Poly1305,
+    buffer: Vec<u8>,
+}
+
+#[cfg(feature = "poly1305")]
+impl Poly1305 {
+    /// Returns a new instance with the key.
+    fn new(key: &[u8]) -> Result<Self, digest::InvalidLength> {
+        let keyed = poly1305::Poly1305::new_from_slice(key)?;
+
+        Ok(Self {
+            hasher: keyed.clone(),
+            keyed,
+            buffer: Vec::with_capacity(poly1305::BLOCK_SIZE),
+        })
+    }
+}
+
+#[cfg(feature = "poly1305")]
+impl digest::Update for Poly1305 {
+    fn update(&mut self, data: &[u8]) {
+        use poly1305::universal_hash::UniversalHash;
+
+        self.buffer.extend_from_slice(data);
+        let full =
+            self.buffer.len() - self.buffer.len() % poly1305::BLOCK_SIZE;
+        let blocks = self.buffer[..full]
+            .chunks_exact(poly1305::BLOCK_SIZE)
+            .map(|chunk| *poly1305::Block::from_slice(chunk))
+            .collect::<Vec<_>>();
+        self.hasher.update(&blocks);
+        self.buffer.drain(..full);
+    }
+}
+
+#[cfg(feature = "poly1305")]
+impl digest::OutputSizeUser for Poly1305 {
+    type OutputSize = digest::consts::U16;
+}
+
+#[cfg(feature = "poly1305")]
+impl digest::FixedOutput for Poly1305 {
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.hasher.compute_unpadded(&self.buffer));
+    }
+}
+
+#[cfg(feature = "poly1305")]
+impl FixedOutputReset for Poly1305 {
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        let hasher = std::mem::replace(&mut self.hasher, self.keyed.clone());
+        out.copy_from_slice(&hasher.compute_unpadded(&self.buffer));
+        self.buffer.clear();
+    }
+}
+
+#[cfg(feature = "poly1305")]
+impl digest::Reset for Poly1305 {
+    fn reset(&mut self) {
+        self.hasher = self.keyed.clone();
+        self.buffer.clear();
+    }
+}
+
+#[cfg(feature = "poly1305")]
+impl digest::MacMarker for Poly1305 {}
+
+/// SipHash-2-4 with the output in little endian byte order, like the
+/// reference implementation.
+#[cfg(feature = "siphash")]
+#[derive(Clone)]
+struct SipHash24 {
+    hasher: siphasher::sip::SipHasher24,
+    keyed: siphasher::sip::SipHasher24,
+}
+
+#[cfg(feature = "siphash")]
+impl SipHash24 {
+    /// Returns a new instance with the key.
+    fn new(key: &[u8]) -> Result<Self, digest::InvalidLength> {
+        let key = key.try_into().map_err(|_| digest::InvalidLength)?;
+        let keyed = siphasher::sip::SipHasher24::new_with_key(key);
+
+        Ok(Self {
+            hasher: keyed,
+            keyed,
+        })
+    }
+}
+
+#[cfg(feature = "siphash")]
+impl digest::Update for SipHash24 {
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.write(data);
+    }
+}
+
+#[cfg(feature = "siphash")]
+impl digest::OutputSizeUser for SipHash24 {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "siphash")]
+impl digest::FixedOutput for SipHash24 {
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.hasher.finish().to_le_bytes());
+    }
+}
+
+#[cfg(feature = "siphash")]
+impl FixedOutputReset for SipHash24 {
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.hasher.finish().to_le_bytes());
+        self.hasher = self.keyed;
+    }
+}
+
+#[cfg(feature = "siphash")]
+impl digest::Reset for SipHash24 {
+    fn reset(&mut self) {
+        self.hasher = self.keyed;
+    }
+}
+
+#[cfg(feature = "siphash")]
+impl digest::MacMarker for SipHash24 {}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    #[cfg(any(
+        all(feature = "hmac", feature = "sha2"),
+        all(feature = "cmac", feature = "poly1305", feature = "sha3")
+    ))]
+    use super::Error;
+    use super::Mac;
+    #[cfg(feature = "hmac")]
+    use crate::Digest;
+
+    #[cfg(all(feature = "hmac", feature = "sha2"))]
+    #[test]
+    fn hmac() {
+        // RFC 4231 test case 2
+        let cases = [
+            (
+                Digest::SHA256,
+                "5bdcc146bf60754e6a042426089575c7\
+                 5a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                Digest::SHA512,
+                "164b7a7bfcf819e2e395fbe73b56e0a3\
+                 87bd64222e831fd610270cd7ea250554\
+                 9758bf75c05a994a6d034f65f8f0e6fd\
+                 caeab1a34d4a6b4b636e070a38bce737",
+            ),
+        ];
+
+        for (digest, expected) in cases {
+            let mac = Mac::Hmac(digest);
+            let tag = mac.compute(b"Jefe", b"what do ya want for nothing?");
+
+            assert_eq!(tag.unwrap().to_string(), expected);
+        }
+    }
+
+    #[cfg(all(feature = "hmac", feature = "md5"))]
+    #[test]
+    fn dyn_mac() {
+        let mac = Mac::Hmac(Digest::MD5);
+        let mut instance = mac.new_mac(b"key").unwrap();
+        instance.update(b"foo");
+        let fork = instance.clone();
+        let first = instance.finalize_reset();
+        instance.update(b"foo");
+
+        assert_eq!(instance.output_size(), 16);
+        assert_eq!(first, instance.finalize());
+        assert_eq!(first, fork.finalize());
+        assert_eq!(first, mac.compute(b"key", b"foo").unwrap());
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn kmac() {
+        // NIST SP 800-185 samples
+        let key = (0x40..0x60).collect::<Vec<u8>>();
+        let data = [0, 1, 2, 3];
+        let cases = [
+            (
+                Mac::KMAC128,
+                &b""[..],
+                "e5780b0d3ea6f7d3a429c5706aa43a00\
+                 fadbd7d49628839e3187243f456ee14e",
+            ),
+            (
+                Mac::KMAC128,
+                b"My Tagged Application",
+                "3b1fba963cd8b0b59e8c1a6d71888b71\
+                 43651af8ba0a7070c0979e2811324aa5",
+            ),
+            (
+                Mac::KMAC256,
+                b"My Tagged Application",
+                "20c570c31346f703c9ac36c61c03cb64\
+                 c3970d0cfc787e9b79599d273a68d2f7\
+                 f69d4cc3de9d104a351689f27cf6f595\
+                 1f0103f33f4f24871024d9c27773a8dd",
+            ),
+        ];
+
+        for (mac, customization, expected) in cases {
+            let mut instance =
+                mac.new_customized_mac(&key, customization).unwrap();
+            instance.update(b"garbage");
+            instance.reset();
+            instance.update(&data);
+
+            assert_eq!(instance.finalize_reset().to_string(), expected);
+
+            instance.update(&data);
+            assert_eq!(instance.finalize().to_string(), expected);
+        }
+    }
+
+    #[cfg(all(feature = "hmac", feature = "sha2"))]
+    #[test]
+    fn customization() {
+        let error = Mac::Hmac(Digest::SHA256)
+            .new_customized_mac(b"key", b"x")
+            .err()
+            .unwrap();
+
+        assert_eq!(error, Error::Customization(Mac::Hmac(Digest::SHA256)));
+        assert_eq!(
+            error.to_string(),
+            "HMAC-SHA256 doesn't take a customization string"
+        );
+    }
+
+    #[cfg(all(feature = "hmac", feature = "sha2"))]
+    #[test]
+    fn long_key() {
+        let mac = Mac::Hmac(Digest::SHA256);
+
+        // RFC 4231 test case 6
+        assert_eq!(
+            mac.compute(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )
+            .unwrap()
+            .to_string(),
+            "60e431591ee0b67f0d8a26aacbf5b77f\
+             8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[cfg(feature = "cmac")]
+    #[test]
+    fn cmac() {
+        let cases = [
+            // RFC 4493 examples 1 and 2
+            (
+                Mac::CmacAes128,
+                "2b7e151628aed2a6abf7158809cf4f3c",
+                &b""[..],
+                "bb1d6929e95937287fa37d129b756746",
+            ),
+            (
+                Mac::CmacAes128,
+                "2b7e151628aed2a6abf7158809cf4f3c",
+                &[
+                    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9,
+                    0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+                ],
+                "070a16b46b4d4144f79bdd9dd04a287c",
+            ),
+            // NIST SP 800-38B examples
+            (
+                Mac::CmacAes192,
+                "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+                b"",
+                "d17ddf46adaacde531cac483de7a9367",
+            ),
+            (
+                Mac::CmacAes256,
+                "603deb1015ca71be2b73aef0857d7781\
+                 1f352c073b6108d72d9810a30914dff4",
+                b"",
+                "028962f61b7bf89efc6b551f4667d983",
+            ),
+        ];
+
+        for (mac, key, data, expected) in cases {
+            let key = crate::encode::decode_hex(key).unwrap();
+
+            assert_eq!(mac.compute(&key, data).unwrap().to_string(), expected);
+        }
+    }
+
+    #[cfg(feature = "poly1305")]
+    #[test]
+    fn poly1305() {
+        // RFC 8439 section 2.5.2
+        let key = crate::encode::decode_hex(
+            "85d6be7857556d337f4452fe42d506a8\
+             0103808afb0db2fd4abff6af4149f51b",
+        )
+        .unwrap();
+        let expected = "a8061dc1305136c6c22b8baf0c0127a9";
+
+        let mut instance = Mac::Poly1305.new_mac(&key).unwrap();
+        instance.update(b"garbage");
+        instance.reset();
+        for chunk in b"Cryptographic Forum Research Group".chunks(5) {
+            instance.update(chunk);
+        }
+
+        assert_eq!(instance.finalize_reset().to_string(), expected);
+
+        instance.update(b"Cryptographic Forum Research Group");
+        assert_eq!(instance.finalize().to_string(), expected);
+    }
+
+    #[cfg(feature = "siphash")]
+    #[test]
+    fn siphash() {
+        // reference implementation test vectors
+        let key = (0..16).collect::<Vec<u8>>();
+        let data = (0..63).collect::<Vec<u8>>();
+        let cases = [
+            (&data[..0], "310e0edd47db6f72"),
+            (&data[..15], "e545be4961ca29a1"),
+            (&data[..], "724506eb4c328a95"),
+        ];
+
+        for (data, expected) in cases {
+            let mut instance = Mac::SipHash24.new_mac(&key).unwrap();
+            instance.update(b"garbage");
+            instance.reset();
+            for chunk in data.chunks(3) {
+                instance.update(chunk);
+            }
+
+            assert_eq!(instance.finalize_reset().to_string(), expected);
+
+            instance.update(data);
+            assert_eq!(instance.finalize().to_string(), expected);
+        }
+
+        assert_eq!(
+            Mac::SipHash24.new_mac(&key[1..]).err(),
+            Some(super::Error::KeyLength(Mac::SipHash24, 15))
+        );
+    }
+
+    #[cfg(all(feature = "cmac", feature = "poly1305", feature = "sha3"))]
+    #[test]
+    fn key_len() {
+        for (mac, len) in [
+            (Mac::CmacAes128, 16),
+            (Mac::CmacAes192, 24),
+            (Mac::CmacAes256, 32),
+            (Mac::Poly1305, 32),
+        ] {
+            assert_eq!(mac.key_len(), Some(len));
+            assert!(mac.new_mac(&vec![0; len]).is_ok());
+            assert_eq!(
+                mac.new_mac(&vec![0; len + 1]).err(),
+                Some(Error::KeyLength(mac, len + 1))
+            );
+        }
+
+        assert_eq!(Mac::KMAC128.key_len(), None);
+        assert_eq!(
+            Mac::CmacAes128
+                .compute(b"key", b"")
+                .unwrap_err()
+                .to_string(),
+            "CMAC-AES128 doesn't support 3 byte keys"
+        );
+    }
+
+    #[test]
+    fn value_enum() {
+        for mac in Mac::value_variants() {
+            assert_eq!(Mac::from_str(mac.name(), false), Ok(*mac));
+        }
+
+        #[cfg(feature = "hmac")]
+        for digest in Digest::value_variants() {
+            assert_eq!(
+                Mac::value_variants().contains(&Mac::Hmac(*digest)),
+                digest.is_cryptographic(),
+                "{digest}"
+            );
+        }
+
+        #[cfg(all(feature = "hmac", feature = "sha3"))]
+        assert_eq!(Mac::Hmac(Digest::SHA3_256).to_string(), "HMAC-SHA3_256");
+    }
+}
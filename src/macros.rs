@@ -0,0 +1,97 @@
+//! Contains the static-dispatch [`with_digest!`] macro.
+
+/// Defines [`with_digest!`] from the table.
+///
+/// The `#[cfg]` attributes of the table can't be part of an exported macro,
+/// as they would be evaluated in the calling crate, so the entries of
+/// disabled features are dropped here one by one. The `$` token is passed in
+/// brackets to write the metavariables of the defined macro.
+macro_rules! define_with_digest {
+    (@munch [$d:tt] [$($variant:ident)*]) => {
+        /// Evaluates an expression with a type alias for the concrete hasher
+        /// type of a [`Digest`](crate::Digest).
+        ///
+        /// The expression is expanded once per enabled digest algorithm, so
+        /// generic code using the [`digest::Digest`] trait is monomorphized
+        /// instead of going through [`DynDigest`](crate::DynDigest).
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use clap_digest::{with_digest, Digest};
+        ///
+        /// fn hash_generic<D: digest::Digest>(data: &[u8]) -> Vec<u8> {
+        ///     D::digest(data).to_vec()
+        /// }
+        ///
+        /// let digest = Digest::MD5;
+        /// let hash = with_digest!(digest, |D| hash_generic::<D>(b"foo"));
+        ///
+        /// assert_eq!(hash[..4], [0xac, 0xbd, 0x18, 0xdb]);
+        /// ```
+        #[macro_export]
+        macro_rules! with_digest {
+            ($d digest:expr, |$d ty:ident| $d body:expr) => {{
+                let digest: $crate::Digest = $d digest;
+                match digest {
+                    $(
+                        $crate::Digest::$variant => {
+                            type $d ty = $crate::__private::$variant;
+                            $d body
+                        }
+                    )*
+                    // Digest is non_exhaustive outside of this crate
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!("all enabled digests are covered"),
+                }
+            }};
+        }
+    };
+
+    (
+        @munch [$d:tt] [$($variant:ident)*]
+        #[cfg(feature = $feature:literal)]
+        $next:ident { $($fields:tt)* }
+        $($rest:tt)*
+    ) => {
+        #[cfg(feature = $feature)]
+        define_with_digest! { @munch [$d] [$($variant)* $next] $($rest)* }
+
+        #[cfg(not(feature = $feature))]
+        define_with_digest! { @munch [$d] [$($variant)*] $($rest)* }
+    };
+
+    ([$d:tt] $($table:tt)*) => {
+        define_with_digest! { @munch [$d] [] $($table)* }
+    };
+}
+
+crate::table::for_each_digest!(define_with_digest [$]);
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use crate::{Digest, DynDigest};
+
+    fn hash_generic<D: digest::Digest>(data: &[u8]) -> Box<[u8]> {
+        D::digest(data).to_vec().into_boxed_slice()
+    }
+
+    #[test]
+    fn same_as_dyn() {
+        for digest in Digest::value_variants() {
+            let mut hasher: Box<dyn DynDigest> = (*digest).into();
+            hasher.update(b"foo");
+
+            assert_eq!(
+                with_digest!(*digest, |D| hash_generic::<D>(b"foo")),
+                hasher.finalize()
+            );
+        }
+    }
+}
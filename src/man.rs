@@ -0,0 +1,79 @@
+//! Contains helpers to document digest algorithms in man pages.
+//!
+//! The generated section can be spliced into man pages generated with
+//! [clap_mangen][]:
+//!
+//! ```
+//! use clap::Command;
+//!
+//! let cli = Command::new("myapp").arg(clap_digest::arg::digest());
+//!
+//! let mut page = Vec::new();
+//! // clap_mangen::Man::new(cli).render(&mut page)?;
+//! clap_digest::man::render_digests_section(&mut page)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [clap_mangen]: https://crates.io/crates/clap_mangen
+
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use roff::{bold, line_break, roman, Roff};
+
+use crate::DigestChoice;
+
+/// Returns a `DIGESTS` man page section listing the supported digest
+/// algorithms, including the [registered](crate::registry) custom ones, with
+/// their descriptions and output sizes.
+///
+/// # Examples
+///
+/// ```
+/// let section = clap_digest::man::digests_section().to_roff();
+///
+/// assert!(section.starts_with(".SH DIGESTS"));
+/// ```
+#[must_use]
+pub fn digests_section() -> Roff {
+    let mut roff = Roff::new();
+
+    roff.control("SH", ["DIGESTS"]);
+
+    for digest in DigestChoice::value_variants() {
+        roff.control("TP", []).text([bold(digest.name())]).text([
+            roman(digest.description()),
+            line_break(),
+            roman(format!("Output size: {} bits", digest.output_size() * 8)),
+        ]);
+    }
+
+    roff
+}
+
+/// Renders the section of [`digests_section`] into a writer.
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn render_digests_section(w: &mut dyn Write) -> io::Result<()> {
+    digests_section().to_writer(w)
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn digests_section() {
+        let mut out = Vec::new();
+        super::render_digests_section(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains(".SH DIGESTS"));
+        assert!(out.contains("SHA\\-2 with 256 bit output"));
+        assert!(out.contains("Output size: 256 bits"));
+    }
+}
@@ -0,0 +1,224 @@
+//! Contains generation of checksum files, like `sha256sum <file>...`.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::manifest::Manifest;
+//! use clap_digest::Digest;
+//!
+//! let manifest = Manifest::new(Digest::SHA256)
+//!     .generate(["Cargo.toml", "./README.md", "Cargo.toml"])?;
+//!
+//! let files = manifest
+//!     .lines()
+//!     .map(|line| line.split_once("  ").unwrap().1)
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!(files, ["Cargo.toml", "README.md"]);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+
+use crate::format::{Formatter, Mode, Style};
+use crate::Digest;
+
+/// Generates checksum files.
+#[derive(Clone, Debug)]
+pub struct Manifest {
+    digest: Digest,
+    style: Style,
+    mode: Mode,
+    base: Option<PathBuf>,
+}
+
+impl Manifest {
+    /// Returns a new generator of GNU checksum files in text mode.
+    #[must_use]
+    pub const fn new(digest: Digest) -> Self {
+        Self {
+            digest,
+            style: Style::Gnu,
+            mode: Mode::Text,
+            base: None,
+        }
+    }
+
+    /// Sets the style of the checksum lines.
+    #[must_use]
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the mode marked in GNU checksum lines.
+    #[must_use]
+    pub const fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Writes paths relative to this base directory.
+    ///
+    /// Paths outside of the base directory are written unchanged. Files are
+    /// still read as given, i.e. relative to the current directory.
+    #[must_use]
+    pub fn base(mut self, base: impl Into<PathBuf>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    /// Returns the checksum file content for the files.
+    ///
+    /// The written paths are normalized, i.e. without the base directory,
+    /// without `.` components and with `/` as separator. The lines are
+    /// sorted by these paths and files given more than once are listed once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading a file fails.
+    pub fn generate<P: AsRef<Path>>(
+        &self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> io::Result<String> {
+        let mut files = paths
+            .into_iter()
+            .map(|path| {
+                let path = path.as_ref();
+                (self.normalize(path), path.to_path_buf())
+            })
+            .collect::<Vec<_>>();
+
+        files.sort();
+        files.dedup_by(|a, b| a.0 == b.0);
+
+        let formatter = Formatter::new().style(self.style).mode(self.mode);
+        let mut content = String::new();
+
+        for (name, path) in files {
+            let output = crate::hash_file(&path, self.digest)?;
+            content.push_str(&formatter.line(self.digest, &output, &name));
+        }
+
+        Ok(content)
+    }
+
+    /// Writes the checksum file for the files to `dest`.
+    ///
+    /// The content is written to a temporary file next to `dest` first,
+    /// which is then renamed, so `dest` is never left partially written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading a file or writing `dest` fails.
+    pub fn write<P: AsRef<Path>>(
+        &self,
+        paths: impl IntoIterator<Item = P>,
+        dest: impl AsRef<Path>,
+    ) -> io::Result<()> {
+        let content = self.generate(paths)?;
+        let dest = dest.as_ref();
+
+        let mut name = dest.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}.tmp", std::process::id()));
+        let temp = dest.with_file_name(name);
+
+        let result = File::create(&temp).and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        });
+
+        match result.and_then(|()| fs::rename(&temp, dest)) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                let _ = fs::remove_file(&temp);
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns the path as written to the checksum file.
+    fn normalize(&self, path: &Path) -> PathBuf {
+        let path = self
+            .base
+            .as_deref()
+            .and_then(|base| path.strip_prefix(base).ok())
+            .unwrap_or(path);
+
+        let mut normalized = PathBuf::new();
+        let mut components = path
+            .components()
+            .filter(|component| *component != Component::CurDir);
+
+        if let Some(component) = components.next() {
+            normalized.push(component);
+        }
+
+        let mut normalized = normalized.display().to_string();
+
+        for component in components {
+            if !normalized.ends_with('/') {
+                normalized.push('/');
+            }
+
+            normalized.push_str(&component.as_os_str().to_string_lossy());
+        }
+
+        normalized.into()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "md5"))]
+mod tests {
+    use super::Manifest;
+    use crate::format::Style;
+    use crate::verify::Verifier;
+    use crate::Digest;
+
+    #[test]
+    fn generate() {
+        let dir = std::env::temp_dir().join("clap-digest-manifest");
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("b"), "foo").unwrap();
+        std::fs::write(dir.join("a"), "foo").unwrap();
+        std::fs::write(dir.join("sub").join("c"), "foo").unwrap();
+
+        let paths = [dir.join("sub/./c"), dir.join("b"), dir.join("a")];
+        let md5 = "acbd18db4cc2f85cedef654fccc4a4d8";
+
+        let content = Manifest::new(Digest::MD5)
+            .base(&dir)
+            .generate(&paths)
+            .unwrap();
+        assert_eq!(content, format!("{md5}  a\n{md5}  b\n{md5}  sub/c\n"));
+
+        let content = Manifest::new(Digest::MD5)
+            .style(Style::Bsd)
+            .base(&dir)
+            .generate([&paths[1], &paths[1]])
+            .unwrap();
+        assert_eq!(content, format!("MD5 (b) = {md5}\n"));
+
+        let content = Manifest::new(Digest::MD5).generate(&paths).unwrap();
+        assert_eq!(Verifier::new().verify(&content).exit_code(), 0);
+
+        let dest = dir.join("MD5SUMS");
+        Manifest::new(Digest::MD5).write(&paths, &dest).unwrap();
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), content);
+
+        let missing = [dir.join("missing")];
+        assert!(Manifest::new(Digest::MD5).write(missing, &dest).is_err());
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), content);
+
+        let files = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files, 4);
+    }
+}
@@ -0,0 +1,315 @@
+//! Contains Merkle trees over fixed-size chunks of data.
+//!
+//! Leaves and inner nodes are hashed with domain separation like RFC 6962,
+//! i.e. a leaf is `H(0x00 || chunk)` and an inner node is `H(0x01 || left ||
+//! right)`. A node without sibling is promoted to the next level unchanged,
+//! instead of being paired with itself.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::merkle::MerkleTree;
+//! use clap_digest::Digest;
+//!
+//! let data = b"The quick brown fox jumps over the lazy dog";
+//! let tree = MerkleTree::new(data, 8, Digest::SHA256);
+//!
+//! assert_eq!(tree.leaves().len(), 6);
+//!
+//! let proof = tree.proof(2).unwrap();
+//! assert!(proof.verify(&data[16..24], tree.root(), Digest::SHA256));
+//! assert!(!proof.verify(b"tampered", tree.root(), Digest::SHA256));
+//! ```
+
+use std::io::{self, Read};
+
+use digest::Update;
+
+use crate::{Digest, Hasher, Output};
+
+/// Prefix of leaf hashes.
+const LEAF: u8 = 0x00;
+
+/// Prefix of inner node hashes.
+const NODE: u8 = 0x01;
+
+/// Merkle tree over fixed-size chunks of data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleTree {
+    digest: Digest,
+    chunk_size: usize,
+    levels: Vec<Vec<Output>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree over chunks of this size, at least 1.
+    ///
+    /// The last chunk may be shorter. Empty data has one empty chunk.
+    #[must_use]
+    pub fn new(data: &[u8], chunk_size: usize, digest: Digest) -> Self {
+        let chunk_size = chunk_size.max(1);
+
+        let leaves = if data.is_empty() {
+            vec![leaf(b"", digest)]
+        } else {
+            data.chunks(chunk_size)
+                .map(|chunk| leaf(chunk, digest))
+                .collect()
+        };
+
+        Self::from_leaves(leaves, chunk_size, digest)
+    }
+
+    /// Builds the tree over chunks of this size, at least 1, read from the
+    /// reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails.
+    pub fn from_reader(
+        reader: impl Read,
+        chunk_size: usize,
+        digest: Digest,
+    ) -> io::Result<Self> {
+        let chunk_size = chunk_size.max(1);
+        let mut reader = reader.take(u64::MAX);
+        let mut chunk = Vec::with_capacity(chunk_size);
+        let mut leaves = Vec::new();
+
+        loop {
+            chunk.clear();
+            reader.set_limit(chunk_size as u64);
+            reader.read_to_end(&mut chunk)?;
+
+            if chunk.is_empty() && !leaves.is_empty() {
+                break;
+            }
+
+            leaves.push(leaf(&chunk, digest));
+
+            if chunk.len() < chunk_size {
+                break;
+            }
+        }
+
+        Ok(Self::from_leaves(leaves, chunk_size, digest))
+    }
+
+    /// Builds the levels above the leaves.
+    fn from_leaves(
+        leaves: Vec<Output>,
+        chunk_size: usize,
+        digest: Digest,
+    ) -> Self {
+        let mut levels = vec![leaves];
+
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node(left, right, digest),
+                    [single] => single.clone(),
+                    _ => unreachable!("chunks of 2"),
+                })
+                .collect();
+
+            levels.push(next);
+        }
+
+        Self {
+            digest,
+            chunk_size,
+            levels,
+        }
+    }
+
+    /// Returns the digest algorithm of the tree.
+    #[must_use]
+    pub const fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns the chunk size.
+    #[must_use]
+    pub const fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the root hash.
+    #[must_use]
+    pub fn root(&self) -> &Output {
+        // there is always at least one leaf, so the last level is the root
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Returns the leaf hashes, one per chunk.
+    #[must_use]
+    pub fn leaves(&self) -> &[Output] {
+        &self.levels[0]
+    }
+
+    /// Returns the inclusion proof of the chunk with this index, or
+    /// [`None`] if there is no such chunk.
+    #[must_use]
+    pub fn proof(&self, index: usize) -> Option<Proof> {
+        if index >= self.leaves().len() {
+            return None;
+        }
+
+        let mut siblings = Vec::new();
+        let mut position = index;
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = position ^ 1;
+
+            if let Some(output) = level.get(sibling) {
+                let side = if sibling < position {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+
+                siblings.push((side, output.clone()));
+            }
+
+            position /= 2;
+        }
+
+        Some(Proof { index, siblings })
+    }
+}
+
+/// Side of a sibling in an inclusion [`Proof`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Side {
+    /// The sibling is hashed before the node.
+    Left,
+
+    /// The sibling is hashed after the node.
+    Right,
+}
+
+/// Inclusion proof of a chunk in a [`MerkleTree`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proof {
+    index: usize,
+    siblings: Vec<(Side, Output)>,
+}
+
+impl Proof {
+    /// Returns the index of the chunk.
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the sibling hashes from the leaf up to the root.
+    #[must_use]
+    pub fn siblings(&self) -> &[(Side, Output)] {
+        &self.siblings
+    }
+
+    /// Returns `true` if the chunk is included in the tree with the root,
+    /// comparing in constant time.
+    #[must_use]
+    pub fn verify(&self, chunk: &[u8], root: &Output, digest: Digest) -> bool {
+        let hash = self.siblings.iter().fold(
+            leaf(chunk, digest),
+            |hash, (side, sibling)| match side {
+                Side::Left => node(sibling, &hash, digest),
+                Side::Right => node(&hash, sibling, digest),
+            },
+        );
+
+        hash.ct_eq(root.as_bytes())
+    }
+}
+
+/// Returns the leaf hash of the chunk.
+fn leaf(chunk: &[u8], digest: Digest) -> Output {
+    let mut hasher = Hasher::new(digest);
+    hasher.update(&[LEAF]);
+    hasher.update(chunk);
+    hasher.finalize_output()
+}
+
+/// Returns the inner node hash of the children.
+fn node(left: &Output, right: &Output, digest: Digest) -> Output {
+    let mut hasher = Hasher::new(digest);
+    hasher.update(&[NODE]);
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+    hasher.finalize_output()
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "md5", feature = "sha2"))]
+    use super::MerkleTree;
+    #[cfg(feature = "sha2")]
+    use super::Side;
+    #[cfg(any(feature = "md5", feature = "sha2"))]
+    use crate::Digest;
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn root() {
+        let tree = MerkleTree::new(b"foobar", 3, Digest::MD5);
+
+        let foo = Digest::MD5.hash(b"\x00foo");
+        let bar = Digest::MD5.hash(b"\x00bar");
+        let root = Digest::MD5
+            .hash(&[&[0x01], foo.as_bytes(), bar.as_bytes()].concat());
+
+        assert_eq!(tree.leaves(), [foo, bar]);
+        assert_eq!(tree.root(), &root);
+
+        let empty = MerkleTree::new(b"", 3, Digest::MD5);
+        assert_eq!(empty.root(), &Digest::MD5.hash(b"\x00"));
+    }
+
+    #[cfg(all(feature = "md5", feature = "sha1"))]
+    #[test]
+    fn from_reader() {
+        let data = (0..=255).collect::<Vec<u8>>();
+
+        for chunk_size in [0, 1, 7, 64, 256, 1000] {
+            let tree = MerkleTree::new(&data, chunk_size, Digest::SHA1);
+            let read =
+                MerkleTree::from_reader(&data[..], chunk_size, Digest::SHA1)
+                    .unwrap();
+
+            assert_eq!(tree, read);
+        }
+
+        let empty = MerkleTree::from_reader(&b""[..], 3, Digest::MD5).unwrap();
+        assert_eq!(empty, MerkleTree::new(b"", 3, Digest::MD5));
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn proof() {
+        let data = b"abcdefghij";
+        let tree = MerkleTree::new(data, 2, Digest::SHA256);
+
+        assert_eq!(tree.leaves().len(), 5);
+        assert!(tree.proof(5).is_none());
+
+        for (index, chunk) in data.chunks(2).enumerate() {
+            let proof = tree.proof(index).unwrap();
+
+            assert_eq!(proof.index(), index);
+            assert!(proof.verify(chunk, tree.root(), Digest::SHA256));
+            assert!(!proof.verify(b"xx", tree.root(), Digest::SHA256));
+        }
+
+        // the fifth leaf is promoted twice, so only the root level pairs it
+        let proof = tree.proof(4).unwrap();
+        assert_eq!(proof.siblings().len(), 1);
+        assert_eq!(proof.siblings()[0].0, Side::Left);
+    }
+}
@@ -0,0 +1,186 @@
+//! Contains the [`MultiDigest`] computing several digests in one pass.
+
+use std::io::{self, Write};
+
+use digest::{Reset, Update};
+
+use crate::{Digest, Hasher, Output};
+
+/// Computes several digests of the same data in one pass.
+///
+/// All data is fed to one [`Hasher`] per digest, so e.g. MD5, SHA-256 and
+/// SHA-512 of an artifact can be computed while reading it only once. It
+/// also implements [`io::Write`], so it can be used with [`io::copy`].
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::{Digest, MultiDigest};
+///
+/// let mut multi = MultiDigest::new(&[Digest::MD5, Digest::SHA256]);
+/// std::io::copy(&mut &b"foo"[..], &mut multi)?;
+///
+/// for (digest, output) in multi.finalize() {
+///     assert_eq!(output, digest.hash(b"foo"));
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MultiDigest {
+    pub(crate) hashers: Vec<Hasher>,
+}
+
+impl MultiDigest {
+    /// Returns a new instance computing the digests, in this order.
+    #[must_use]
+    pub fn new(digests: &[Digest]) -> Self {
+        digests.iter().copied().collect()
+    }
+
+    /// Returns the digests computed, in order.
+    pub fn digests(&self) -> impl Iterator<Item = Digest> + '_ {
+        self.hashers.iter().map(Hasher::digest)
+    }
+
+    /// Returns the number of digests computed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.hashers.len()
+    }
+
+    /// Returns `true` if no digests are computed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hashers.is_empty()
+    }
+
+    /// Updates all hashers with the data.
+    pub fn update(&mut self, data: &[u8]) {
+        for hasher in &mut self.hashers {
+            hasher.update(data);
+        }
+    }
+
+    /// Updates all hashers with the data, each one on a separate thread.
+    ///
+    /// This is only worth it for large chunks of data, e.g. of 1 MiB.
+    #[cfg(feature = "rayon")]
+    pub fn par_update(&mut self, data: &[u8]) {
+        use rayon::prelude::*;
+
+        self.hashers
+            .par_iter_mut()
+            .for_each(|hasher| hasher.update(data));
+    }
+
+    /// Returns the digests with their outputs, in order.
+    #[must_use]
+    pub fn finalize(self) -> Vec<(Digest, Output)> {
+        self.hashers
+            .into_iter()
+            .map(|hasher| (hasher.digest(), hasher.finalize_output()))
+            .collect()
+    }
+
+    /// Returns the digests with their outputs, in order, and resets all
+    /// hashers.
+    pub fn finalize_reset(&mut self) -> Vec<(Digest, Output)> {
+        self.hashers
+            .iter_mut()
+            .map(|hasher| {
+                let output = hasher.clone().finalize_output();
+                hasher.reset();
+                (hasher.digest(), output)
+            })
+            .collect()
+    }
+}
+
+impl Update for MultiDigest {
+    fn update(&mut self, data: &[u8]) {
+        Self::update(self, data);
+    }
+}
+
+impl Reset for MultiDigest {
+    fn reset(&mut self) {
+        for hasher in &mut self.hashers {
+            hasher.reset();
+        }
+    }
+}
+
+impl Write for MultiDigest {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl From<&[Digest]> for MultiDigest {
+    fn from(digests: &[Digest]) -> Self {
+        Self::new(digests)
+    }
+}
+
+impl From<Vec<Digest>> for MultiDigest {
+    fn from(digests: Vec<Digest>) -> Self {
+        digests.into_iter().collect()
+    }
+}
+
+impl FromIterator<Digest> for MultiDigest {
+    fn from_iter<I: IntoIterator<Item = Digest>>(digests: I) -> Self {
+        Self {
+            hashers: digests.into_iter().map(Hasher::new).collect(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "md5", feature = "sha2"))]
+mod tests {
+    use super::MultiDigest;
+    use crate::Digest;
+
+    const DIGESTS: [Digest; 3] = [Digest::MD5, Digest::SHA256, Digest::SHA512];
+
+    #[test]
+    fn multi() {
+        let mut multi = MultiDigest::new(&DIGESTS);
+        multi.update(b"foo");
+        multi.update(b"bar");
+
+        assert_eq!(multi.digests().collect::<Vec<_>>(), DIGESTS);
+
+        let outputs = multi.finalize_reset();
+        assert_eq!(outputs.len(), 3);
+
+        for (expected, (digest, output)) in DIGESTS.iter().zip(outputs) {
+            assert_eq!(digest, *expected);
+            assert_eq!(output, digest.hash(b"foobar"));
+        }
+
+        for (digest, output) in multi.finalize() {
+            assert_eq!(output, digest.hash(b""));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_update() {
+        let mut multi = MultiDigest::from(DIGESTS.to_vec());
+        multi.par_update(b"foo");
+
+        for (digest, output) in multi.finalize() {
+            assert_eq!(output, digest.hash(b"foo"));
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Contains [multihash][] interoperability.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::{multihash, Digest};
+//!
+//! let output = Digest::SHA256.hash(b"foo");
+//! let bytes = multihash::encode(Digest::SHA256, &output).unwrap();
+//!
+//! assert_eq!(bytes[..2], [0x12, 0x20]);
+//! assert_eq!(multihash::decode(&bytes)?, (Digest::SHA256, output));
+//! # Ok::<(), multihash::DecodeError>(())
+//! ```
+//!
+//! [multihash]: https://multiformats.io/multihash/
+
+use std::fmt;
+
+use crate::{Digest, Output};
+
+impl Digest {
+    /// Returns the multihash code from the [multicodec table][table], if
+    /// there is one for this digest algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA256.multihash_code(), Some(0x12));
+    /// ```
+    ///
+    /// [table]: https://github.com/multiformats/multicodec/blob/master/table.csv
+    #[must_use]
+    pub const fn multihash_code(&self) -> Option<u64> {
+        match self {
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2b512 => Some(0xb240),
+
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2s256 => Some(0xb260),
+
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3 => Some(0x1e),
+
+            #[cfg(feature = "md4")]
+            Self::MD4 => Some(0xd4),
+
+            #[cfg(feature = "md5")]
+            Self::MD5 => Some(0xd5),
+
+            #[cfg(feature = "ripemd")]
+            Self::RIPEMD160 => Some(0x1053),
+
+            #[cfg(feature = "ripemd")]
+            Self::RIPEMD256 => Some(0x1054),
+
+            #[cfg(feature = "ripemd")]
+            Self::RIPEMD320 => Some(0x1055),
+
+            #[cfg(feature = "sha1")]
+            Self::SHA1 => Some(0x11),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA224 => Some(0x1013),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => Some(0x12),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => Some(0x20),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => Some(0x13),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512_224 => Some(0x1014),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512_256 => Some(0x1015),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_224 => Some(0x17),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_256 => Some(0x16),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_384 => Some(0x15),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_512 => Some(0x14),
+
+            #[cfg(feature = "sha3")]
+            Self::Keccak224 => Some(0x1a),
+
+            #[cfg(feature = "sha3")]
+            Self::Keccak256 => Some(0x1b),
+
+            #[cfg(feature = "sha3")]
+            Self::Keccak384 => Some(0x1c),
+
+            #[cfg(feature = "sha3")]
+            Self::Keccak512 => Some(0x1d),
+
+            #[cfg(feature = "sm3")]
+            Self::SM3 => Some(0x534d),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the digest algorithm for a multihash code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_multihash_code(0x12), Some(Digest::SHA256));
+    /// ```
+    #[must_use]
+    pub fn from_multihash_code(code: u64) -> Option<Self> {
+        use clap::ValueEnum;
+
+        Self::value_variants()
+            .iter()
+            .find(|digest| digest.multihash_code() == Some(code))
+            .copied()
+    }
+}
+
+/// Returns the multihash of a digest output, i.e. the varint code, the
+/// varint output length and the output bytes.
+///
+/// Returns [`None`] if there is no multihash code for the digest algorithm.
+#[must_use]
+pub fn encode(digest: Digest, output: &Output) -> Option<Vec<u8>> {
+    let code = digest.multihash_code()?;
+
+    let mut bytes = Vec::with_capacity(output.len() + 2 * 9);
+    write_varint(&mut bytes, code);
+    write_varint(&mut bytes, output.len() as u64);
+    bytes.extend_from_slice(output.as_bytes());
+
+    Some(bytes)
+}
+
+/// Returns the digest algorithm and output of a multihash.
+///
+/// # Errors
+///
+/// Returns an error if the multihash is malformed or the digest algorithm
+/// is not supported.
+pub fn decode(bytes: &[u8]) -> Result<(Digest, Output), DecodeError> {
+    let (code, bytes) = read_varint(bytes)?;
+    let (len, bytes) = read_varint(bytes)?;
+
+    let digest = Digest::from_multihash_code(code)
+        .ok_or(DecodeError::UnsupportedCode(code))?;
+
+    if usize::try_from(len).ok() != Some(bytes.len()) {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    Ok((digest, bytes.to_vec().into()))
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        // truncation to the lowest 7 bits is intended
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+
+    // truncation is impossible, n < 0x80
+    #[allow(clippy::cast_possible_truncation)]
+    bytes.push(n as u8);
+}
+
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), DecodeError> {
+    let mut n = 0;
+
+    // unsigned varints are limited to 9 bytes, i.e. 63 bits
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        n |= u64::from(byte & 0x7f) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            return Ok((n, &bytes[i + 1..]));
+        }
+    }
+
+    Err(DecodeError::InvalidVarint)
+}
+
+/// Error decoding a multihash.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// A varint is truncated or too long.
+    InvalidVarint,
+
+    /// The multihash code is unknown or its digest algorithm is not enabled.
+    UnsupportedCode(u64),
+
+    /// The output length does not match the remaining bytes.
+    InvalidLength,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidVarint => write!(f, "invalid varint"),
+            Self::UnsupportedCode(code) => {
+                write!(f, "unsupported multihash code {code:#x}")
+            }
+            Self::InvalidLength => write!(f, "invalid digest output length"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::DecodeError;
+    use crate::Digest;
+
+    #[test]
+    fn roundtrip() {
+        for digest in Digest::value_variants() {
+            let output = digest.hash(b"foo");
+
+            if let Some(bytes) = super::encode(*digest, &output) {
+                assert_eq!(super::decode(&bytes), Ok((*digest, output)));
+            }
+        }
+    }
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn varint() {
+        let output = Digest::BLAKE2b512.hash(b"foo");
+        let bytes = super::encode(Digest::BLAKE2b512, &output).unwrap();
+
+        assert_eq!(bytes[..4], [0xc0, 0xe4, 0x02, 0x40]);
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(super::decode(&[0x80]), Err(DecodeError::InvalidVarint));
+        assert_eq!(
+            super::decode(&[0x00, 0x00]),
+            Err(DecodeError::UnsupportedCode(0))
+        );
+        assert_eq!(
+            super::decode(&[0x12, 0x20, 0x00]),
+            Err(DecodeError::InvalidLength)
+        );
+    }
+}
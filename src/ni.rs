@@ -0,0 +1,295 @@
+//! Contains [RFC 6920][rfc] named information (`ni://`) URI support.
+//!
+//! Only the digest algorithms of the [IANA registry][registry] with their
+//! full output size are supported, truncated variants like `sha-256-128` are
+//! not.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::{ni::NamedInformation, Digest};
+//!
+//! let ni = NamedInformation::from_data(Digest::SHA256, b"Hello World!")
+//!     .expect("SHA256 is in the registry");
+//!
+//! assert_eq!(
+//!     ni.to_string(),
+//!     "ni:///sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk"
+//! );
+//!
+//! let parsed: NamedInformation = ni.to_string().parse()?;
+//! assert!(parsed.matches(b"Hello World!"));
+//! # Ok::<(), clap_digest::ni::ParseError>(())
+//! ```
+//!
+//! [rfc]: https://www.rfc-editor.org/rfc/rfc6920
+//! [registry]: https://www.iana.org/assignments/named-information/named-information.xhtml
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{encode, Digest, Output};
+
+impl Digest {
+    /// Returns the name from the IANA "Named Information Hash Algorithm
+    /// Registry", if this digest algorithm is registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA256.ni_name(), Some("sha-256"));
+    /// ```
+    #[must_use]
+    pub const fn ni_name(&self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => Some("sha-256"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => Some("sha-384"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => Some("sha-512"),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_224 => Some("sha3-224"),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_256 => Some("sha3-256"),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_384 => Some("sha3-384"),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_512 => Some("sha3-512"),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the digest algorithm for a name from the IANA "Named
+    /// Information Hash Algorithm Registry".
+    ///
+    /// Names are matched case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_ni_name("sha-256"), Some(Digest::SHA256));
+    /// ```
+    #[must_use]
+    pub fn from_ni_name(name: &str) -> Option<Self> {
+        use clap::ValueEnum;
+
+        Self::value_variants()
+            .iter()
+            .find(|digest| {
+                digest
+                    .ni_name()
+                    .is_some_and(|ni_name| ni_name.eq_ignore_ascii_case(name))
+            })
+            .copied()
+    }
+}
+
+/// Named information, i.e. a digest algorithm with its output and an
+/// optional authority.
+///
+/// [`fmt::Display`] formats it as `ni://<authority>/<name>;<base64url>`,
+/// [`FromStr`] parses it back, ignoring query parameters.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NamedInformation {
+    authority: Option<String>,
+    digest: Digest,
+    output: Output,
+}
+
+impl NamedInformation {
+    /// Returns named information, if the digest algorithm is registered and
+    /// the output has the digest output size.
+    #[must_use]
+    pub fn new(digest: Digest, output: Output) -> Option<Self> {
+        (digest.ni_name().is_some() && output.len() == digest.output_size())
+            .then_some(Self {
+                authority: None,
+                digest,
+                output,
+            })
+    }
+
+    /// Returns named information of the data, if the digest algorithm is
+    /// registered.
+    #[must_use]
+    pub fn from_data(digest: Digest, data: &[u8]) -> Option<Self> {
+        Self::new(digest, digest.hash(data))
+    }
+
+    /// Returns the named information with an authority.
+    #[must_use]
+    pub fn with_authority(mut self, authority: impl Into<String>) -> Self {
+        self.authority = Some(authority.into());
+        self
+    }
+
+    /// Returns the authority.
+    #[must_use]
+    pub fn authority(&self) -> Option<&str> {
+        self.authority.as_deref()
+    }
+
+    /// Returns the digest algorithm.
+    #[must_use]
+    pub const fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns the digest output.
+    #[must_use]
+    pub const fn output(&self) -> &Output {
+        &self.output
+    }
+
+    /// Returns `true` if the data matches this named information.
+    #[must_use]
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.digest.hash(data) == self.output
+    }
+}
+
+impl fmt::Display for NamedInformation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // UNWRAP: only constructed with registered digests
+        let name = self.digest.ni_name().unwrap();
+        let authority = self.authority.as_deref().unwrap_or_default();
+        let value = encode::base64url_nopad(&self.output);
+
+        write!(f, "ni://{authority}/{name};{value}")
+    }
+}
+
+impl FromStr for NamedInformation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s
+            .get(..5)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("ni://"))
+            .map(|_| &s[5..])
+            .ok_or(ParseError::InvalidScheme)?;
+
+        let s = s.split_once('?').map_or(s, |(s, _query)| s);
+
+        let (authority, path) =
+            s.split_once('/').ok_or(ParseError::MissingSeparator)?;
+
+        let (name, value) =
+            path.split_once(';').ok_or(ParseError::MissingSeparator)?;
+
+        let digest = Digest::from_ni_name(name)
+            .ok_or_else(|| ParseError::UnsupportedAlgorithm(name.into()))?;
+
+        let output = encode::decode_base64url(value)
+            .map_err(ParseError::InvalidBase64)?
+            .into();
+
+        let ni = Self::new(digest, output).ok_or(ParseError::InvalidLength)?;
+
+        Ok(if authority.is_empty() {
+            ni
+        } else {
+            ni.with_authority(authority)
+        })
+    }
+}
+
+/// Error parsing a named information URI.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The URI does not start with `ni://`.
+    InvalidScheme,
+
+    /// The `/` after the authority or the `;` after the algorithm name is
+    /// missing.
+    MissingSeparator,
+
+    /// The algorithm name is not supported.
+    UnsupportedAlgorithm(String),
+
+    /// The value is not valid URL-safe base64.
+    InvalidBase64(encode::DecodeError),
+
+    /// The value does not have the digest output size.
+    InvalidLength,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidScheme => write!(f, "scheme is not ni://"),
+            Self::MissingSeparator => {
+                write!(f, "missing '/' or ';' separator")
+            }
+            Self::UnsupportedAlgorithm(name) => {
+                write!(f, "unsupported algorithm {name:?}")
+            }
+            Self::InvalidBase64(error) => {
+                write!(f, "invalid base64url: {error}")
+            }
+            Self::InvalidLength => write!(f, "invalid digest output length"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidBase64(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{NamedInformation, ParseError};
+    #[cfg(feature = "sha2")]
+    use crate::Digest;
+
+    // example from RFC 6920, section 8.1, with erratum 4763 applied
+    #[cfg(feature = "sha2")]
+    const EXAMPLE: &str =
+        "ni://example.com/sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk";
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn rfc_example() {
+        let ni = EXAMPLE.parse::<NamedInformation>().unwrap();
+
+        assert_eq!(ni.authority(), Some("example.com"));
+        assert_eq!(ni.digest(), Digest::SHA256);
+        assert!(ni.matches(b"Hello World!"));
+        assert_eq!(ni.to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "http://example.com/".parse::<NamedInformation>(),
+            Err(ParseError::InvalidScheme)
+        );
+        assert_eq!(
+            "ni:///md5;rL0Y20zC-Fzt72VPzMSk2A".parse::<NamedInformation>(),
+            Err(ParseError::UnsupportedAlgorithm("md5".into()))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Contains the mapping of digest algorithms to ASN.1 object identifiers.
+
+use crate::Digest;
+
+impl Digest {
+    /// Returns the ASN.1 object identifier in dotted decimal notation, if
+    /// one is registered for this digest algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA256.oid(), Some("2.16.840.1.101.3.4.2.1"));
+    /// ```
+    #[must_use]
+    pub const fn oid(&self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2b512 => Some("1.3.6.1.4.1.1722.12.2.1.16"),
+
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2s256 => Some("1.3.6.1.4.1.1722.12.2.2.8"),
+
+            #[cfg(feature = "gost94")]
+            Self::GOST94CryptoPro => Some("1.2.643.2.2.9"),
+
+            #[cfg(feature = "md2")]
+            Self::MD2 => Some("1.2.840.113549.2.2"),
+
+            #[cfg(feature = "md4")]
+            Self::MD4 => Some("1.2.840.113549.2.4"),
+
+            #[cfg(feature = "md5")]
+            Self::MD5 => Some("1.2.840.113549.2.5"),
+
+            #[cfg(feature = "ripemd")]
+            Self::RIPEMD160 => Some("1.3.36.3.2.1"),
+
+            #[cfg(feature = "ripemd")]
+            Self::RIPEMD256 => Some("1.3.36.3.2.3"),
+
+            #[cfg(feature = "sha1")]
+            Self::SHA1 => Some("1.3.14.3.2.26"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA224 => Some("2.16.840.1.101.3.4.2.4"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => Some("2.16.840.1.101.3.4.2.1"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => Some("2.16.840.1.101.3.4.2.2"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => Some("2.16.840.1.101.3.4.2.3"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512_224 => Some("2.16.840.1.101.3.4.2.5"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512_256 => Some("2.16.840.1.101.3.4.2.6"),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_224 => Some("2.16.840.1.101.3.4.2.7"),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_256 => Some("2.16.840.1.101.3.4.2.8"),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_384 => Some("2.16.840.1.101.3.4.2.9"),
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_512 => Some("2.16.840.1.101.3.4.2.10"),
+
+            #[cfg(feature = "sm3")]
+            Self::SM3 => Some("1.2.156.10197.1.401"),
+
+            #[cfg(feature = "streebog")]
+            Self::Streebog256 => Some("1.2.643.7.1.1.2.2"),
+
+            #[cfg(feature = "streebog")]
+            Self::Streebog512 => Some("1.2.643.7.1.1.2.3"),
+
+            #[cfg(feature = "tiger")]
+            Self::Tiger => Some("1.3.6.1.4.1.11591.12.2"),
+
+            #[cfg(feature = "whirlpool")]
+            Self::Whirlpool => Some("1.0.10118.3.0.55"),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the digest algorithm for an ASN.1 object identifier in dotted
+    /// decimal notation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_oid("1.3.14.3.2.26"), Some(Digest::SHA1));
+    /// ```
+    #[must_use]
+    pub fn from_oid(oid: &str) -> Option<Self> {
+        use clap::ValueEnum;
+
+        Self::value_variants()
+            .iter()
+            .find(|digest| digest.oid() == Some(oid))
+            .copied()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use clap::ValueEnum;
+
+    use crate::Digest;
+
+    #[test]
+    fn unique() {
+        let oids = Digest::value_variants()
+            .iter()
+            .filter_map(Digest::oid)
+            .collect::<Vec<_>>();
+
+        assert_eq!(oids.len(), oids.iter().collect::<HashSet<_>>().len());
+    }
+
+    #[test]
+    fn roundtrip() {
+        for digest in Digest::value_variants() {
+            if let Some(oid) = digest.oid() {
+                assert_eq!(Digest::from_oid(oid), Some(*digest));
+            }
+        }
+    }
+}
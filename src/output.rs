@@ -0,0 +1,176 @@
+//! Contains the finalized digest [`Output`].
+
+use std::fmt;
+
+use subtle::ConstantTimeEq;
+
+use crate::encode::{self, Encoding};
+
+/// Finalized digest output with formatting conveniences.
+///
+/// [`fmt::Display`] formats the output as lowercase hex, like the `*sum`
+/// coreutils.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::Digest;
+///
+/// let output = Digest::MD5.hash(b"foo");
+///
+/// assert_eq!(output.to_string(), "acbd18db4cc2f85cedef654fccc4a4d8");
+/// assert_eq!(format!("{output:X}"), "ACBD18DB4CC2F85CEDEF654FCCC4A4D8");
+/// assert_eq!(output.to_base64(), "rL0Y20zC+Fzt72VPzMSk2A==");
+/// assert_eq!(output.len(), 16);
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Output(Box<[u8]>);
+
+impl Output {
+    /// Returns the output bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the output bytes, consuming the output.
+    #[must_use]
+    pub fn into_bytes(self) -> Box<[u8]> {
+        self.0
+    }
+
+    /// Returns the output length in bytes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the output is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the output equals the bytes, comparing in constant
+    /// time.
+    ///
+    /// Use this instead of `==` when the bytes are influenced by an
+    /// attacker. Only the lengths are compared in variable time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// let output = Digest::MD5.hash(b"foo");
+    /// let expected = Digest::MD5.hash(b"foo");
+    ///
+    /// assert!(output.ct_eq(expected.as_bytes()));
+    /// assert!(!output.ct_eq(&expected.as_bytes()[1..]));
+    /// ```
+    #[must_use]
+    pub fn ct_eq(&self, other: &[u8]) -> bool {
+        self.0.len() == other.len() && self.0.as_ref().ct_eq(other).into()
+    }
+
+    /// Returns the output in the encoding.
+    ///
+    /// See [`Encoding::encode`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::{encode::Encoding, Digest};
+    ///
+    /// let output = Digest::MD5.hash(b"foo");
+    ///
+    /// assert_eq!(output.encode(Encoding::Base32), b"VS6RRW2MYL4FZ3PPMVH4ZRFE3A======");
+    /// ```
+    #[must_use]
+    pub fn encode(&self, encoding: Encoding) -> Vec<u8> {
+        encoding.encode(&self.0)
+    }
+
+    /// Returns the output as padded standard base64.
+    #[must_use]
+    pub fn to_base64(&self) -> String {
+        encode::base64(&self.0)
+    }
+}
+
+impl From<Box<[u8]>> for Output {
+    fn from(bytes: Box<[u8]>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Vec<u8>> for Output {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes.into_boxed_slice())
+    }
+}
+
+impl From<Output> for Box<[u8]> {
+    fn from(output: Output) -> Self {
+        output.0
+    }
+}
+
+impl AsRef<[u8]> for Output {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Output({self:x})")
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl fmt::UpperHex for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::Output;
+
+    #[test]
+    fn hex() {
+        let output = Output::from(vec![0x00, 0xab, 0xff]);
+
+        assert_eq!(output.to_string(), "00abff");
+        assert_eq!(format!("{output:x}"), "00abff");
+        assert_eq!(format!("{output:X}"), "00ABFF");
+        assert_eq!(format!("{output:?}"), "Output(00abff)");
+    }
+
+    #[test]
+    fn ct_eq() {
+        let output = Output::from(vec![0x00, 0xab, 0xff]);
+
+        assert!(output.ct_eq(&[0x00, 0xab, 0xff]));
+        assert!(!output.ct_eq(&[0x00, 0xab, 0xfe]));
+        assert!(!output.ct_eq(&[0x00, 0xab]));
+        assert!(!output.ct_eq(&[]));
+    }
+}
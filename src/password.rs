@@ -0,0 +1,587 @@
+//! Contains password hashing ([`PasswordHash`]).
+//!
+//! Unlike digests, password hashes are salted and deliberately slow, so that
+//! leaked hashes are expensive to brute-force. Hashes are strings in the
+//! usual format of their algorithm, i.e. the [PHC string format] or bcrypt's
+//! modular crypt format, embedding the salt and cost parameters, so
+//! [`verify_password`] needs nothing but the password and the hash.
+//!
+//! Use [`arg::password_hash`](crate::arg::password_hash) and
+//! [`arg::password_params`](crate::arg::password_params) to build
+//! password-hashing CLIs the same way as plain-digest ones.
+//!
+//! [PHC string format]: https://github.com/P-H-C/phc-string-format
+//!
+//! # Examples
+//!
+//! ```
+//! use clap::ValueEnum;
+//! use clap_digest::password::{self, Params, PasswordHash};
+//!
+//! let algorithm = PasswordHash::value_variants()[0];
+//!
+//! // cheap parameters to keep the example fast, prefer the defaults
+//! let params = Params::new().iterations(1).memory(64).cost(4);
+//! let hash = algorithm.hash_password(b"hunter2", &params).unwrap();
+//!
+//! assert_eq!(PasswordHash::from_hash(&hash), Some(algorithm));
+//! assert!(password::verify_password(b"hunter2", &hash).unwrap());
+//! assert!(!password::verify_password(b"hunter3", &hash).unwrap());
+//! ```
+
+use std::fmt;
+
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+#[cfg(any(feature = "argon2", feature = "pbkdf2", feature = "scrypt"))]
+use password_hash::{Ident, PasswordHasher, PasswordVerifier, SaltString};
+
+/// Length of the random salt in bytes.
+pub const SALT_LEN: usize = 16;
+
+/// Supported password hashing algorithms.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum PasswordHash {
+    /// Argon2id, the hybrid Argon2 recommended by RFC 9106.
+    #[cfg(feature = "argon2")]
+    Argon2id,
+
+    /// Argon2i, the side-channel resistant Argon2.
+    #[cfg(feature = "argon2")]
+    Argon2i,
+
+    /// bcrypt, using at most the first 72 bytes of the password.
+    #[cfg(feature = "bcrypt")]
+    Bcrypt,
+
+    /// PBKDF2 with HMAC-SHA256.
+    #[cfg(feature = "pbkdf2")]
+    Pbkdf2Sha256,
+
+    /// PBKDF2 with HMAC-SHA512.
+    #[cfg(feature = "pbkdf2")]
+    Pbkdf2Sha512,
+
+    /// scrypt.
+    #[cfg(feature = "scrypt")]
+    Scrypt,
+}
+
+impl PasswordHash {
+    /// Returns the name of the algorithm.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "argon2")]
+            Self::Argon2id => "Argon2id",
+
+            #[cfg(feature = "argon2")]
+            Self::Argon2i => "Argon2i",
+
+            #[cfg(feature = "bcrypt")]
+            Self::Bcrypt => "bcrypt",
+
+            #[cfg(feature = "pbkdf2")]
+            Self::Pbkdf2Sha256 => "PBKDF2-SHA256",
+
+            #[cfg(feature = "pbkdf2")]
+            Self::Pbkdf2Sha512 => "PBKDF2-SHA512",
+
+            #[cfg(feature = "scrypt")]
+            Self::Scrypt => "scrypt",
+        }
+    }
+
+    /// Returns the algorithm of the hash, if it is a supported one.
+    ///
+    /// This only looks at the identifier at the start of the hash, the rest
+    /// may still be malformed.
+    #[must_use]
+    pub fn from_hash(hash: &str) -> Option<Self> {
+        let id = hash.strip_prefix('$')?.split('$').next()?;
+
+        match id {
+            #[cfg(feature = "argon2")]
+            "argon2id" => Some(Self::Argon2id),
+
+            #[cfg(feature = "argon2")]
+            "argon2i" => Some(Self::Argon2i),
+
+            #[cfg(feature = "bcrypt")]
+            "2a" | "2b" | "2x" | "2y" => Some(Self::Bcrypt),
+
+            #[cfg(feature = "pbkdf2")]
+            "pbkdf2-sha256" => Some(Self::Pbkdf2Sha256),
+
+            #[cfg(feature = "pbkdf2")]
+            "pbkdf2-sha512" => Some(Self::Pbkdf2Sha512),
+
+            #[cfg(feature = "scrypt")]
+            "scrypt" => Some(Self::Scrypt),
+
+            _ => None,
+        }
+    }
+
+    /// Returns the hash of the password with a random salt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameters are out of range for the
+    /// algorithm, or if the system's random number generator fails.
+    pub fn hash_password(
+        self,
+        password: &[u8],
+        params: &Params,
+    ) -> Result<String, Error> {
+        let mut salt = [0; SALT_LEN];
+        getrandom::getrandom(&mut salt).map_err(|_| Error::Random)?;
+
+        self.hash_password_with_salt(password, &salt, params)
+    }
+
+    /// Returns the hash of the password with the salt.
+    ///
+    /// Only use this for reproducible hashes, a salt must never be reused
+    /// across passwords. Prefer [`PasswordHash::hash_password`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameters are out of range for the
+    /// algorithm.
+    pub fn hash_password_with_salt(
+        self,
+        password: &[u8],
+        salt: &[u8; SALT_LEN],
+        params: &Params,
+    ) -> Result<String, Error> {
+        match self {
+            #[cfg(feature = "argon2")]
+            Self::Argon2id | Self::Argon2i => {
+                let algorithm = if self == Self::Argon2id {
+                    argon2::Algorithm::Argon2id
+                } else {
+                    argon2::Algorithm::Argon2i
+                };
+                let argon2_params = argon2::Params::new(
+                    params.memory.unwrap_or(argon2::Params::DEFAULT_M_COST),
+                    params
+                        .iterations
+                        .unwrap_or(argon2::Params::DEFAULT_T_COST),
+                    params
+                        .parallelism
+                        .unwrap_or(argon2::Params::DEFAULT_P_COST),
+                    None,
+                )
+                .map_err(|_| Error::Params(self))?;
+                let hasher = argon2::Argon2::new(
+                    algorithm,
+                    argon2::Version::V0x13,
+                    argon2_params,
+                );
+
+                hash_phc(&hasher, None, argon2_params, password, salt)
+                    .map_err(|_| Error::Params(self))
+            }
+
+            #[cfg(feature = "bcrypt")]
+            Self::Bcrypt => {
+                let cost = params.cost.unwrap_or(bcrypt::DEFAULT_COST);
+
+                bcrypt::hash_with_salt(password, cost, *salt)
+                    .map(|parts| {
+                        parts.format_for_version(bcrypt::Version::TwoB)
+                    })
+                    .map_err(|_| Error::Params(self))
+            }
+
+            #[cfg(feature = "pbkdf2")]
+            Self::Pbkdf2Sha256 | Self::Pbkdf2Sha512 => {
+                let algorithm = if self == Self::Pbkdf2Sha256 {
+                    pbkdf2::Algorithm::Pbkdf2Sha256
+                } else {
+                    pbkdf2::Algorithm::Pbkdf2Sha512
+                };
+                let defaults = pbkdf2::Params::default();
+                let pbkdf2_params = pbkdf2::Params {
+                    rounds: params.iterations.unwrap_or(defaults.rounds),
+                    output_length: defaults.output_length,
+                };
+
+                if pbkdf2_params.rounds == 0 {
+                    return Err(Error::Params(self));
+                }
+
+                hash_phc(
+                    &pbkdf2::Pbkdf2,
+                    Some(algorithm.ident()),
+                    pbkdf2_params,
+                    password,
+                    salt,
+                )
+                .map_err(|_| Error::Params(self))
+            }
+
+            #[cfg(feature = "scrypt")]
+            Self::Scrypt => {
+                let log_n = params
+                    .cost
+                    .map_or(
+                        Ok(scrypt::Params::RECOMMENDED_LOG_N),
+                        u8::try_from,
+                    )
+                    .map_err(|_| Error::Params(self))?;
+                let scrypt_params = scrypt::Params::new(
+                    log_n,
+                    scrypt::Params::RECOMMENDED_R,
+                    params
+                        .parallelism
+                        .unwrap_or(scrypt::Params::RECOMMENDED_P),
+                    scrypt::Params::RECOMMENDED_LEN,
+                )
+                .map_err(|_| Error::Params(self))?;
+
+                hash_phc(&scrypt::Scrypt, None, scrypt_params, password, salt)
+                    .map_err(|_| Error::Params(self))
+            }
+        }
+    }
+}
+
+impl fmt::Display for PasswordHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl ValueEnum for PasswordHash {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            #[cfg(feature = "argon2")]
+            Self::Argon2id,
+            #[cfg(feature = "argon2")]
+            Self::Argon2i,
+            #[cfg(feature = "bcrypt")]
+            Self::Bcrypt,
+            #[cfg(feature = "pbkdf2")]
+            Self::Pbkdf2Sha256,
+            #[cfg(feature = "pbkdf2")]
+            Self::Pbkdf2Sha512,
+            #[cfg(feature = "scrypt")]
+            Self::Scrypt,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+/// Cost parameters for [`PasswordHash::hash_password`].
+///
+/// Each algorithm uses the parameters it knows and ignores the others.
+/// Unset parameters default to the recommendations of the underlying
+/// implementation.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::password::Params;
+///
+/// let params = Params::new().memory(64 * 1024).iterations(3);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Params {
+    iterations: Option<u32>,
+    memory: Option<u32>,
+    parallelism: Option<u32>,
+    cost: Option<u32>,
+}
+
+impl Params {
+    /// Returns the default parameters.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of iterations: the Argon2 passes or the PBKDF2
+    /// rounds.
+    #[must_use]
+    pub const fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    /// Sets the memory in KiB used by Argon2.
+    #[must_use]
+    pub const fn memory(mut self, memory: u32) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Sets the degree of parallelism of Argon2 and scrypt.
+    #[must_use]
+    pub const fn parallelism(mut self, parallelism: u32) -> Self {
+        self.parallelism = Some(parallelism);
+        self
+    }
+
+    /// Sets the base-2 logarithm of the work factor: the bcrypt cost or the
+    /// scrypt `ln`.
+    #[must_use]
+    pub const fn cost(mut self, cost: u32) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+}
+
+/// Error hashing or verifying a password.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The parameters are out of range for the algorithm.
+    Params(PasswordHash),
+
+    /// The hash is malformed or of an unsupported algorithm.
+    Hash,
+
+    /// The system's random number generator failed.
+    Random,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Params(algorithm) => {
+                write!(f, "invalid {algorithm} parameters")
+            }
+            Self::Hash => {
+                f.write_str("malformed or unsupported password hash")
+            }
+            Self::Random => f.write_str("failed to generate a random salt"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Returns `true` if the hash is the hash of the password.
+///
+/// The algorithm, salt and parameters are taken from the hash.
+///
+/// # Errors
+///
+/// Returns an error if the hash is malformed or of an algorithm not
+/// compiled in, see [`PasswordHash::from_hash`].
+pub fn verify_password(password: &[u8], hash: &str) -> Result<bool, Error> {
+    match PasswordHash::from_hash(hash).ok_or(Error::Hash)? {
+        #[cfg(feature = "argon2")]
+        PasswordHash::Argon2id | PasswordHash::Argon2i => {
+            verify_phc(&argon2::Argon2::default(), password, hash)
+        }
+
+        #[cfg(feature = "bcrypt")]
+        PasswordHash::Bcrypt => {
+            bcrypt::verify(password, hash).map_err(|_| Error::Hash)
+        }
+
+        #[cfg(feature = "pbkdf2")]
+        PasswordHash::Pbkdf2Sha256 | PasswordHash::Pbkdf2Sha512 => {
+            verify_phc(&pbkdf2::Pbkdf2, password, hash)
+        }
+
+        #[cfg(feature = "scrypt")]
+        PasswordHash::Scrypt => verify_phc(&scrypt::Scrypt, password, hash),
+    }
+}
+
+/// Returns the PHC string of the password hash.
+#[cfg(any(feature = "argon2", feature = "pbkdf2", feature = "scrypt"))]
+fn hash_phc<H: PasswordHasher>(
+    hasher: &H,
+    algorithm: Option<Ident<'_>>,
+    params: H::Params,
+    password: &[u8],
+    salt: &[u8],
+) -> password_hash::Result<String> {
+    let salt = SaltString::encode_b64(salt)?;
+
+    hasher
+        .hash_password_customized(password, algorithm, None, params, &salt)
+        .map(|hash| hash.to_string())
+}
+
+/// Verifies the password against the PHC string.
+#[cfg(any(feature = "argon2", feature = "pbkdf2", feature = "scrypt"))]
+fn verify_phc(
+    verifier: &dyn PasswordVerifier,
+    password: &[u8],
+    hash: &str,
+) -> Result<bool, Error> {
+    let hash =
+        password_hash::PasswordHash::new(hash).map_err(|_| Error::Hash)?;
+
+    if hash.salt.is_none() || hash.hash.is_none() {
+        return Err(Error::Hash);
+    }
+
+    match verifier.verify_password(password, &hash) {
+        Ok(()) => Ok(true),
+        Err(password_hash::Error::Password) => Ok(false),
+        Err(_) => Err(Error::Hash),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::{Error, Params, PasswordHash};
+
+    const SALT: [u8; 16] =
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    #[cfg(any(feature = "argon2", feature = "pbkdf2", feature = "scrypt"))]
+    fn check(algorithm: PasswordHash, params: &Params, expected: &str) {
+        let hash = algorithm
+            .hash_password_with_salt(b"password", &SALT, params)
+            .unwrap();
+
+        assert_eq!(hash, expected);
+        assert_eq!(PasswordHash::from_hash(&hash), Some(algorithm));
+        assert_eq!(super::verify_password(b"password", &hash), Ok(true));
+        assert_eq!(super::verify_password(b"Password", &hash), Ok(false));
+    }
+
+    #[cfg(feature = "argon2")]
+    #[test]
+    fn argon2() {
+        let params = Params::new().memory(256).iterations(3).parallelism(2);
+
+        check(
+            PasswordHash::Argon2id,
+            &params,
+            "$argon2id$v=19$m=256,t=3,p=2$AAECAwQFBgcICQoLDA0ODw\
+             $USWE1oQR/FCC5GXRdWUQzPKzcmFc73VMU0W0arafFo8",
+        );
+        check(
+            PasswordHash::Argon2i,
+            &params,
+            "$argon2i$v=19$m=256,t=3,p=2$AAECAwQFBgcICQoLDA0ODw\
+             $+p8zQ08nlL8c9s/gBlHZdmHt4tWaKWopdeDEatohz0E",
+        );
+    }
+
+    #[cfg(feature = "bcrypt")]
+    #[test]
+    fn bcrypt() {
+        // Openwall crypt_blowfish test vector
+        let salt = [0x10, 0x41, 0x04].repeat(6);
+        let hash = PasswordHash::Bcrypt
+            .hash_password_with_salt(
+                b"U*U",
+                salt[..16].try_into().unwrap(),
+                &Params::new().cost(5),
+            )
+            .unwrap();
+
+        assert_eq!(
+            hash,
+            "$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"
+        );
+        assert_eq!(
+            super::verify_password(
+                b"U*U",
+                "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW"
+            ),
+            Ok(true)
+        );
+        assert_eq!(super::verify_password(b"U*V", &hash), Ok(false));
+    }
+
+    #[cfg(feature = "pbkdf2")]
+    #[test]
+    fn pbkdf2() {
+        let params = Params::new().iterations(1000);
+
+        check(
+            PasswordHash::Pbkdf2Sha256,
+            &params,
+            "$pbkdf2-sha256$i=1000,l=32$AAECAwQFBgcICQoLDA0ODw\
+             $JeuGrMduQwGPGLmo+Qwv7UYtHHmeg9SK49fGkEamC2c",
+        );
+        check(
+            PasswordHash::Pbkdf2Sha512,
+            &params,
+            "$pbkdf2-sha512$i=1000,l=32$AAECAwQFBgcICQoLDA0ODw\
+             $x05AgND7tB/uWGjA/2D9dayuJjghWYfl/1T46uIRM5s",
+        );
+    }
+
+    #[cfg(feature = "scrypt")]
+    #[test]
+    fn scrypt() {
+        check(
+            PasswordHash::Scrypt,
+            &Params::new().cost(10).parallelism(2),
+            "$scrypt$ln=10,r=8,p=2$AAECAwQFBgcICQoLDA0ODw\
+             $2fs6JV7IbiGgyjyPIxLxLLy5vXNrhQ+FJbFF9Dg9WqE",
+        );
+    }
+
+    #[test]
+    fn random_salt() {
+        let params = Params::new().iterations(1).memory(64).cost(4);
+
+        for algorithm in PasswordHash::value_variants() {
+            let first = algorithm.hash_password(b"", &params).unwrap();
+            let second = algorithm.hash_password(b"", &params).unwrap();
+
+            assert_ne!(first, second);
+            assert_eq!(super::verify_password(b"", &first), Ok(true));
+            assert_eq!(super::verify_password(b"", &second), Ok(true));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let params = Params::new()
+            .iterations(0)
+            .memory(0)
+            .parallelism(0)
+            .cost(100);
+
+        for algorithm in PasswordHash::value_variants() {
+            let error = algorithm
+                .hash_password_with_salt(b"", &SALT, &params)
+                .unwrap_err();
+
+            assert_eq!(error, Error::Params(*algorithm));
+            assert_eq!(
+                error.to_string(),
+                format!("invalid {algorithm} parameters")
+            );
+        }
+
+        for hash in ["", "$", "$md5$x", "plain", "$scrypt$ln=x"] {
+            assert_eq!(super::verify_password(b"", hash), Err(Error::Hash));
+        }
+    }
+
+    #[test]
+    fn value_enum() {
+        for algorithm in PasswordHash::value_variants() {
+            assert_eq!(
+                PasswordHash::from_str(algorithm.name(), false),
+                Ok(*algorithm)
+            );
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Contains piece-wise hashing, like for torrent files.
+//!
+//! The data is split into pieces of a fixed size, the last one possibly
+//! shorter. Every piece is hashed separately, and the whole data too, in one
+//! pass.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::pieces;
+//! use clap_digest::Digest;
+//!
+//! let pieces = pieces::hash_pieces(&b"foobar"[..], 4, Digest::SHA1)?;
+//!
+//! assert_eq!(pieces.pieces(), [Digest::SHA1.hash(b"foob"), Digest::SHA1.hash(b"ar")]);
+//! assert_eq!(pieces.output(), &Digest::SHA1.hash(b"foobar"));
+//! assert_eq!(pieces.len(), 6);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use digest::{Reset, Update};
+
+use crate::hash::BUFFER_SIZE;
+use crate::{Digest, Hasher, Output};
+
+/// Digests of the pieces and of the whole data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pieces {
+    piece_size: usize,
+    outputs: Vec<Output>,
+    output: Output,
+    len: u64,
+}
+
+impl Pieces {
+    /// Returns the piece size.
+    #[must_use]
+    pub const fn piece_size(&self) -> usize {
+        self.piece_size
+    }
+
+    /// Returns the digests of the pieces.
+    #[must_use]
+    pub fn pieces(&self) -> &[Output] {
+        &self.outputs
+    }
+
+    /// Returns the digest of the whole data.
+    #[must_use]
+    pub const fn output(&self) -> &Output {
+        &self.output
+    }
+
+    /// Returns the length of the whole data in bytes.
+    #[must_use]
+    pub const fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the data is empty, i.e. there are no pieces.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the concatenated piece digests, like the `pieces` field of
+    /// torrent files.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.outputs
+            .iter()
+            .flat_map(Output::as_bytes)
+            .copied()
+            .collect()
+    }
+}
+
+/// Hashes the data read in pieces of this size, at least 1.
+///
+/// Empty data has no pieces.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub fn hash_pieces(
+    mut reader: impl Read,
+    piece_size: usize,
+    digest: Digest,
+) -> io::Result<Pieces> {
+    let piece_size = piece_size.max(1);
+    let mut buffer = vec![0; BUFFER_SIZE];
+
+    let mut full = Hasher::new(digest);
+    let mut piece = Hasher::new(digest);
+    let mut piece_len = 0;
+    let mut pieces = Vec::new();
+    let mut len = 0;
+
+    loop {
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        full.update(&buffer[..n]);
+        len += n as u64;
+
+        let mut data = &buffer[..n];
+
+        while !data.is_empty() {
+            let take = data.len().min(piece_size - piece_len);
+            piece.update(&data[..take]);
+            piece_len += take;
+            data = &data[take..];
+
+            if piece_len == piece_size {
+                pieces.push(piece.checkpoint());
+                piece.reset();
+                piece_len = 0;
+            }
+        }
+    }
+
+    if piece_len > 0 {
+        pieces.push(piece.finalize_output());
+    }
+
+    Ok(Pieces {
+        piece_size,
+        outputs: pieces,
+        output: full.finalize_output(),
+        len,
+    })
+}
+
+/// Hashes the file in pieces, see [`hash_pieces`].
+///
+/// # Errors
+///
+/// Returns an error if opening or reading the file fails.
+pub fn hash_pieces_file(
+    path: impl AsRef<Path>,
+    piece_size: usize,
+    digest: Digest,
+) -> io::Result<Pieces> {
+    hash_pieces(File::open(path)?, piece_size, digest)
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, any(feature = "md5", feature = "sha1")))]
+mod tests {
+    use crate::Digest;
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn hash_pieces() {
+        let data = (0..=255).cycle().take(200_000).collect::<Vec<u8>>();
+
+        for piece_size in [0, 1, 1000, 65_536, 100_000, 300_000] {
+            let pieces =
+                super::hash_pieces(&data[..], piece_size, Digest::MD5)
+                    .unwrap();
+
+            let expected = data
+                .chunks(piece_size.max(1))
+                .map(|piece| Digest::MD5.hash(piece))
+                .collect::<Vec<_>>();
+
+            assert_eq!(pieces.piece_size(), piece_size.max(1));
+            assert_eq!(pieces.pieces(), expected);
+            assert_eq!(pieces.output(), &Digest::MD5.hash(&data));
+            assert_eq!(pieces.len(), 200_000);
+            assert_eq!(pieces.to_bytes().len(), expected.len() * 16);
+        }
+
+        let empty = super::hash_pieces(&b""[..], 4, Digest::MD5).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.pieces().is_empty());
+        assert_eq!(empty.output(), &Digest::MD5.hash(b""));
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn hash_pieces_file() {
+        let pieces =
+            super::hash_pieces_file("Cargo.toml", 100, Digest::SHA1).unwrap();
+        let data = std::fs::read("Cargo.toml").unwrap();
+
+        assert_eq!(pieces.pieces()[0], Digest::SHA1.hash(&data[..100]));
+        assert_eq!(pieces.output(), &Digest::SHA1.hash(&data));
+    }
+}
@@ -0,0 +1,167 @@
+//! Contains recommendations of digest algorithms among the enabled features.
+
+use clap::ValueEnum;
+
+use crate::Digest;
+
+/// Digest algorithms ranked by typical throughput on 64 bit machines, the
+/// fastest first. Insecure legacy algorithms are left out.
+const FASTEST: &[Digest] = &[
+    #[cfg(feature = "blake3")]
+    Digest::BLAKE3,
+    #[cfg(feature = "blake2")]
+    Digest::BLAKE2bp,
+    #[cfg(feature = "blake2")]
+    Digest::BLAKE2b512,
+    #[cfg(feature = "sha2")]
+    Digest::SHA512_256,
+    #[cfg(feature = "sha2")]
+    Digest::SHA256,
+    #[cfg(feature = "blake2")]
+    Digest::BLAKE2s256,
+    #[cfg(feature = "sha3")]
+    Digest::SHA3_256,
+    #[cfg(feature = "sm3")]
+    Digest::SM3,
+];
+
+/// Digest algorithms ranked by security margin, the strongest first.
+const STRONGEST: &[Digest] = &[
+    #[cfg(feature = "sha3")]
+    Digest::SHA3_512,
+    #[cfg(feature = "sha2")]
+    Digest::SHA512,
+    #[cfg(feature = "blake2")]
+    Digest::BLAKE2b512,
+    #[cfg(feature = "streebog")]
+    Digest::Streebog512,
+    #[cfg(feature = "sha3")]
+    Digest::SHA3_384,
+    #[cfg(feature = "sha2")]
+    Digest::SHA384,
+    #[cfg(feature = "blake3")]
+    Digest::BLAKE3,
+    #[cfg(feature = "sha3")]
+    Digest::SHA3_256,
+    #[cfg(feature = "sha2")]
+    Digest::SHA256,
+    #[cfg(feature = "sha2")]
+    Digest::SHA512_256,
+    #[cfg(feature = "blake2")]
+    Digest::BLAKE2s256,
+];
+
+/// Digest algorithms used by default, the preferred first. These are widely
+/// supported by other tools.
+const DEFAULTS: &[Digest] = &[
+    #[cfg(feature = "sha2")]
+    Digest::SHA256,
+    #[cfg(feature = "blake3")]
+    Digest::BLAKE3,
+    #[cfg(feature = "blake2")]
+    Digest::BLAKE2b512,
+    #[cfg(feature = "sha3")]
+    Digest::SHA3_256,
+];
+
+impl Digest {
+    /// Returns the fastest enabled digest algorithm that is not insecure,
+    /// according to a static ranking.
+    ///
+    /// This is meant for tools whose users just want a checksum. If none of
+    /// the ranked algorithms is enabled, any enabled algorithm is returned.
+    /// Use [`bench::compare_all`](crate::bench::compare_all) to measure on
+    /// the current machine instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::fastest_available(), Some(Digest::BLAKE3));
+    /// ```
+    #[must_use]
+    pub fn fastest_available() -> Option<Self> {
+        FASTEST
+            .first()
+            .or_else(|| Self::value_variants().first())
+            .copied()
+    }
+
+    /// Returns the enabled digest algorithm with the largest security
+    /// margin, according to a static ranking.
+    ///
+    /// If none of the ranked algorithms is enabled, the enabled algorithm
+    /// with the largest output is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::strongest_available(), Some(Digest::SHA3_512));
+    /// ```
+    #[must_use]
+    pub fn strongest_available() -> Option<Self> {
+        STRONGEST.first().copied().or_else(|| {
+            Self::value_variants()
+                .iter()
+                .copied()
+                .max_by_key(Self::output_size)
+        })
+    }
+}
+
+impl Default for Digest {
+    /// Returns SHA256, or another widely supported digest algorithm if it is
+    /// not enabled.
+    fn default() -> Self {
+        DEFAULTS
+            .first()
+            .or_else(|| Self::value_variants().first())
+            .copied()
+            // UNWRAP: any digest algorithm is enabled
+            .unwrap()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::FASTEST;
+    #[cfg(feature = "sha2")]
+    use super::STRONGEST;
+    use crate::Digest;
+
+    #[test]
+    fn fastest_available() {
+        let fastest = Digest::fastest_available().unwrap();
+
+        assert!(Digest::value_variants().contains(&fastest));
+        assert!(FASTEST
+            .iter()
+            .all(|digest| !digest.description().contains("insecure")));
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn strongest_available() {
+        let strongest = Digest::strongest_available().unwrap();
+
+        assert_eq!(strongest.output_size(), 64);
+        assert!(STRONGEST
+            .iter()
+            .all(|digest| !digest.description().contains("insecure")));
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn default() {
+        assert_eq!(Digest::default(), Digest::SHA256);
+    }
+}
@@ -0,0 +1,420 @@
+//! Contains the runtime registry of custom digest algorithms.
+//!
+//! Downstream crates can [`register`] digest algorithms that are not built
+//! into this crate. Together with the built-in ones, they are the
+//! [`DigestChoice`]s accepted by [`arg::digest_choice`](crate::arg::digest_choice)
+//! and listed by [`command::digests`](crate::command::digests).
+//!
+//! # Examples
+//!
+//! ```
+//! use clap::Command;
+//! use clap_digest::registry::{self, Metadata};
+//! use clap_digest::{DigestChoice, DynDigest};
+//!
+//! fn new_my_hash() -> Box<dyn DynDigest + Send + Sync> {
+//!     // any digest implementation
+//!     # clap_digest::Digest::MD5.new_dyn()
+//! }
+//!
+//! registry::register(
+//!     "MyHash",
+//!     Metadata::new("in-house digest algorithm"),
+//!     new_my_hash,
+//! )
+//! .unwrap();
+//!
+//! let cli = Command::new("myapp").arg(clap_digest::arg::digest_choice());
+//! let args = cli.get_matches_from(["myapp", "--digest", "my-hash"]);
+//!
+//! let digest = *args.get_one::<DigestChoice>("digest").unwrap();
+//!
+//! assert_eq!(digest.name(), "MyHash");
+//! assert_eq!(digest.description(), "in-house digest algorithm");
+//! ```
+
+use std::fmt;
+use std::sync::{PoisonError, RwLock};
+
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+
+use crate::{Digest, DynDigest, Output};
+
+/// Returns a new instance of a custom digest algorithm.
+pub type Factory = fn() -> Box<dyn DynDigest + Send + Sync>;
+
+/// Metadata of a custom digest algorithm.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Metadata {
+    description: &'static str,
+}
+
+impl Metadata {
+    /// Returns new metadata with the description, see
+    /// [`Digest::description`].
+    #[must_use]
+    pub const fn new(description: &'static str) -> Self {
+        Self { description }
+    }
+
+    /// Returns the description.
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+/// A registered custom digest algorithm.
+struct Entry {
+    name: &'static str,
+    metadata: Metadata,
+    factory: Factory,
+}
+
+/// The registered custom digest algorithms.
+struct Registry {
+    entries: Vec<Entry>,
+
+    /// All choices, built-in and custom, built on first use after a
+    /// registration. The slice is leaked, as [`ValueEnum::value_variants`]
+    /// borrows it, so registering at startup leaks it only once.
+    choices: Option<&'static [DigestChoice]>,
+}
+
+impl Registry {
+    /// Returns the registered custom digest algorithm with the name, matched
+    /// like [`Digest::from_name`].
+    fn find(&self, name: &str) -> Option<Custom> {
+        self.entries
+            .iter()
+            .position(|entry| Digest::name_eq(entry.name, name))
+            .map(|index| Custom { index })
+    }
+}
+
+static REGISTRY: RwLock<Registry> = RwLock::new(Registry {
+    entries: Vec::new(),
+    choices: None,
+});
+
+/// Registers a custom digest algorithm with the name.
+///
+/// Registration is meant to happen once at startup, before parsing the
+/// command line.
+///
+/// # Errors
+///
+/// Returns an error if the name is already taken by a built-in or registered
+/// digest algorithm, allowing the spellings of [`Digest::from_name`].
+pub fn register(
+    name: &'static str,
+    metadata: Metadata,
+    factory: Factory,
+) -> Result<DigestChoice, Error> {
+    let mut registry =
+        REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+
+    if Digest::from_name(name).is_some() || registry.find(name).is_some() {
+        return Err(Error::Duplicate(name));
+    }
+
+    registry.entries.push(Entry {
+        name,
+        metadata,
+        factory,
+    });
+
+    registry.choices = None;
+
+    Ok(DigestChoice::Custom(Custom {
+        index: registry.entries.len() - 1,
+    }))
+}
+
+/// Error registering a custom digest algorithm.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The name is already taken.
+    Duplicate(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate(name) => {
+                write!(f, "digest algorithm {name} already exists")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A registered custom digest algorithm, see [`register`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Custom {
+    index: usize,
+}
+
+impl Custom {
+    /// Calls the function with the registry entry.
+    fn with<T>(self, f: impl FnOnce(&Entry) -> T) -> T {
+        let registry = REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+
+        // INDEX: entries are never removed
+        f(&registry.entries[self.index])
+    }
+
+    /// Returns the name.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        self.with(|entry| entry.name)
+    }
+
+    /// Returns the description.
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        self.with(|entry| entry.metadata.description())
+    }
+
+    /// Returns a new instance.
+    #[must_use]
+    pub fn new_dyn(self) -> Box<dyn DynDigest + Send + Sync> {
+        (self.with(|entry| entry.factory))()
+    }
+}
+
+impl fmt::Debug for Custom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Custom").field(&self.name()).finish()
+    }
+}
+
+/// A built-in or registered custom digest algorithm.
+///
+/// This is the [`Digest`] counterpart aware of the [`register`]ed digest
+/// algorithms.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum DigestChoice {
+    /// A built-in digest algorithm.
+    Builtin(Digest),
+
+    /// A registered custom digest algorithm.
+    Custom(Custom),
+}
+
+impl DigestChoice {
+    /// Returns the built-in or registered digest algorithm with the name,
+    /// allowing the spellings of [`Digest::from_name`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::{Digest, DigestChoice};
+    ///
+    /// assert_eq!(
+    ///     DigestChoice::from_name("sha-256"),
+    ///     Some(DigestChoice::Builtin(Digest::SHA256))
+    /// );
+    /// assert_eq!(DigestChoice::from_name("sha-257"), None);
+    /// ```
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Digest::from_name(name).map(Self::Builtin).or_else(|| {
+            REGISTRY
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .find(name)
+                .map(Self::Custom)
+        })
+    }
+
+    /// Returns the name, see [`Digest::name`].
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Builtin(digest) => digest.name(),
+            Self::Custom(custom) => custom.name(),
+        }
+    }
+
+    /// Returns the description, see [`Digest::description`].
+    #[must_use]
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Builtin(digest) => digest.description(),
+            Self::Custom(custom) => custom.description(),
+        }
+    }
+
+    /// Returns the built-in digest algorithm, if it is one.
+    #[must_use]
+    pub const fn builtin(&self) -> Option<Digest> {
+        match self {
+            Self::Builtin(digest) => Some(*digest),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Returns a new [`DynDigest`] instance.
+    #[must_use]
+    pub fn new_dyn(&self) -> Box<dyn DynDigest + Send + Sync> {
+        match self {
+            Self::Builtin(digest) => digest.new_dyn(),
+            Self::Custom(custom) => custom.new_dyn(),
+        }
+    }
+
+    /// Returns the digest of the data.
+    #[must_use]
+    pub fn hash(&self, data: &[u8]) -> Output {
+        let mut hasher = self.new_dyn();
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+
+    /// Returns the digest output size in bytes.
+    #[must_use]
+    pub fn output_size(&self) -> usize {
+        match self {
+            Self::Builtin(digest) => digest.output_size(),
+            Self::Custom(custom) => custom.new_dyn().output_size(),
+        }
+    }
+}
+
+impl fmt::Display for DigestChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl ValueEnum for DigestChoice {
+    fn value_variants<'a>() -> &'a [Self] {
+        if let Some(choices) = REGISTRY
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .choices
+        {
+            return choices;
+        }
+
+        let mut registry =
+            REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+        let len = registry.entries.len();
+
+        registry.choices.get_or_insert_with(|| {
+            Digest::value_variants()
+                .iter()
+                .copied()
+                .map(Self::Builtin)
+                .chain((0..len).map(|index| Self::Custom(Custom { index })))
+                .collect::<Vec<_>>()
+                .leak()
+        })
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+impl From<Digest> for DigestChoice {
+    fn from(digest: Digest) -> Self {
+        Self::Builtin(digest)
+    }
+}
+
+impl From<DigestChoice> for Box<dyn DynDigest> {
+    fn from(digest: DigestChoice) -> Self {
+        digest.new_dyn()
+    }
+}
+
+impl From<DigestChoice> for Box<dyn DynDigest + Send + Sync> {
+    fn from(digest: DigestChoice) -> Self {
+        digest.new_dyn()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "md5", feature = "sha2"))]
+mod tests {
+    use clap::{Command, ValueEnum};
+
+    use super::{DigestChoice, Error, Metadata};
+    use crate::{Digest, DynDigest};
+
+    fn new_test_hash() -> Box<dyn DynDigest + Send + Sync> {
+        Digest::MD5.new_dyn()
+    }
+
+    #[test]
+    fn register() {
+        let custom = super::register(
+            "TestRegistryHash",
+            Metadata::new("test digest"),
+            new_test_hash,
+        )
+        .unwrap();
+
+        assert_eq!(custom.name(), "TestRegistryHash");
+        assert_eq!(custom.description(), "test digest");
+        assert_eq!(custom.builtin(), None);
+        assert_eq!(custom.hash(b"foo"), Digest::MD5.hash(b"foo"));
+        assert_eq!(
+            DigestChoice::from_str("TestRegistryHash", false),
+            Ok(custom)
+        );
+        assert!(DigestChoice::value_variants()
+            .contains(&DigestChoice::Builtin(Digest::SHA256)));
+
+        assert_eq!(
+            super::register(
+                "testregistryhash",
+                Metadata::new(""),
+                new_test_hash
+            ),
+            Err(Error::Duplicate("testregistryhash"))
+        );
+        assert_eq!(
+            super::register(
+                "test-registry-hash",
+                Metadata::new(""),
+                new_test_hash
+            ),
+            Err(Error::Duplicate("test-registry-hash"))
+        );
+        assert_eq!(
+            super::register("SHA-256", Metadata::new(""), new_test_hash)
+                .unwrap_err()
+                .to_string(),
+            "digest algorithm SHA-256 already exists"
+        );
+
+        assert_eq!(
+            DigestChoice::from_name("test_registry_hash"),
+            Some(custom)
+        );
+        assert!(DigestChoice::value_variants().contains(&custom));
+
+        let cli = Command::new("myapp").arg(crate::arg::digest_choice());
+        let args = cli.get_matches_from(["myapp", "-d", "test-registry-hash"]);
+        assert_eq!(args.get_one::<DigestChoice>("digest"), Some(&custom));
+    }
+
+    #[test]
+    fn builtin() {
+        let choice = DigestChoice::from(Digest::SHA256);
+
+        assert_eq!(choice.name(), "SHA256");
+        assert_eq!(choice.builtin(), Some(Digest::SHA256));
+        assert_eq!(choice.output_size(), 32);
+        assert_eq!(choice.hash(b"foo"), Digest::SHA256.hash(b"foo"));
+    }
+}
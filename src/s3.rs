@@ -0,0 +1,186 @@
+//! Contains composite checksums of multipart uploads, like Amazon S3.
+//!
+//! The data is split into parts of a fixed size. The composite checksum is
+//! the digest of the concatenated binary part digests, suffixed with the
+//! number of parts, e.g. `<hex>-3`. With MD5, this is the `ETag` of a
+//! multipart upload, with e.g. SHA256 in base64, this is its
+//! `x-amz-checksum-sha256`.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::s3;
+//! use clap_digest::Digest;
+//!
+//! let data = vec![0; 20 * 1024 * 1024];
+//! let composite = s3::composite(&data[..], s3::DEFAULT_PART_SIZE, Digest::MD5)?;
+//!
+//! assert_eq!(composite.part_count(), 3);
+//! assert!(composite.etag().ends_with("-3"));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use digest::Update;
+
+use crate::{Digest, Hasher, Output};
+
+/// Default part size of the AWS CLI, i.e. 8 MiB.
+pub const DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Composite checksum of a multipart upload.
+///
+/// [`fmt::Display`] formats the checksum as [`Composite::etag`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Composite {
+    output: Output,
+    parts: Vec<Output>,
+}
+
+impl Composite {
+    /// Returns the digest of the concatenated part digests.
+    #[must_use]
+    pub const fn output(&self) -> &Output {
+        &self.output
+    }
+
+    /// Returns the digests of the parts.
+    #[must_use]
+    pub fn parts(&self) -> &[Output] {
+        &self.parts
+    }
+
+    /// Returns the number of parts.
+    #[must_use]
+    pub fn part_count(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Returns the checksum in hex, suffixed with the number of parts, like
+    /// the `ETag` of a multipart upload with MD5.
+    #[must_use]
+    pub fn etag(&self) -> String {
+        format!("{}-{}", self.output, self.parts.len())
+    }
+
+    /// Returns the checksum in base64, suffixed with the number of parts,
+    /// like the `x-amz-checksum-*` headers of a multipart upload.
+    #[must_use]
+    pub fn checksum(&self) -> String {
+        format!("{}-{}", self.output.to_base64(), self.parts.len())
+    }
+}
+
+impl fmt::Display for Composite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.etag())
+    }
+}
+
+/// Returns the composite checksum of the data read, split into parts of
+/// this size, at least 1.
+///
+/// Empty data is one empty part.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub fn composite(
+    mut reader: impl Read,
+    part_size: u64,
+    digest: Digest,
+) -> io::Result<Composite> {
+    let part_size = part_size.max(1);
+    let mut parts = Vec::new();
+
+    loop {
+        let part = (&mut reader).take(part_size);
+        let (len, output) = crate::io::hash_copy(part, io::sink(), digest)?;
+
+        if len == 0 && !parts.is_empty() {
+            break;
+        }
+
+        parts.push(output);
+
+        if len < part_size {
+            break;
+        }
+    }
+
+    let mut hasher = Hasher::new(digest);
+    for part in &parts {
+        hasher.update(part.as_bytes());
+    }
+
+    Ok(Composite {
+        output: hasher.finalize_output(),
+        parts,
+    })
+}
+
+/// Returns the composite checksum of the file, see [`composite`].
+///
+/// # Errors
+///
+/// Returns an error if opening or reading the file fails.
+pub fn composite_file(
+    path: impl AsRef<Path>,
+    part_size: u64,
+    digest: Digest,
+) -> io::Result<Composite> {
+    composite(File::open(path)?, part_size, digest)
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, any(feature = "md5", feature = "sha1", feature = "sha2")))]
+mod tests {
+    use crate::Digest;
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn composite() {
+        let composite =
+            super::composite(&b"foobar"[..], 3, Digest::MD5).unwrap();
+
+        let foo = Digest::MD5.hash(b"foo");
+        let bar = Digest::MD5.hash(b"bar");
+        let output =
+            Digest::MD5.hash(&[foo.as_bytes(), bar.as_bytes()].concat());
+
+        assert_eq!(composite.parts(), [foo, bar]);
+        assert_eq!(composite.output(), &output);
+        assert_eq!(composite.etag(), format!("{output}-2"));
+        assert_eq!(composite.to_string(), composite.etag());
+        assert_eq!(composite.checksum(), format!("{}-2", output.to_base64()));
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn composite_parts() {
+        for (data, part_count) in
+            [(&b""[..], 1), (b"f", 1), (b"foo", 1), (b"foob", 2)]
+        {
+            let composite = super::composite(data, 3, Digest::SHA256).unwrap();
+            assert_eq!(composite.part_count(), part_count);
+        }
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn composite_file() {
+        let composite =
+            super::composite_file("Cargo.toml", 1024, Digest::SHA1).unwrap();
+        let data = std::fs::read("Cargo.toml").unwrap();
+
+        assert_eq!(composite.parts()[0], Digest::SHA1.hash(&data[..1024]));
+        assert_eq!(composite.part_count(), (data.len() + 1023) / 1024);
+    }
+}
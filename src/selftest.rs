@@ -0,0 +1,732 @@
+//! Contains known-answer self-tests of the digest algorithms.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::Digest;
+//!
+//! Digest::SHA256.self_test()?;
+//! Digest::self_test_all()?;
+//! # Ok::<(), clap_digest::selftest::Error>(())
+//! ```
+
+use std::fmt;
+
+use clap::ValueEnum;
+
+use digest::Update;
+
+use crate::{Digest, Hasher, Output};
+
+/// Inputs of the known-answer tests, i.e. the empty string, `abc` and the
+/// 448 bit message of the NIST examples.
+const INPUTS: [&[u8]; 3] = [
+    b"",
+    b"abc",
+    b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+];
+
+impl Digest {
+    /// Checks the digest algorithm against embedded known answers.
+    ///
+    /// Every input is hashed both at once and byte by byte. This is meant as
+    /// a runtime health check of the compiled implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for the first input with an unexpected output.
+    pub fn self_test(&self) -> Result<(), Error> {
+        for (input, expected) in INPUTS.iter().zip(self.known_answers()) {
+            let mut hasher = Hasher::new(*self);
+            for byte in input.chunks(1) {
+                hasher.update(byte);
+            }
+
+            for actual in [self.hash(input), hasher.finalize_output()] {
+                if actual.to_string() != expected {
+                    return Err(Error {
+                        digest: *self,
+                        input,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks every supported digest algorithm, see [`Digest::self_test`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failing digest algorithm.
+    pub fn self_test_all() -> Result<(), Error> {
+        Self::value_variants().iter().try_for_each(Self::self_test)
+    }
+
+    /// Returns the hex outputs of the known-answer test [`INPUTS`].
+    #[allow(clippy::too_many_lines)]
+    const fn known_answers(self) -> [&'static str; 3] {
+        match self {
+            #[cfg(feature = "adler")]
+            Self::Adler32 => [
+                "00000001",
+                "024d0127",
+                "807416f9",
+            ],
+
+            #[cfg(feature = "ascon")]
+            Self::AsconHash => [
+                "7346bc14f036e87ae03d0997913088f5f68411434b3cf8b54fa796a80d251f91",
+                "d37fe9f1d10dbcfad8408a6804dbe91124a8912693322bb23ec1701e19e3fd51",
+                "7165de397346e55f200fd10319357a66dd6e649bb42c6dcfef397885c5c4256c",
+            ],
+
+            #[cfg(feature = "belt")]
+            Self::BeltHash => [
+                "734708ff2bec067e829d5b74e735192eceec9dda522213a35a79f592bbadcc6f",
+                "6c31d021f90ad5bd5e63a70eaa7d410ea0026e5e71d9a84e4682373fdcdc1bf6",
+                "8e6394a968f082b7f193481093cadfce9aff9db35134f01cdd5b2f27ac148b62",
+            ],
+
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2b512 => [
+                "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce",
+                "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+                "7285ff3e8bd768d69be62b3bf18765a325917fa9744ac2f582a20850bc2b1141ed1b3e4528595acc90772bdf2d37dc8a47130b44f33a02e8730e5ad8e166e888",
+            ],
+
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2s256 => [
+                "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9",
+                "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+                "6f4df5116a6f332edab1d9e10ee87df6557beab6259d7663f3bcd5722c13f189",
+            ],
+
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2bp => [
+                "b5ef811a8038f70b628fa8b294daae7492b1ebe343a80eaabbf1f6ae664dd67b9d90b0120791eab81dc96985f28849f6a305186a85501b405114bfa678df9380",
+                "b91a6b66ae87526c400b0a8b53774dc65284ad8f6575f8148ff93dff943a6ecd8362130f22d6dae633aa0f91df4ac89aaff31d0f1b923c898e82025dedbdad6e",
+                "c5a0341eebb615503e229330e06a3dce8805b434ca758e899e72ac40bac36e637b70098a24ae5c3c4d39a183a43eb974823e3ddb5b09e07ad1e526e905f65bc4",
+            ],
+
+            #[cfg(feature = "blake2")]
+            Self::BLAKE2sp => [
+                "dd0e891776933f43c7d032b08a917e25741f8aa9a12c12e1cac8801500f2ca4f",
+                "70f75b58f1fecab821db43c88ad84edde5a52600616cd22517b7bb14d440a7d5",
+                "3d107e42f17c13c82b436ebb651a48def67e7772fa06f4738ee968c7f4d8b48b",
+            ],
+
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3 => [
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+                "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+                "c19012cc2aaf0dc3d8e5c45a1b79114d2df42abb2a410bf54be09e891af06ff8",
+            ],
+
+            #[cfg(feature = "cityhash")]
+            Self::CityHash64 => [
+                "9ae16a3b2f90404f",
+                "24a5b3a074e7f369",
+                "5a9d42ddc174cd1b",
+            ],
+
+            #[cfg(feature = "crc")]
+            Self::CRC32 => [
+                "00000000",
+                "352441c2",
+                "171a3f5f",
+            ],
+
+            #[cfg(feature = "crc")]
+            Self::CRC32C => [
+                "00000000",
+                "364b3fb7",
+                "071325f5",
+            ],
+
+            #[cfg(feature = "crc")]
+            Self::CRC64XZ => [
+                "0000000000000000",
+                "2cd8094a1a277627",
+                "9f446c74e3dc7c4b",
+            ],
+
+            #[cfg(feature = "crc")]
+            Self::CRC64ECMA => [
+                "0000000000000000",
+                "66501a349a0e0855",
+                "29d18301fe33ca5d",
+            ],
+
+            #[cfg(feature = "farmhash")]
+            Self::FarmHash64 => [
+                "9ae16a3b2f90404f",
+                "24a5b3a074e7f369",
+                "161e79597efa71a7",
+            ],
+
+            #[cfg(feature = "fnv")]
+            Self::FNV1a32 => [
+                "811c9dc5",
+                "1a47e90b",
+                "ccdc1355",
+            ],
+
+            #[cfg(feature = "fnv")]
+            Self::FNV1a64 => [
+                "cbf29ce484222325",
+                "e71fa2190541574b",
+                "dd305304cdb45735",
+            ],
+
+            #[cfg(feature = "fsb")]
+            Self::FSB160 => [
+                "bd874daa024be58a7bb2725914132819f9c64c2e",
+                "c93c6cbd9f9a7d35fcd02d0e9822bd8589854aef",
+                "7eb9718bcae0d6b1def2cbe6d7f0c60d64a97903",
+            ],
+
+            #[cfg(feature = "fsb")]
+            Self::FSB224 => [
+                "c66ded00ab44a5d29b16133a7078cdb431e8d089ec6fdef7d265b554",
+                "a5c46ac3abbd72b27d680915500762ce10b43db7d1cc996c5669a7e3",
+                "60983f59086ae0c0529dfdb1f3b1bd91596628ba4d2179653bae3480",
+            ],
+
+            #[cfg(feature = "fsb")]
+            Self::FSB256 => [
+                "344eaf42ab2a9716a07cfd61d6e717a341b701162cbabc71673f9599167bb0b0",
+                "e13c678b7d557ae9b26b605c8f4e38ad582581629eb42198cbeb7e40046c7d69",
+                "e22df825660a773a70229838d384327a7186de2cf8d41817c90b51bb29377a53",
+            ],
+
+            #[cfg(feature = "fsb")]
+            Self::FSB384 => [
+                "ed1f3e8d9c5f9af16d82a80043b227e3cd942453d5bd55222d62270c3aca8cc608ed16aba4202b59c194d5e783d80cca",
+                "163cd26db98ea5a346d079cc999e50840b24fb55b9228ee06269b32128605606f94d58e169f686e6b92c6819890f1708",
+                "36b008ef98ea6c52c8a6011ed8d5c1623447cbffac955690a7f38d91a26dba9c76f08153ca5437221285bbd1c5fb60d6",
+            ],
+
+            #[cfg(feature = "fsb")]
+            Self::FSB512 => [
+                "a60c6ba0e439d4a2137252e34623e1b1a0a35bff1d3893c11b0a31db8d9063990bee6c3084b24aea384c3ea08866d643fadeb679959778636a950b95da5c93f9",
+                "ee2e8824748fb1dd8f0a7424e2a7a25ee5a03d92ba974dfc611ea74065981e7ab5e64f6962fe7363d1e4f79c526585e095dbe0e02adacb9073678964db08105f",
+                "b2feb444b2ca0b1fb5a17aa619acfb6d1db1dd088607df61fce567ff229ebcc01872505476c14b232108f4966c53bc57b94e5ef84b6be1d968d20c5a04654077",
+            ],
+
+            #[cfg(feature = "gost94")]
+            Self::GOST94CryptoPro => [
+                "981e5f3ca30c841487830f84fb433e13ac1101569b9c13584ac483234cd656c0",
+                "b285056dbf18d7392d7677369524dd14747459ed8143997e163b2986f92fd42c",
+                "fa0f6806e76264fef2735dda4232c9c54dee4cec82278b50ff76560ad2c61a3e",
+            ],
+
+            #[cfg(feature = "gost94")]
+            Self::GOST94UA => [
+                "da37bdf41145e39e34111775b40646e8059c2e969c1460bb98abccb26f0f76a5",
+                "a34a53504d8ba070cb73a583146167a0a3c226d793440d9cea24465fe02251f2",
+                "fbbe3a1bd6ecccdeb05c7a57fbcec61066c0434d21094d5aec3291095917864b",
+            ],
+
+            #[cfg(feature = "gost94")]
+            Self::GOST94s2015 => [
+                "d47819718a633fa42ff02a4d1c7180da02178067aeb4b1490388c84f88538d80",
+                "69b5724535aecaa596c9383462a9aa41e010d8829222cd193ca5f8ec71ee0069",
+                "44e73f436033089c5a69132a0d60456fce138ec78fb3e94dafa60b4521a6a893",
+            ],
+
+            #[cfg(feature = "groestl")]
+            Self::Groestl224 => [
+                "f2e180fb5947be964cd584e22e496242c6a329c577fc4ce8c36d34c3",
+                "ed7bb299331c99ee485d49c22d368f05d9158f2055b9605676786f43",
+                "b7b310994ad64eb635141fce7a8494703da7db05099a89fdd004c940",
+            ],
+
+            #[cfg(feature = "groestl")]
+            Self::Groestl256 => [
+                "1a52d11d550039be16107f9c58db9ebcc417f16f736adb2502567119f0083467",
+                "f3c1bb19c048801326a7efbcf16e3d7887446249829c379e1840d1a3a1e7d4d2",
+                "22c23b160e561f80924d44f2cc5974cd5a1d36f69324211861e63b9b6cb7974c",
+            ],
+
+            #[cfg(feature = "groestl")]
+            Self::Groestl384 => [
+                "ac353c1095ace21439251007862d6c62f829ddbe6de4f78e68d310a9205a736d8b11d99bffe448f57a1cfa2934f044a5",
+                "32c39f82ab41ee4fdb1582f83dde41089d47b904988b1a9a647553cb1a502cf07df7eb1e11dc3d66bec096a39a790336",
+                "eb13c7770892daa071b49889ac22ebd709cc54efbb511c98741d0b1385f7abaa4878fcf85907f93f88bafe08886fd022",
+            ],
+
+            #[cfg(feature = "groestl")]
+            Self::Groestl512 => [
+                "6d3ad29d279110eef3adbd66de2a0345a77baede1557f5d099fce0c03d6dc2ba8e6d4a6633dfbd66053c20faa87d1a11f39a7fbe4a6c2f009801370308fc4ad8",
+                "70e1c68c60df3b655339d67dc291cc3f1dde4ef343f11b23fdd44957693815a75a8339c682fc28322513fd1f283c18e53cff2b264e06bf83a2f0ac8c1f6fbff6",
+                "6637458bb67f5aa5311112e6fa584a38b33a51204472fa4dc43795c865527b38a7c3941e23a3f27f88646e5efe7d05fb704ff7848bfe8ffe329b80a265dcdbc3",
+            ],
+
+            #[cfg(feature = "jh")]
+            Self::JH224 => [
+                "2c99df889b019309051c60fecc2bd285a774940e43175b76b2626630",
+                "a278a73d4fb03712e38c9ddef0247d6ffb5a55fc5107a673f57d0a0c",
+                "65ec1dc61137dad16c4b7256dc6301fb3f449f51cdf525c21dcf4e37",
+            ],
+
+            #[cfg(feature = "jh")]
+            Self::JH256 => [
+                "46e64619c18bb0a92a5e87185a47eef83ca747b8fcc8e1412921357e326df434",
+                "8485f362bae493a745b4bcb4e18db96ba40d6bf00752a1964aded5640328704b",
+                "146105745c7e2dfac6a48a9b1223b111fed3a53c3efbfe8c2b2e8fa9cafd94d4",
+            ],
+
+            #[cfg(feature = "jh")]
+            Self::JH384 => [
+                "2fe5f71b1b3290d3c017fb3c1a4d02a5cbeb03a0476481e25082434a881994b0ff99e078d2c16b105ad069b569315328",
+                "687717bd7a120870d5137964954d5025182992e429633f848b49130a43913478456586090d9979a182d11818031610b6",
+                "b7b666f8d3a9b011055c5444b10973f7e8e4b6bb92bb78d1f6f365bc99b8e9c6cbb25af2f95567677ba25862af6700e8",
+            ],
+
+            #[cfg(feature = "jh")]
+            Self::JH512 => [
+                "90ecf2f76f9d2c8017d979ad5ab96b87d58fc8fc4b83060f3f900774faa2c8fabe69c5f4ff1ec2b61d6b316941cedee117fb04b1f4c5bc1b919ae841c50eec4f",
+                "29d6b2da637870e6d334d857f54b346a1013bf851e3664d1dbed4ebb25e3e3a861e526e348b7bf6ad9359646966539a14009d370891035e9c28e5370d4cc1551",
+                "acc8570edacf2fd0951c1274a50b8b6ffc08d47cd6d56e1183bd4c4f005e66614ff8e4097658d82182d224f118255bf5ac0fc6c519daab81ca8ea8c29a2d83b8",
+            ],
+
+            #[cfg(feature = "kupyna")]
+            Self::Kupyna256 => [
+                "cd5101d1ccdf0d1d1f4ada56e888cd724ca1a0838a3521e7131d4fb78d0f5eb6",
+                "0bd1b36109f1318411a0517315aa46b8839df06622a278676f5487996c9cfc04",
+                "bb8c26fd0a6d4b094ed9833eae2c83e3f9479d1f9fd96281bc67ae0e7054c4e1",
+            ],
+
+            #[cfg(feature = "kupyna")]
+            Self::Kupyna512 => [
+                "656b2f4cd71462388b64a37043ea55dbe445d452aecd46c3298343314ef04019bcfa3f04265a9857f91be91fce197096187ceda78c9c1c021c294a0689198538",
+                "9e5be7daf7b68b49d2ecbd04c7a5b3af72945012b0820c3132846ddc90da511f80bb7b70abd0cb1ab8df785d600c187b9d0ac567e8b6f76fde8a0b417a2ebf88",
+                "b94f5620f6c1b5c5ff489501eccb651d9f6d06f8507e121d860619a53688c9916538971941428779951b2856cc57115deefc9119a7e3a2aab7a8f2e297120b0a",
+            ],
+
+            #[cfg(feature = "md2")]
+            Self::MD2 => [
+                "8350e5a3e24c153df2275c9f80692773",
+                "da853b0d3f88d99b30283a69e6ded6bb",
+                "0dff6b398ad5a62ac8d97566b80c3a7f",
+            ],
+
+            #[cfg(feature = "md4")]
+            Self::MD4 => [
+                "31d6cfe0d16ae931b73c59d7e0c089c0",
+                "a448017aaf21d8525fc10ae87aa6729d",
+                "4691a9ec81b1a6bd1ab8557240b245c5",
+            ],
+
+            #[cfg(feature = "md5")]
+            Self::MD5 => [
+                "d41d8cd98f00b204e9800998ecf8427e",
+                "900150983cd24fb0d6963f7d28e17f72",
+                "8215ef0796a20bcaaae116d3876c664a",
+            ],
+
+            #[cfg(feature = "metrohash")]
+            Self::MetroHash64 => [
+                "7d961e0708b05f70",
+                "bbfffae624554fed",
+                "1bf3f4763f6e0d96",
+            ],
+
+            #[cfg(feature = "metrohash")]
+            Self::MetroHash128 => [
+                "cbd1413dcaf30500b65fc68446b10646",
+                "a77baf9fdb9cab89a501f8c18563d81c",
+                "31f39851f834b376850e527916b2f1ba",
+            ],
+
+            #[cfg(feature = "murmur3")]
+            Self::Murmur3_32 => [
+                "00000000",
+                "b3dd93fa",
+                "ee925b90",
+            ],
+
+            #[cfg(feature = "murmur3")]
+            Self::Murmur3_128 => [
+                "00000000000000000000000000000000",
+                "b4963f3f3fad78673ba2744126ca2d52",
+                "b48876ce69b9b1b4056c8de4a2ade2fa",
+            ],
+
+            #[cfg(feature = "ripemd")]
+            Self::RIPEMD160 => [
+                "9c1185a5c5e9fc54612808977ee8f548b2258d31",
+                "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+                "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+            ],
+
+            #[cfg(feature = "ripemd")]
+            Self::RIPEMD256 => [
+                "02ba4c4e5f8ecd1877fc52d64d30e37a2d9774fb1e5d026380ae0168e3c5522d",
+                "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65",
+                "3843045583aac6c8c8d9128573e7a9809afb2a0f34ccc36ea9e72f16f6368e3f",
+            ],
+
+            #[cfg(feature = "ripemd")]
+            Self::RIPEMD320 => [
+                "22d65d5661536cdc75c1fdf5c6de7b41b9f27325ebc61e8557177d705a0ec880151c3a32a00899b8",
+                "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d",
+                "d034a7950cf722021ba4b84df769a5de2060e259df4c9bb4a4268c0e935bbc7470a969c9d072a1ac",
+            ],
+
+            #[cfg(feature = "seahash")]
+            Self::SeaHash => [
+                "c920ca43256fdcb9",
+                "80796d63c232ed86",
+                "0059e9838649a142",
+            ],
+
+            #[cfg(feature = "sha1")]
+            Self::SHA1 => [
+                "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+                "a9993e364706816aba3e25717850c26c9cd0d89d",
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            ],
+
+            #[cfg(feature = "sha2")]
+            Self::SHA224 => [
+                "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f",
+                "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+                "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525",
+            ],
+
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => [
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            ],
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => [
+                "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b",
+                "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7",
+                "3391fdddfc8dc7393707a65b1b4709397cf8b1d162af05abfe8f450de5f36bc6b0455a8520bc4e6f5fe95b1fe3c8452b",
+            ],
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => [
+                "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+                "204a8fc6dda82f0a0ced7beb8e08a41657c16ef468b228a8279be331a703c33596fd15c13b1b07f9aa1d3bea57789ca031ad85c7a71dd70354ec631238ca3445",
+            ],
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512_224 => [
+                "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4",
+                "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa",
+                "e5302d6d54bb242275d1e7622d68df6eb02dedd13f564c13dbda2174",
+            ],
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512_256 => [
+                "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a",
+                "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+                "bde8e1f9f19bb9fd3406c90ec6bc47bd36d8ada9f11880dbc8a22a7078b6a461",
+            ],
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_224 => [
+                "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7",
+                "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf",
+                "8a24108b154ada21c9fd5574494479ba5c7e7ab76ef264ead0fcce33",
+            ],
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_256 => [
+                "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+                "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+                "41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376",
+            ],
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_384 => [
+                "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2ac3713831264adb47fb6bd1e058d5f004",
+                "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25",
+                "991c665755eb3a4b6bbdfb75c78a492e8c56a22c5c4d7e429bfdbc32b9d4ad5aa04a1f076e62fea19eef51acd0657c22",
+            ],
+
+            #[cfg(feature = "sha3")]
+            Self::SHA3_512 => [
+                "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26",
+                "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+                "04a371e84ecfb5b8b77cb48610fca8182dd457ce6f326a0fd3d7ec2f1e91636dee691fbe0c985302ba1b0d8dc78c086346b533b49c030d99a27daf1139d6e75e",
+            ],
+
+            #[cfg(feature = "sha3")]
+            Self::Keccak224 => [
+                "f71837502ba8e10837bdd8d365adb85591895602fc552b48b7390abd",
+                "c30411768506ebe1c2871b1ee2e87d38df342317300a9b97a95ec6a8",
+                "e51faa2b4655150b931ee8d700dc202f763ca5f962c529eae55012b6",
+            ],
+
+            #[cfg(feature = "sha3")]
+            Self::Keccak256 => [
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+                "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+                "45d3b367a6904e6e8d502ee04999a7c27647f91fa845d456525fd352ae3d7371",
+            ],
+
+            #[cfg(feature = "sha3")]
+            Self::Keccak384 => [
+                "2c23146a63a29acf99e73b88f8c24eaa7dc60aa771780ccc006afbfa8fe2479b2dd2b21362337441ac12b515911957ff",
+                "f7df1165f033337be098e7d288ad6a2f74409d7a60b49c36642218de161b1f99f8c681e4afaf31a34db29fb763e3c28e",
+                "b41e8896428f1bcbb51e17abd6acc98052a3502e0d5bf7fa1af949b4d3c855e7c4dc2c390326b3f3e74c7b1e2b9a3657",
+            ],
+
+            #[cfg(feature = "sha3")]
+            Self::Keccak512 => [
+                "0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e",
+                "18587dc2ea106b9a1563e32b3312421ca164c7f1f07bc922a9c83d77cea3a1e5d0c69910739025372dc14ac9642629379540c17e2a65b19d77aa511a9d00bb96",
+                "6aa6d3669597df6d5a007b00d09c20795b5c4218234e1698a944757a488ecdc09965435d97ca32c3cfed7201ff30e070cd947f1fc12b9d9214c467d342bcba5d",
+            ],
+
+            #[cfg(feature = "shabal")]
+            Self::SHABAL192 => [
+                "e10dc32232f98b039dbbcfa41269b9cdf67a73c841214c81",
+                "fc0e7b3568c6daef93e7b9a44e83739a75ae2722c6713ce8",
+                "2cb1c10393f5d19f73ca5a4bf8ff7560a915801dd173852e",
+            ],
+
+            #[cfg(feature = "shabal")]
+            Self::SHABAL224 => [
+                "562b4fdbe1706247552927f814b66a3d74b465a090af23e277bf8029",
+                "f47578239607af492d5f7df9241818adf6fba4180ddcbef6e39ac1e9",
+                "44780e1f0aa440a1d27c60541de9e0a7b8827b742dfec5f83e01e69a",
+            ],
+
+            #[cfg(feature = "shabal")]
+            Self::SHABAL256 => [
+                "aec750d11feee9f16271922fbaf5a9be142f62019ef8d720f858940070889014",
+                "07225fab83ca48fb480d22219410d5ca008359efbfd315829029afe2cb3f0404",
+                "ea446f857487e6c18c6661742cc362f21f1d92d4f4e74a4c501ffaa98d5c0673",
+            ],
+
+            #[cfg(feature = "shabal")]
+            Self::SHABAL384 => [
+                "ff093d67d22b06a674b5f384719150d617e0ff9c8923569a2ab60cda886df63c91a25f33cd71cc22c9eebc5cd6aee52a",
+                "66613058865271722c0295774aa77258a5082bebbb5a02f9d6aee9ad303fc71cbf19e2f599ddfde88cf0bf30a028e530",
+                "19df382bad56aefed2b9b022a99a0185780c76e0b09c25796181b3899a8dc03015d2ea2160a46903614a950af25d0779",
+            ],
+
+            #[cfg(feature = "shabal")]
+            Self::SHABAL512 => [
+                "fc2d5dff5d70b7f6b1f8c2fcc8c1f9fe9934e54257eded0cf2b539a2ef0a19ccffa84f8d9fa135e4bd3c09f590f3a927ebd603ac29eb729e6f2a9af031ad8dc6",
+                "4a7f0f707c1b0c1d12ddcfa8aa0f9d2410dd9bab57c2d56705fc1acb02066f99678738cedb20a2aba94842a441e77bc02656fe5690f98b421d029bfc4df09f91",
+                "6c40a5eaaae40a50bff9e530a4254c3e2fd2975f9d19f4eafa84c87de1c728acd89ce23c3c4af5ba0d38032ad327629d204fd8090435f3e79032e676bf1b55ff",
+            ],
+
+            #[cfg(feature = "skein")]
+            Self::Skein256 => [
+                "c8877087da56e072870daa843f176e9453115929094c3a40c463a196c29bf7ba",
+                "258bdec343b9fde1639221a5ae0144a96e552e5288753c5fec76c05fc2fc1870",
+                "21cdd023e2b2e90a1f137d050c5a81bc84545048e1ed035d87e9e721f19ffea2",
+            ],
+
+            #[cfg(feature = "skein")]
+            Self::Skein512 => [
+                "bc5b4c50925519c290cc634277ae3d6257212395cba733bbad37a4af0fa06af41fca7903d06564fea7a2d3730dbdb80c1f85562dfcc070334ea4d1d9e72cba7a",
+                "8f5dd9ec798152668e35129496b029a960c9a9b88662f7f9482f110b31f9f93893ecfb25c009baad9e46737197d5630379816a886aa05526d3a70df272d96e75",
+                "ded9469f2dbb2bd32390d2a3396045bb33c706291954f66d4f296ade2c09a61eeb51a72d86e392c489b53a90536045222b40d9355d1aa187d59041b7b98e521a",
+            ],
+
+            #[cfg(feature = "skein")]
+            Self::Skein1024 => [
+                "0fff9563bb3279289227ac77d319b6fff8d7e9f09da1247b72a0a265cd6d2a62645ad547ed8193db48cff847c06494a03f55666d3b47eb4c20456c9373c86297d630d5578ebd34cb40991578f9f52b18003efa35d3da6553ff35db91b81ab890bec1b189b7f52cb2a783ebb7d823d725b0b4a71f6824e88f68f982eefc6d19c6",
+                "35a599a0f91abcdb4cb73c19b8cb8d947742d82c309137a7caed29e8e0a2ca7a9ff9a90c34c1908cc7e7fd99bb15032fb86e76df21b72628399b5f7c3cc209d7bb31c99cd4e19465622a049afbb87c03b5ce3888d17e6e667279ec0aa9b3e2712624c01b5f5bbe1a564220bdcf6990af0c2539019f313fdd7406cca3892a1f1f",
+                "07b03a80f11f09294da9346bfe5499a5253ccf69c9fb338f190dda93103b82ca89d34dfa3de778a8c1a92f0fe5ced5a856815e2ca5e86289a5529fcc886781068a3b257ec7cb6ec127d5244d897d64490e9e1dfcfa48463974c0555baf517280c34b761d160301e2e9b24c4b1ba850f1b3a2396c807bd5412fdd5002eec05cf2",
+            ],
+
+            #[cfg(feature = "sm3")]
+            Self::SM3 => [
+                "1ab21d8355cfa17f8e61194831e81a8f22bec8c728fefb747ed035eb5082aa2b",
+                "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0",
+                "639b6cc5e64d9e37a390b192df4fa1ea0720ab747ff692b9f38c4e66ad7b8c05",
+            ],
+
+            #[cfg(feature = "streebog")]
+            Self::Streebog256 => [
+                "3f539a213e97c802cc229d474c6aa32a825a360b2a933a949fd925208d9ce1bb",
+                "4e2919cf137ed41ec4fb6270c61826cc4fffb660341e0af3688cd0626d23b481",
+                "47440b6ca733f24c7b80dada8055796a2742cb729f92cb7fedf5188f5f3f1cfc",
+            ],
+
+            #[cfg(feature = "streebog")]
+            Self::Streebog512 => [
+                "8e945da209aa869f0455928529bcae4679e9873ab707b55315f56ceb98bef0a7362f715528356ee83cda5f2aac4c6ad2ba3a715c1bcd81cb8e9f90bf4c1c1a8a",
+                "28156e28317da7c98f4fe2bed6b542d0dab85bb224445fcedaf75d46e26d7eb8d5997f3e0915dd6b7f0aab08d9c8beb0d8c64bae2ab8b3c8c6bc53b3bf0db728",
+                "859190f728250159b34a08b1d3262279a19668c571fc7a7e724c0910318fd4a251974e67592dbc96919d282de2da875488d59dc37a2876296f633f451a488e24",
+            ],
+
+            #[cfg(feature = "sum")]
+            Self::Cksum => [
+                "ffffffff",
+                "48aa78a2",
+                "97d32c84",
+            ],
+
+            #[cfg(feature = "sum")]
+            Self::BSDSum => [
+                "0000",
+                "40ac",
+                "d612",
+            ],
+
+            #[cfg(feature = "sum")]
+            Self::SysVSum => [
+                "0000",
+                "0126",
+                "16f8",
+            ],
+
+            #[cfg(feature = "tiger")]
+            Self::Tiger => [
+                "3293ac630c13f0245f92bbb1766e16167a4e58492dde73f3",
+                "2aab1484e8c158f2bfb8c5ff41b57a525129131c957b5f93",
+                "0f7bf9a19b9c58f2b7610df7e84f0ac3a71c631e7b53f78e",
+            ],
+
+            #[cfg(feature = "tiger")]
+            Self::Tiger2 => [
+                "4441be75f6018773c206c22745374b924aa8313fef919f41",
+                "f68d7bc5af4b43a06e048d7829560d4a9415658bb0b1f3bf",
+                "a6737f3997e8fbb63d20d2df88f86376b5fe2d5ce36646a9",
+            ],
+
+            #[cfg(feature = "whirlpool")]
+            Self::Whirlpool => [
+                "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a73e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3",
+                "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5",
+                "526b2394d85683e24b29acd0fd37f7d5027f61366a1407262dc2a6a345d9e240c017c1833db1e6db6a46bd444b0c69520c856e7c6e9c366d150a7da3aeb160d1",
+            ],
+
+            #[cfg(feature = "wyhash")]
+            Self::WyHash => [
+                "93228a4de0eec5a2",
+                "989b4a209c1011c9",
+                "7f06f9cbbda69f9c",
+            ],
+
+            #[cfg(feature = "xxhash")]
+            Self::XXH32 => [
+                "02cc5d05",
+                "32d153ff",
+                "89ea60c3",
+            ],
+
+            #[cfg(feature = "xxhash")]
+            Self::XXH64 => [
+                "ef46db3751d8e999",
+                "44bc2cf5ad770999",
+                "f06103773e8585df",
+            ],
+
+            #[cfg(feature = "xxhash")]
+            Self::XXH3_64 => [
+                "2d06800538d394c2",
+                "78af5f94892f3950",
+                "5bbcbbabcdcc3d3f",
+            ],
+
+            #[cfg(feature = "xxhash")]
+            Self::XXH3_128 => [
+                "99aa06d3014798d86001c324468d497f",
+                "06b05ab6733a618578af5f94892f3950",
+                "3d62d22a5169b016c0d894fd4828a1a7",
+            ],
+        }
+    }
+}
+
+/// Error of a failed known-answer self-test.
+#[derive(Clone, Debug)]
+pub struct Error {
+    digest: Digest,
+    input: &'static [u8],
+    expected: &'static str,
+    actual: Output,
+}
+
+impl Error {
+    /// Returns the failing digest algorithm.
+    #[must_use]
+    pub const fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns the input of the failing test.
+    #[must_use]
+    pub const fn input(&self) -> &'static [u8] {
+        self.input
+    }
+
+    /// Returns the expected output as lowercase hex.
+    #[must_use]
+    pub const fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Returns the actual output.
+    #[must_use]
+    pub const fn actual(&self) -> &Output {
+        &self.actual
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} self-test failed for {:?}: expected {}, got {}",
+            self.digest,
+            String::from_utf8_lossy(self.input),
+            self.expected,
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "md5")]
+    use super::Error;
+    use crate::Digest;
+    #[cfg(feature = "md5")]
+    use crate::Output;
+
+    #[test]
+    fn self_test_all() {
+        Digest::self_test_all().unwrap();
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn error() {
+        let error = Error {
+            digest: Digest::MD5,
+            input: b"abc",
+            expected: "00",
+            actual: Output::from(vec![0xff]),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "MD5 self-test failed for \"abc\": expected 00, got ff"
+        );
+    }
+}
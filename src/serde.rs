@@ -0,0 +1,85 @@
+//! Contains the [`serde`] representation of [`Digest`] by name.
+
+use std::fmt;
+
+use ::serde::de::{self, Deserialize, Deserializer, Visitor};
+use ::serde::{Serialize, Serializer};
+
+use crate::Digest;
+
+impl Serialize for Digest {
+    /// Serializes the digest algorithm [`name`](Digest::name).
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Digest {
+    /// Deserializes a digest algorithm name, allowing the spellings of
+    /// [`Digest::from_name`].
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+/// Visits a digest algorithm name.
+struct NameVisitor;
+
+impl Visitor<'_> for NameVisitor {
+    type Value = Digest;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a digest algorithm name")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Digest::from_name(value)
+            .ok_or_else(|| E::unknown_variant(value, Digest::all_names()))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use ::serde::de::value::Error;
+    use ::serde::de::{Deserialize, IntoDeserializer};
+
+    use crate::Digest;
+
+    fn deserialize(name: &str) -> Result<Digest, Error> {
+        Digest::deserialize(name.into_deserializer())
+    }
+
+    #[cfg(all(feature = "crc", feature = "sha2", feature = "sha3"))]
+    #[test]
+    fn aliases() {
+        for name in ["SHA256", "sha256", "sha-256", "Sha_256"] {
+            assert_eq!(deserialize(name), Ok(Digest::SHA256));
+        }
+
+        assert_eq!(deserialize("SHA3_256"), Ok(Digest::SHA3_256));
+        assert_eq!(deserialize("CRC64ECMA"), Ok(Digest::CRC64ECMA));
+        assert_eq!(deserialize("crc-64/ecma-182"), Ok(Digest::CRC64ECMA));
+    }
+
+    #[test]
+    fn unknown() {
+        let error = deserialize("SHA-257").unwrap_err().to_string();
+
+        assert!(
+            error.starts_with("unknown variant `SHA-257`, expected one of")
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Contains [Subresource Integrity][sri] (SRI) support.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::{sri::Integrity, Digest};
+//!
+//! let integrity = Integrity::from_data(Digest::SHA256, b"alert('Hello, world.');")
+//!     .expect("SHA256 is supported by SRI");
+//!
+//! assert_eq!(
+//!     integrity.to_string(),
+//!     "sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng="
+//! );
+//!
+//! let parsed: Integrity = integrity.to_string().parse()?;
+//! assert!(parsed.matches(b"alert('Hello, world.');"));
+//! # Ok::<(), clap_digest::sri::ParseError>(())
+//! ```
+//!
+//! [sri]: https://www.w3.org/TR/SRI/
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{encode, Digest, Output};
+
+impl Digest {
+    /// Returns the SRI hash algorithm token, if this digest algorithm is
+    /// supported by SRI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA384.sri_token(), Some("sha384"));
+    /// assert_eq!(Digest::MD5.sri_token(), None);
+    /// ```
+    #[must_use]
+    pub const fn sri_token(&self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => Some("sha256"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => Some("sha384"),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => Some("sha512"),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the digest algorithm for an SRI hash algorithm token.
+    ///
+    /// Tokens are matched case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_sri_token("sha256"), Some(Digest::SHA256));
+    /// assert_eq!(Digest::from_sri_token("md5"), None);
+    /// ```
+    #[must_use]
+    pub fn from_sri_token(token: &str) -> Option<Self> {
+        match token.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "sha2")]
+            "sha256" => Some(Self::SHA256),
+
+            #[cfg(feature = "sha2")]
+            "sha384" => Some(Self::SHA384),
+
+            #[cfg(feature = "sha2")]
+            "sha512" => Some(Self::SHA512),
+
+            _ => None,
+        }
+    }
+}
+
+/// SRI integrity metadata, i.e. a digest algorithm with its output.
+///
+/// [`fmt::Display`] formats it as `<token>-<base64>`, [`FromStr`] parses it
+/// back, ignoring options following a `?`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Integrity {
+    digest: Digest,
+    output: Output,
+}
+
+impl Integrity {
+    /// Returns integrity metadata, if the digest algorithm is supported by
+    /// SRI and the output has the digest output size.
+    #[must_use]
+    pub fn new(digest: Digest, output: Output) -> Option<Self> {
+        (digest.sri_token().is_some() && output.len() == digest.output_size())
+            .then_some(Self { digest, output })
+    }
+
+    /// Returns integrity metadata of the data, if the digest algorithm is
+    /// supported by SRI.
+    #[must_use]
+    pub fn from_data(digest: Digest, data: &[u8]) -> Option<Self> {
+        Self::new(digest, digest.hash(data))
+    }
+
+    /// Returns the digest algorithm.
+    #[must_use]
+    pub const fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns the digest output.
+    #[must_use]
+    pub const fn output(&self) -> &Output {
+        &self.output
+    }
+
+    /// Returns `true` if the data matches this integrity metadata.
+    #[must_use]
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.digest.hash(data) == self.output
+    }
+}
+
+impl fmt::Display for Integrity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // UNWRAP: only constructed with supported digests
+        let token = self.digest.sri_token().unwrap();
+        write!(f, "{token}-{}", self.output.to_base64())
+    }
+}
+
+impl FromStr for Integrity {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.split_once('?').map_or(s, |(s, _options)| s);
+
+        let (token, base64) =
+            s.split_once('-').ok_or(ParseError::MissingSeparator)?;
+
+        let digest = Digest::from_sri_token(token)
+            .ok_or_else(|| ParseError::UnsupportedAlgorithm(token.into()))?;
+
+        let output = encode::decode_base64(base64)
+            .map_err(ParseError::InvalidBase64)?
+            .into();
+
+        Self::new(digest, output).ok_or(ParseError::InvalidLength)
+    }
+}
+
+/// Error parsing SRI integrity metadata.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The `-` between algorithm token and base64 output is missing.
+    MissingSeparator,
+
+    /// The algorithm token is not supported.
+    UnsupportedAlgorithm(String),
+
+    /// The output is not valid base64.
+    InvalidBase64(encode::DecodeError),
+
+    /// The output does not have the digest output size.
+    InvalidLength,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSeparator => write!(f, "missing '-' separator"),
+            Self::UnsupportedAlgorithm(token) => {
+                write!(f, "unsupported algorithm {token:?}")
+            }
+            Self::InvalidBase64(error) => write!(f, "invalid base64: {error}"),
+            Self::InvalidLength => write!(f, "invalid digest output length"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidBase64(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use super::{Integrity, ParseError};
+    use crate::Digest;
+
+    #[test]
+    fn roundtrip() {
+        for digest in [Digest::SHA256, Digest::SHA384, Digest::SHA512] {
+            let integrity = Integrity::from_data(digest, b"foo").unwrap();
+            let parsed = integrity.to_string().parse::<Integrity>().unwrap();
+
+            assert_eq!(parsed, integrity);
+            assert!(parsed.matches(b"foo"));
+            assert!(!parsed.matches(b"bar"));
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "sha256".parse::<Integrity>(),
+            Err(ParseError::MissingSeparator)
+        );
+        assert_eq!(
+            "md5-rL0Y20zC+Fzt72VPzMSk2A==".parse::<Integrity>(),
+            Err(ParseError::UnsupportedAlgorithm("md5".into()))
+        );
+        assert_eq!(
+            "sha256-rL0Y20zC+Fzt72VPzMSk2A==".parse::<Integrity>(),
+            Err(ParseError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn parse_options() {
+        let integrity =
+            "sha256-LCa0a2j/xo/5m0U8HTBBNBNCLXBkg7+g+YpeiGJm564=?foo"
+                .parse::<Integrity>()
+                .unwrap();
+
+        assert!(integrity.matches(b"foo"));
+    }
+}
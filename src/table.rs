@@ -0,0 +1,857 @@
+//! Contains the table of all supported digest algorithms.
+//!
+//! Everything that exists once per digest algorithm is generated from this
+//! table: the [`Digest`](crate::Digest) and [`Hasher`](crate::Hasher) enums,
+//! their metadata accessors and conversions, [`with_digest!`] and the HMAC
+//! variants of [`Mac`](crate::mac::Mac). Adding a digest algorithm needs:
+//!
+//! - a new entry here,
+//! - its known answers in [`crate::selftest`],
+//! - for a new family, its Cargo feature, also listed in the `all` feature,
+//! - for a hasher in `adapter`, its feature in the `cfg` of `mod adapter`.
+//!
+//! Optional metadata like OIDs or multihash codes is matched by hand where it
+//! is used, with a fallback for the other digest algorithms.
+
+/// Invokes the macro with the table of all digest algorithms.
+///
+/// The tokens in brackets are passed through to the macro in front of the
+/// table, e.g. the expression to dispatch. Each entry is the variant with
+/// the feature enabling it, its name, description, whether it is
+/// cryptographic, its hasher type, output size in bytes and checksum file
+/// extension. The output size is checked against the hasher type at compile
+/// time.
+macro_rules! for_each_digest {
+    ($callback:ident $([$($args:tt)*])?) => {
+        $callback! {
+            [$($($args)*)?]
+
+            #[cfg(feature = "adler")]
+            Adler32 {
+                name: "Adler-32",
+                description: "Adler-32 as used by zlib, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Adler32,
+                output_size: 4,
+                extension: "adler-32",
+            }
+
+            #[cfg(feature = "ascon")]
+            AsconHash {
+                name: "Ascon-Hash",
+                description: "Ascon-Hash with 256 bit output",
+                cryptographic: true,
+                hasher: ascon_hash::AsconHash,
+                output_size: 32,
+                extension: "ascon-hash",
+            }
+
+            #[cfg(feature = "belt")]
+            BeltHash {
+                name: "BeltHash",
+                description: "Belarusian BeltHash (STB 34.101.31)",
+                cryptographic: true,
+                hasher: belt_hash::BeltHash,
+                output_size: 32,
+                extension: "belt-hash",
+            }
+
+            #[cfg(feature = "blake2")]
+            BLAKE2b512 {
+                name: "BLAKE2b512",
+                description: "BLAKE2b with 512 bit output",
+                cryptographic: true,
+                hasher: blake2::Blake2b512,
+                output_size: 64,
+                extension: "b2",
+            }
+
+            #[cfg(feature = "blake2")]
+            BLAKE2s256 {
+                name: "BLAKE2s256",
+                description: "BLAKE2s with 256 bit output",
+                cryptographic: true,
+                hasher: blake2::Blake2s256,
+                output_size: 32,
+                extension: "blake2s256",
+            }
+
+            #[cfg(feature = "blake2")]
+            BLAKE2bp {
+                name: "BLAKE2bp",
+                description:
+                    "BLAKE2bp, 4-way parallel BLAKE2b with 512 bit output",
+                cryptographic: true,
+                hasher: crate::adapter::Blake2bp,
+                output_size: 64,
+                extension: "blake2bp",
+            }
+
+            #[cfg(feature = "blake2")]
+            BLAKE2sp {
+                name: "BLAKE2sp",
+                description:
+                    "BLAKE2sp, 8-way parallel BLAKE2s with 256 bit output",
+                cryptographic: true,
+                hasher: crate::adapter::Blake2sp,
+                output_size: 32,
+                extension: "blake2sp",
+            }
+
+            #[cfg(feature = "blake3")]
+            BLAKE3 {
+                name: "BLAKE3",
+                description: "BLAKE3 with 256 bit output",
+                cryptographic: true,
+                hasher: blake3::Hasher,
+                output_size: 32,
+                extension: "b3",
+            }
+
+            #[cfg(feature = "cityhash")]
+            CityHash64 {
+                name: "CityHash-64",
+                description: "64 bit CityHash, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::CityHash64,
+                output_size: 8,
+                extension: "cityhash-64",
+            }
+
+            #[cfg(feature = "crc")]
+            CRC32 {
+                name: "CRC-32",
+                description: "CRC-32 (IEEE 802.3), not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Crc32,
+                output_size: 4,
+                extension: "crc-32",
+            }
+
+            #[cfg(feature = "crc")]
+            CRC32C {
+                name: "CRC-32C",
+                description: "CRC-32C (Castagnoli), not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Crc32c,
+                output_size: 4,
+                extension: "crc-32c",
+            }
+
+            #[cfg(feature = "crc")]
+            CRC64XZ {
+                name: "CRC-64/XZ",
+                description: "CRC-64 as used by xz, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Crc64Xz,
+                output_size: 8,
+                extension: "crc-64-xz",
+            }
+
+            #[cfg(feature = "crc")]
+            CRC64ECMA {
+                name: "CRC-64/ECMA-182",
+                description: "CRC-64 (ECMA-182), not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Crc64Ecma182,
+                output_size: 8,
+                extension: "crc-64-ecma-182",
+            }
+
+            #[cfg(feature = "farmhash")]
+            FarmHash64 {
+                name: "FarmHash-64",
+                description: "64 bit FarmHash fingerprint, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::FarmHash64,
+                output_size: 8,
+                extension: "farmhash-64",
+            }
+
+            #[cfg(feature = "fnv")]
+            FNV1a32 {
+                name: "FNV-1a-32",
+                description: "32 bit FNV-1a, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Fnv1a32,
+                output_size: 4,
+                extension: "fnv-1a-32",
+            }
+
+            #[cfg(feature = "fnv")]
+            FNV1a64 {
+                name: "FNV-1a-64",
+                description: "64 bit FNV-1a, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Fnv1a64,
+                output_size: 8,
+                extension: "fnv-1a-64",
+            }
+
+            #[cfg(feature = "fsb")]
+            FSB160 {
+                name: "FSB160",
+                description: "Fast Syndrome-Based hash with 160 bit output",
+                cryptographic: true,
+                hasher: fsb::Fsb160,
+                output_size: 20,
+                extension: "fsb160",
+            }
+
+            #[cfg(feature = "fsb")]
+            FSB224 {
+                name: "FSB224",
+                description: "Fast Syndrome-Based hash with 224 bit output",
+                cryptographic: true,
+                hasher: fsb::Fsb224,
+                output_size: 28,
+                extension: "fsb224",
+            }
+
+            #[cfg(feature = "fsb")]
+            FSB256 {
+                name: "FSB256",
+                description: "Fast Syndrome-Based hash with 256 bit output",
+                cryptographic: true,
+                hasher: fsb::Fsb256,
+                output_size: 32,
+                extension: "fsb256",
+            }
+
+            #[cfg(feature = "fsb")]
+            FSB384 {
+                name: "FSB384",
+                description: "Fast Syndrome-Based hash with 384 bit output",
+                cryptographic: true,
+                hasher: fsb::Fsb384,
+                output_size: 48,
+                extension: "fsb384",
+            }
+
+            #[cfg(feature = "fsb")]
+            FSB512 {
+                name: "FSB512",
+                description: "Fast Syndrome-Based hash with 512 bit output",
+                cryptographic: true,
+                hasher: fsb::Fsb512,
+                output_size: 64,
+                extension: "fsb512",
+            }
+
+            #[cfg(feature = "gost94")]
+            GOST94CryptoPro {
+                name: "GOST94CryptoPro",
+                description: "GOST R 34.11-94 with CryptoPro parameters",
+                cryptographic: true,
+                hasher: gost94::Gost94CryptoPro,
+                output_size: 32,
+                extension: "gost94cryptopro",
+            }
+
+            #[cfg(feature = "gost94")]
+            GOST94UA {
+                name: "GOST94UA",
+                description: "GOST 34.311-95 with Ukrainian parameters",
+                cryptographic: true,
+                hasher: gost94::Gost94UA,
+                output_size: 32,
+                extension: "gost94ua",
+            }
+
+            #[cfg(feature = "gost94")]
+            GOST94s2015 {
+                name: "GOST94s2015",
+                description:
+                    "GOST R 34.11-94 with S-box from GOST R 34.12-2015",
+                cryptographic: true,
+                hasher: gost94::Gost94s2015,
+                output_size: 32,
+                extension: "gost94s2015",
+            }
+
+            #[cfg(feature = "groestl")]
+            Groestl224 {
+                name: "Groestl224",
+                description: "Grøstl with 224 bit output",
+                cryptographic: true,
+                hasher: groestl::Groestl224,
+                output_size: 28,
+                extension: "groestl224",
+            }
+
+            #[cfg(feature = "groestl")]
+            Groestl256 {
+                name: "Groestl256",
+                description: "Grøstl with 256 bit output",
+                cryptographic: true,
+                hasher: groestl::Groestl256,
+                output_size: 32,
+                extension: "groestl256",
+            }
+
+            #[cfg(feature = "groestl")]
+            Groestl384 {
+                name: "Groestl384",
+                description: "Grøstl with 384 bit output",
+                cryptographic: true,
+                hasher: groestl::Groestl384,
+                output_size: 48,
+                extension: "groestl384",
+            }
+
+            #[cfg(feature = "groestl")]
+            Groestl512 {
+                name: "Groestl512",
+                description: "Grøstl with 512 bit output",
+                cryptographic: true,
+                hasher: groestl::Groestl512,
+                output_size: 64,
+                extension: "groestl512",
+            }
+
+            #[cfg(feature = "jh")]
+            JH224 {
+                name: "JH-224",
+                description: "JH with 224 bit output",
+                cryptographic: true,
+                hasher: jh::Jh224,
+                output_size: 28,
+                extension: "jh-224",
+            }
+
+            #[cfg(feature = "jh")]
+            JH256 {
+                name: "JH-256",
+                description: "JH with 256 bit output",
+                cryptographic: true,
+                hasher: jh::Jh256,
+                output_size: 32,
+                extension: "jh-256",
+            }
+
+            #[cfg(feature = "jh")]
+            JH384 {
+                name: "JH-384",
+                description: "JH with 384 bit output",
+                cryptographic: true,
+                hasher: jh::Jh384,
+                output_size: 48,
+                extension: "jh-384",
+            }
+
+            #[cfg(feature = "jh")]
+            JH512 {
+                name: "JH-512",
+                description: "JH with 512 bit output",
+                cryptographic: true,
+                hasher: jh::Jh512,
+                output_size: 64,
+                extension: "jh-512",
+            }
+
+            #[cfg(feature = "kupyna")]
+            Kupyna256 {
+                name: "Kupyna-256",
+                description: "Kupyna with 256 bit output",
+                cryptographic: true,
+                hasher: kupyna::Kupyna256,
+                output_size: 32,
+                extension: "kupyna-256",
+            }
+
+            #[cfg(feature = "kupyna")]
+            Kupyna512 {
+                name: "Kupyna-512",
+                description: "Kupyna with 512 bit output",
+                cryptographic: true,
+                hasher: kupyna::Kupyna512,
+                output_size: 64,
+                extension: "kupyna-512",
+            }
+
+            #[cfg(feature = "md2")]
+            MD2 {
+                name: "MD2",
+                description: "MD2 (insecure, legacy use only)",
+                cryptographic: true,
+                hasher: md2::Md2,
+                output_size: 16,
+                extension: "md2",
+            }
+
+            #[cfg(feature = "md4")]
+            MD4 {
+                name: "MD4",
+                description: "MD4 (insecure, legacy use only)",
+                cryptographic: true,
+                hasher: md4::Md4,
+                output_size: 16,
+                extension: "md4",
+            }
+
+            #[cfg(feature = "md5")]
+            MD5 {
+                name: "MD5",
+                description: "MD5 (insecure, legacy use only)",
+                cryptographic: true,
+                hasher: md5::Md5,
+                output_size: 16,
+                extension: "md5",
+            }
+
+            #[cfg(feature = "metrohash")]
+            MetroHash64 {
+                name: "MetroHash-64",
+                description: "64 bit MetroHash, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::MetroHash64,
+                output_size: 8,
+                extension: "metrohash-64",
+            }
+
+            #[cfg(feature = "metrohash")]
+            MetroHash128 {
+                name: "MetroHash-128",
+                description: "128 bit MetroHash, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::MetroHash128,
+                output_size: 16,
+                extension: "metrohash-128",
+            }
+
+            #[cfg(feature = "murmur3")]
+            Murmur3_32 {
+                name: "Murmur3-32",
+                description: "32 bit Murmur3 (x86), not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Murmur3_32,
+                output_size: 4,
+                extension: "murmur3-32",
+            }
+
+            #[cfg(feature = "murmur3")]
+            Murmur3_128 {
+                name: "Murmur3-128",
+                description: "128 bit Murmur3 (x64), not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Murmur3_128,
+                output_size: 16,
+                extension: "murmur3-128",
+            }
+
+            #[cfg(feature = "ripemd")]
+            RIPEMD160 {
+                name: "RIPEMD160",
+                description: "RIPEMD with 160 bit output",
+                cryptographic: true,
+                hasher: ripemd::Ripemd160,
+                output_size: 20,
+                extension: "ripemd160",
+            }
+
+            #[cfg(feature = "ripemd")]
+            RIPEMD256 {
+                name: "RIPEMD256",
+                description: "RIPEMD with 256 bit output",
+                cryptographic: true,
+                hasher: ripemd::Ripemd256,
+                output_size: 32,
+                extension: "ripemd256",
+            }
+
+            #[cfg(feature = "ripemd")]
+            RIPEMD320 {
+                name: "RIPEMD320",
+                description: "RIPEMD with 320 bit output",
+                cryptographic: true,
+                hasher: ripemd::Ripemd320,
+                output_size: 40,
+                extension: "ripemd320",
+            }
+
+            #[cfg(feature = "seahash")]
+            SeaHash {
+                name: "SeaHash",
+                description: "64 bit SeaHash, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::SeaHash,
+                output_size: 8,
+                extension: "seahash",
+            }
+
+            #[cfg(feature = "sha1")]
+            SHA1 {
+                name: "SHA1",
+                description: "SHA-1 (insecure, legacy use only)",
+                cryptographic: true,
+                hasher: sha1::Sha1,
+                output_size: 20,
+                extension: "sha1",
+            }
+
+            #[cfg(feature = "sha2")]
+            SHA224 {
+                name: "SHA224",
+                description: "SHA-2 with 224 bit output",
+                cryptographic: true,
+                hasher: sha2::Sha224,
+                output_size: 28,
+                extension: "sha224",
+            }
+
+            #[cfg(feature = "sha2")]
+            SHA256 {
+                name: "SHA256",
+                description: "SHA-2 with 256 bit output",
+                cryptographic: true,
+                hasher: sha2::Sha256,
+                output_size: 32,
+                extension: "sha256",
+            }
+
+            #[cfg(feature = "sha2")]
+            SHA384 {
+                name: "SHA384",
+                description: "SHA-2 with 384 bit output",
+                cryptographic: true,
+                hasher: sha2::Sha384,
+                output_size: 48,
+                extension: "sha384",
+            }
+
+            #[cfg(feature = "sha2")]
+            SHA512 {
+                name: "SHA512",
+                description: "SHA-2 with 512 bit output",
+                cryptographic: true,
+                hasher: sha2::Sha512,
+                output_size: 64,
+                extension: "sha512",
+            }
+
+            #[cfg(feature = "sha2")]
+            SHA512_224 {
+                name: "SHA512/224",
+                description: "SHA-512 truncated to 224 bit output",
+                cryptographic: true,
+                hasher: sha2::Sha512_224,
+                output_size: 28,
+                extension: "sha512-224",
+            }
+
+            #[cfg(feature = "sha2")]
+            SHA512_256 {
+                name: "SHA512/256",
+                description: "SHA-512 truncated to 256 bit output",
+                cryptographic: true,
+                hasher: sha2::Sha512_256,
+                output_size: 32,
+                extension: "sha512-256",
+            }
+
+            #[cfg(feature = "sha3")]
+            SHA3_224 {
+                name: "SHA3-224",
+                description: "SHA-3 with 224 bit output",
+                cryptographic: true,
+                hasher: sha3::Sha3_224,
+                output_size: 28,
+                extension: "sha3-224",
+            }
+
+            #[cfg(feature = "sha3")]
+            SHA3_256 {
+                name: "SHA3-256",
+                description: "SHA-3 with 256 bit output",
+                cryptographic: true,
+                hasher: sha3::Sha3_256,
+                output_size: 32,
+                extension: "sha3-256",
+            }
+
+            #[cfg(feature = "sha3")]
+            SHA3_384 {
+                name: "SHA3-384",
+                description: "SHA-3 with 384 bit output",
+                cryptographic: true,
+                hasher: sha3::Sha3_384,
+                output_size: 48,
+                extension: "sha3-384",
+            }
+
+            #[cfg(feature = "sha3")]
+            SHA3_512 {
+                name: "SHA3-512",
+                description: "SHA-3 with 512 bit output",
+                cryptographic: true,
+                hasher: sha3::Sha3_512,
+                output_size: 64,
+                extension: "sha3-512",
+            }
+
+            #[cfg(feature = "sha3")]
+            Keccak224 {
+                name: "Keccak-224",
+                description: "Keccak with 224 bit output, not SHA-3",
+                cryptographic: true,
+                hasher: sha3::Keccak224,
+                output_size: 28,
+                extension: "keccak-224",
+            }
+
+            #[cfg(feature = "sha3")]
+            Keccak256 {
+                name: "Keccak-256",
+                description: "Keccak with 256 bit output, not SHA-3",
+                cryptographic: true,
+                hasher: sha3::Keccak256,
+                output_size: 32,
+                extension: "keccak-256",
+            }
+
+            #[cfg(feature = "sha3")]
+            Keccak384 {
+                name: "Keccak-384",
+                description: "Keccak with 384 bit output, not SHA-3",
+                cryptographic: true,
+                hasher: sha3::Keccak384,
+                output_size: 48,
+                extension: "keccak-384",
+            }
+
+            #[cfg(feature = "sha3")]
+            Keccak512 {
+                name: "Keccak-512",
+                description: "Keccak with 512 bit output, not SHA-3",
+                cryptographic: true,
+                hasher: sha3::Keccak512,
+                output_size: 64,
+                extension: "keccak-512",
+            }
+
+            #[cfg(feature = "shabal")]
+            SHABAL192 {
+                name: "Shabal-192",
+                description: "Shabal with 192 bit output",
+                cryptographic: true,
+                hasher: shabal::Shabal192,
+                output_size: 24,
+                extension: "shabal-192",
+            }
+
+            #[cfg(feature = "shabal")]
+            SHABAL224 {
+                name: "Shabal-224",
+                description: "Shabal with 224 bit output",
+                cryptographic: true,
+                hasher: shabal::Shabal224,
+                output_size: 28,
+                extension: "shabal-224",
+            }
+
+            #[cfg(feature = "shabal")]
+            SHABAL256 {
+                name: "Shabal-256",
+                description: "Shabal with 256 bit output",
+                cryptographic: true,
+                hasher: shabal::Shabal256,
+                output_size: 32,
+                extension: "shabal-256",
+            }
+
+            #[cfg(feature = "shabal")]
+            SHABAL384 {
+                name: "Shabal-384",
+                description: "Shabal with 384 bit output",
+                cryptographic: true,
+                hasher: shabal::Shabal384,
+                output_size: 48,
+                extension: "shabal-384",
+            }
+
+            #[cfg(feature = "shabal")]
+            SHABAL512 {
+                name: "Shabal-512",
+                description: "Shabal with 512 bit output",
+                cryptographic: true,
+                hasher: shabal::Shabal512,
+                output_size: 64,
+                extension: "shabal-512",
+            }
+
+            #[cfg(feature = "skein")]
+            Skein256 {
+                name: "Skein-256",
+                description: "Skein with 256 bit state and output",
+                cryptographic: true,
+                hasher: skein::Skein256<skein::consts::U32>,
+                output_size: 32,
+                extension: "skein-256",
+            }
+
+            #[cfg(feature = "skein")]
+            Skein512 {
+                name: "Skein-512",
+                description: "Skein with 512 bit state and output",
+                cryptographic: true,
+                hasher: skein::Skein512<skein::consts::U64>,
+                output_size: 64,
+                extension: "skein-512",
+            }
+
+            #[cfg(feature = "skein")]
+            Skein1024 {
+                name: "Skein-1024",
+                description: "Skein with 1024 bit state and output",
+                cryptographic: true,
+                hasher: skein::Skein1024<skein::consts::U128>,
+                output_size: 128,
+                extension: "skein-1024",
+            }
+
+            #[cfg(feature = "sm3")]
+            SM3 {
+                name: "SM3",
+                description: "ShangMi 3 (GB/T 32905-2016)",
+                cryptographic: true,
+                hasher: sm3::Sm3,
+                output_size: 32,
+                extension: "sm3",
+            }
+
+            #[cfg(feature = "streebog")]
+            Streebog256 {
+                name: "Streebog-256",
+                description:
+                    "Streebog (GOST R 34.11-2012) with 256 bit output",
+                cryptographic: true,
+                hasher: streebog::Streebog256,
+                output_size: 32,
+                extension: "streebog-256",
+            }
+
+            #[cfg(feature = "streebog")]
+            Streebog512 {
+                name: "Streebog-512",
+                description:
+                    "Streebog (GOST R 34.11-2012) with 512 bit output",
+                cryptographic: true,
+                hasher: streebog::Streebog512,
+                output_size: 64,
+                extension: "streebog-512",
+            }
+
+            #[cfg(feature = "sum")]
+            Cksum {
+                name: "cksum",
+                description: "POSIX cksum CRC, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Cksum,
+                output_size: 4,
+                extension: "cksum",
+            }
+
+            #[cfg(feature = "sum")]
+            BSDSum {
+                name: "BSD-sum",
+                description: "BSD sum checksum, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::BsdSum,
+                output_size: 2,
+                extension: "bsd-sum",
+            }
+
+            #[cfg(feature = "sum")]
+            SysVSum {
+                name: "SysV-sum",
+                description: "System V sum checksum, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::SysVSum,
+                output_size: 2,
+                extension: "sysv-sum",
+            }
+
+            #[cfg(feature = "tiger")]
+            Tiger {
+                name: "Tiger",
+                description: "Tiger with 192 bit output",
+                cryptographic: true,
+                hasher: tiger::Tiger,
+                output_size: 24,
+                extension: "tiger",
+            }
+
+            #[cfg(feature = "tiger")]
+            Tiger2 {
+                name: "Tiger2",
+                description: "Tiger2 with 192 bit output",
+                cryptographic: true,
+                hasher: tiger::Tiger2,
+                output_size: 24,
+                extension: "tiger2",
+            }
+
+            #[cfg(feature = "whirlpool")]
+            Whirlpool {
+                name: "Whirlpool",
+                description: "Whirlpool with 512 bit output",
+                cryptographic: true,
+                hasher: whirlpool::Whirlpool,
+                output_size: 64,
+                extension: "whirlpool",
+            }
+
+            #[cfg(feature = "wyhash")]
+            WyHash {
+                name: "wyhash",
+                description: "64 bit wyhash, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::WyHash,
+                output_size: 8,
+                extension: "wyhash",
+            }
+
+            #[cfg(feature = "xxhash")]
+            XXH32 {
+                name: "XXH32",
+                description: "xxHash with 32 bit output, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Xxh32,
+                output_size: 4,
+                extension: "xxh32",
+            }
+
+            #[cfg(feature = "xxhash")]
+            XXH64 {
+                name: "XXH64",
+                description: "xxHash with 64 bit output, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Xxh64,
+                output_size: 8,
+                extension: "xxh64",
+            }
+
+            #[cfg(feature = "xxhash")]
+            XXH3_64 {
+                name: "XXH3-64",
+                description: "XXH3 with 64 bit output, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Xxh3_64,
+                output_size: 8,
+                extension: "xxh3",
+            }
+
+            #[cfg(feature = "xxhash")]
+            XXH3_128 {
+                name: "XXH3-128",
+                description: "XXH3 with 128 bit output, not cryptographic",
+                cryptographic: false,
+                hasher: crate::adapter::Xxh3_128,
+                output_size: 16,
+                extension: "xxh128",
+            }
+        }
+    };
+}
+
+pub(crate) use for_each_digest;
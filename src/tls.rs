@@ -0,0 +1,103 @@
+//! Contains the mapping of digest algorithms to TLS hash algorithm IDs.
+
+use crate::Digest;
+
+impl Digest {
+    /// Returns the ID from the IANA TLS `HashAlgorithm` registry, if this
+    /// digest algorithm is registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::SHA256.tls_hash_id(), Some(4));
+    /// ```
+    #[must_use]
+    pub const fn tls_hash_id(&self) -> Option<u8> {
+        match self {
+            #[cfg(feature = "md5")]
+            Self::MD5 => Some(1),
+
+            #[cfg(feature = "sha1")]
+            Self::SHA1 => Some(2),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA224 => Some(3),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA256 => Some(4),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA384 => Some(5),
+
+            #[cfg(feature = "sha2")]
+            Self::SHA512 => Some(6),
+
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns the digest algorithm for an ID from the IANA TLS
+    /// `HashAlgorithm` registry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::Digest;
+    ///
+    /// assert_eq!(Digest::from_tls_hash_id(1), Some(Digest::MD5));
+    /// assert_eq!(Digest::from_tls_hash_id(0), None);
+    /// ```
+    #[must_use]
+    pub const fn from_tls_hash_id(id: u8) -> Option<Self> {
+        match id {
+            #[cfg(feature = "md5")]
+            1 => Some(Self::MD5),
+
+            #[cfg(feature = "sha1")]
+            2 => Some(Self::SHA1),
+
+            #[cfg(feature = "sha2")]
+            3 => Some(Self::SHA224),
+
+            #[cfg(feature = "sha2")]
+            4 => Some(Self::SHA256),
+
+            #[cfg(feature = "sha2")]
+            5 => Some(Self::SHA384),
+
+            #[cfg(feature = "sha2")]
+            6 => Some(Self::SHA512),
+
+            _ => None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use crate::Digest;
+
+    #[test]
+    fn roundtrip() {
+        for digest in Digest::value_variants() {
+            if let Some(id) = digest.tls_hash_id() {
+                assert_eq!(Digest::from_tls_hash_id(id), Some(*digest));
+            }
+        }
+
+        for id in 0..=u8::MAX {
+            if let Some(digest) = Digest::from_tls_hash_id(id) {
+                assert_eq!(digest.tls_hash_id(), Some(id));
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Contains async hashing helpers and adapters for Tokio.
+//!
+//! These are the async counterparts of [`crate::hash`] and [`crate::io`].
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::{tokio, Digest};
+//!
+//! # ::tokio::runtime::Builder::new_current_thread().build()?.block_on(async {
+//! let output = tokio::hash_file("Cargo.toml", Digest::SHA256).await?;
+//!
+//! assert_eq!(output, clap_digest::hash_file("Cargo.toml", Digest::SHA256)?);
+//! # Ok::<(), std::io::Error>(())
+//! # })?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use ::tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+use digest::Update;
+
+use crate::hash::BUFFER_SIZE;
+use crate::{Digest, Hasher, Output};
+
+/// Returns the digest of everything read from the reader.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub async fn hash_reader(
+    mut reader: impl AsyncRead + Unpin,
+    digest: Digest,
+) -> io::Result<Output> {
+    let mut hasher = Hasher::new(digest);
+    let mut buffer = vec![0; BUFFER_SIZE];
+
+    loop {
+        match reader.read(&mut buffer).await {
+            Ok(0) => break,
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(hasher.finalize_output())
+}
+
+/// Returns the digest of the file content, read via [`tokio::fs`].
+///
+/// # Errors
+///
+/// Returns an error if opening or reading the file fails.
+pub async fn hash_file(
+    path: impl AsRef<Path>,
+    digest: Digest,
+) -> io::Result<Output> {
+    let file = ::tokio::fs::File::open(path).await?;
+
+    hash_reader(file, digest).await
+}
+
+/// Async reader that hashes everything read through it.
+///
+/// This is the async counterpart of [`crate::io::HashingReader`].
+#[derive(Clone, Debug)]
+pub struct AsyncHashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R> AsyncHashingReader<R> {
+    /// Wraps the reader, hashing with the digest.
+    pub fn new(inner: R, digest: Digest) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(digest),
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// Data read directly from the inner reader is not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the hasher, e.g. to get an intermediate output via
+    /// [`Hasher::checkpoint`].
+    pub const fn hasher(&self) -> &Hasher {
+        &self.hasher
+    }
+
+    /// Returns the output of the data read so far.
+    #[must_use]
+    pub fn finalize(self) -> Output {
+        self.hasher.finalize_output()
+    }
+
+    /// Returns the inner reader and the output of the data read so far.
+    pub fn into_parts(self) -> (R, Output) {
+        (self.inner, self.hasher.finalize_output())
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncHashingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.hasher.update(&buf.filled()[filled..]);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Async writer that hashes everything written through it.
+///
+/// This is the async counterpart of [`crate::io::HashingWriter`].
+#[derive(Clone, Debug)]
+pub struct AsyncHashingWriter<W> {
+    inner: W,
+    hasher: Hasher,
+}
+
+impl<W> AsyncHashingWriter<W> {
+    /// Wraps the writer, hashing with the digest.
+    pub fn new(inner: W, digest: Digest) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(digest),
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Data written directly to the inner writer is not hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the hasher, e.g. to get an intermediate output via
+    /// [`Hasher::checkpoint`].
+    pub const fn hasher(&self) -> &Hasher {
+        &self.hasher
+    }
+
+    /// Returns the output of the data written so far.
+    ///
+    /// The inner writer is dropped without flushing it.
+    #[must_use]
+    pub fn finalize(self) -> Output {
+        self.hasher.finalize_output()
+    }
+
+    /// Returns the inner writer and the output of the data written so far.
+    pub fn into_parts(self) -> (W, Output) {
+        (self.inner, self.hasher.finalize_output())
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncHashingWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        let n = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+        this.hasher.update(&buf[..n]);
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use ::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::{AsyncHashingReader, AsyncHashingWriter};
+    use crate::Digest;
+
+    #[::tokio::test]
+    async fn hash_file() {
+        let output = super::hash_file("Cargo.toml", Digest::SHA256).await;
+
+        assert_eq!(
+            output.unwrap(),
+            crate::hash_file("Cargo.toml", Digest::SHA256).unwrap()
+        );
+    }
+
+    #[::tokio::test]
+    async fn reader() {
+        let data = (0..=255).cycle().take(10_000).collect::<Vec<u8>>();
+        let mut reader = AsyncHashingReader::new(&data[..], Digest::SHA256);
+
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).await.unwrap();
+
+        assert_eq!(content, data);
+        assert_eq!(reader.finalize(), Digest::SHA256.hash(&data));
+    }
+
+    #[::tokio::test]
+    async fn writer() {
+        let data = (0..=255).cycle().take(10_000).collect::<Vec<u8>>();
+        let mut writer = AsyncHashingWriter::new(Vec::new(), Digest::SHA256);
+
+        writer.write_all(&data).await.unwrap();
+        writer.flush().await.unwrap();
+
+        let (content, output) = writer.into_parts();
+        assert_eq!(content, data);
+        assert_eq!(output, Digest::SHA256.hash(&data));
+    }
+}
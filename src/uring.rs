@@ -0,0 +1,145 @@
+//! Contains the `io_uring` read backend of [`crate::hash::hash_file`].
+
+use std::fs::File;
+use std::io;
+use std::mem;
+use std::os::unix::io::AsRawFd;
+
+use io_uring::{opcode, types, IoUring};
+
+use crate::hash::{Error, HashOptions};
+use crate::{Digest, Hasher, Output};
+
+/// Returns the digest of the file content, or `None` if `io_uring` is not
+/// available.
+///
+/// Two buffers are used alternately, so the next chunk is read by the kernel
+/// while the current chunk is hashed. At most one read is in flight at any
+/// time, and it is waited for on every exit path, see [`Reads`].
+pub fn hash_file(
+    file: &File,
+    digest: Digest,
+    options: &mut HashOptions<'_>,
+) -> Result<Option<Output>, Error> {
+    let Ok(ring) = IoUring::new(2) else {
+        return Ok(None);
+    };
+
+    let size = options.buffer_size;
+    let mut reads = Reads {
+        ring,
+        fd: types::Fd(file.as_raw_fd()),
+        buffers: [vec![0; size], vec![0; size]],
+        pending: false,
+    };
+    let mut hasher = Hasher::new(digest);
+    let mut offset = options.offset;
+    let mut remaining = options.max_bytes.unwrap_or(u64::MAX);
+    let mut total = 0;
+    let mut current = 0;
+
+    options.check_cancelled()?;
+
+    if remaining > 0 {
+        reads.submit(current, chunk_len(size, remaining), offset)?;
+    }
+
+    while remaining > 0 {
+        let n = reads.wait()?;
+
+        if n == 0 {
+            break;
+        }
+
+        options.check_cancelled()?;
+
+        offset += n as u64;
+        remaining -= n as u64;
+        let next = 1 - current;
+
+        if remaining > 0 {
+            reads.submit(next, chunk_len(size, remaining), offset)?;
+        }
+
+        options.update(&mut hasher, &reads.buffers[current][..n]);
+        total += n as u64;
+        options.report_progress(total);
+        current = next;
+    }
+
+    Ok(Some(hasher.finalize_output()))
+}
+
+/// Returns the length of the next read, limited by the remaining bytes.
+fn chunk_len(size: usize, remaining: u64) -> usize {
+    usize::try_from(remaining).map_or(size, |remaining| size.min(remaining))
+}
+
+/// Reads of a file into two buffers via a ring.
+///
+/// A read still in flight is waited for on drop, including when unwinding
+/// from a panic in the hasher or the progress callback, so the kernel never
+/// writes into freed buffers.
+struct Reads {
+    ring: IoUring,
+    fd: types::Fd,
+    buffers: [Vec<u8>; 2],
+
+    /// Whether a read was submitted and not yet waited for.
+    pending: bool,
+}
+
+impl Reads {
+    /// Submits a read of the file at the offset into the first `len` bytes
+    /// of the buffer with the index.
+    fn submit(
+        &mut self,
+        buffer: usize,
+        len: usize,
+        offset: u64,
+    ) -> io::Result<()> {
+        let buffer = &mut self.buffers[buffer][..len];
+        let len = u32::try_from(buffer.len()).unwrap_or(u32::MAX);
+        let read = opcode::Read::new(self.fd, buffer.as_mut_ptr(), len)
+            .offset(offset)
+            .build();
+
+        // SAFETY: The buffer is not accessed again until the read is
+        // completed by `wait`, which happens at the latest on drop. If that
+        // fails, the buffers are leaked instead of freed.
+        unsafe { self.ring.submission().push(&read) }
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        self.pending = true;
+        self.ring.submit()?;
+
+        Ok(())
+    }
+
+    /// Waits for the submitted read and returns the number of bytes read.
+    fn wait(&mut self) -> io::Result<usize> {
+        loop {
+            match self.ring.submit_and_wait(1) {
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+
+            if let Some(completion) = self.ring.completion().next() {
+                self.pending = false;
+                let result = completion.result();
+
+                return usize::try_from(result)
+                    .map_err(|_| io::Error::from_raw_os_error(-result));
+            }
+        }
+    }
+}
+
+impl Drop for Reads {
+    fn drop(&mut self) {
+        if self.pending && self.wait().is_err() {
+            mem::forget(mem::take(&mut self.buffers));
+        }
+    }
+}
@@ -0,0 +1,722 @@
+//! Contains verification of checksum files, like `sha256sum --check`.
+//!
+//! Both GNU checksum lines, i.e. `<hex>  <file>`, and BSD-style lines, i.e.
+//! `<TAG> (<file>) = <hex>`, are supported, even mixed in one file. The
+//! digest algorithm of BSD-style lines is taken from their tags.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::verify::Verifier;
+//! use clap_digest::Digest;
+//!
+//! let output = clap_digest::hash_file("Cargo.toml", Digest::SHA256)?;
+//! let content = format!("{output}  Cargo.toml\n");
+//!
+//! let report = Verifier::new().digest(Digest::SHA256).verify(&content);
+//!
+//! assert_eq!(report.ok(), 1);
+//! assert!(report.checks()[0].status().is_ok());
+//! assert_eq!(report.exit_code(), 0);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::format::{self, Mode};
+use crate::hash::{self, Input};
+use crate::{detect, encode, Digest, Output};
+
+/// Checksum line of a checksum file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    digest: Digest,
+    expected: Output,
+    path: PathBuf,
+    mode: Mode,
+}
+
+impl Entry {
+    /// Returns the digest algorithm of the checksum.
+    #[must_use]
+    pub const fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns the expected digest output.
+    #[must_use]
+    pub const fn expected(&self) -> &Output {
+        &self.expected
+    }
+
+    /// Returns the path of the file to check.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the mode the file is read in.
+    #[must_use]
+    pub const fn mode(&self) -> Mode {
+        self.mode
+    }
+}
+
+/// Status of a checked line.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Status {
+    /// The checksum matches.
+    Ok,
+
+    /// The checksum doesn't match.
+    Failed,
+
+    /// The file doesn't exist.
+    Missing,
+
+    /// Opening or reading the file failed.
+    Unreadable(io::Error),
+
+    /// The line is not a valid checksum line.
+    Malformed,
+}
+
+impl Status {
+    /// Returns `true` if the checksum matches.
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok)
+    }
+}
+
+impl fmt::Display for Status {
+    /// Formats the status like GNU coreutils.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => write!(f, "OK"),
+            Self::Failed => write!(f, "FAILED"),
+            Self::Missing | Self::Unreadable(_) => {
+                write!(f, "FAILED open or read")
+            }
+            Self::Malformed => write!(f, "improperly formatted"),
+        }
+    }
+}
+
+/// Result of checking one line of a checksum file.
+#[derive(Debug)]
+pub struct Check {
+    line: usize,
+    entry: Option<Entry>,
+    status: Status,
+}
+
+impl Check {
+    /// Returns the line number, starting at 1.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the parsed line, or [`None`] if it is malformed.
+    #[must_use]
+    pub const fn entry(&self) -> Option<&Entry> {
+        self.entry.as_ref()
+    }
+
+    /// Returns the status.
+    #[must_use]
+    pub const fn status(&self) -> &Status {
+        &self.status
+    }
+}
+
+/// Verifies the files listed in checksum files.
+///
+/// The behavior flags match the options of `sha256sum --check`, see the
+/// corresponding args in [`crate::arg`].
+#[derive(Clone, Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Verifier {
+    digest: Option<Digest>,
+    ignore_missing: bool,
+    quiet: bool,
+    status: bool,
+    strict: bool,
+    warn: bool,
+}
+
+impl Verifier {
+    /// Returns a new verifier detecting the digest algorithm.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the digest algorithm of the checksums.
+    ///
+    /// By default, the algorithm of GNU checksum lines is detected via
+    /// [`detect::detect`]. BSD-style lines with another algorithm are
+    /// malformed.
+    #[must_use]
+    pub const fn digest(mut self, digest: Digest) -> Self {
+        self.digest = Some(digest);
+        self
+    }
+
+    /// Skips missing files instead of failing.
+    #[must_use]
+    pub const fn ignore_missing(mut self, ignore_missing: bool) -> Self {
+        self.ignore_missing = ignore_missing;
+        self
+    }
+
+    /// Doesn't report successfully verified files.
+    #[must_use]
+    pub const fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Doesn't report anything, only [`VerifyReport::exit_code`] tells the
+    /// result.
+    #[must_use]
+    pub const fn status(mut self, status: bool) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Fails on improperly formatted lines.
+    #[must_use]
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Warns about every improperly formatted line.
+    #[must_use]
+    pub const fn warn(mut self, warn: bool) -> Self {
+        self.warn = warn;
+        self
+    }
+
+    /// Checks every line of the checksum file content.
+    ///
+    /// Lines starting with `#` are skipped, as are missing files if
+    /// [`Verifier::ignore_missing`] is set. Files are read relative to the
+    /// current directory, `-` meaning standard input.
+    #[must_use]
+    pub fn verify(&self, content: &str) -> VerifyReport {
+        let digest = self
+            .digest
+            .or_else(|| detect::detect(content).and_then(|d| d.digest()));
+
+        let checks = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.starts_with('#'))
+            .map(|(index, line)| {
+                let entry = parse_bsd(line, self.digest)
+                    .or_else(|| parse_gnu(line, digest));
+                let status = entry.as_ref().map_or(Status::Malformed, check);
+
+                Check {
+                    line: index + 1,
+                    entry,
+                    status,
+                }
+            })
+            .filter(|check| {
+                !(self.ignore_missing
+                    && matches!(check.status, Status::Missing))
+            })
+            .collect::<Vec<_>>();
+
+        let mut report = VerifyReport {
+            ok: 0,
+            failed: 0,
+            missing: 0,
+            malformed: 0,
+            strict: self.strict,
+            checks: Vec::new(),
+        };
+
+        for check in &checks {
+            match check.status {
+                Status::Ok => report.ok += 1,
+                Status::Failed => report.failed += 1,
+                Status::Missing | Status::Unreadable(_) => report.missing += 1,
+                Status::Malformed => report.malformed += 1,
+            }
+        }
+
+        report.checks = checks;
+        report
+    }
+
+    /// Writes the report like GNU coreutils.
+    ///
+    /// The status of every file is written to `out` as `<file>: <status>`,
+    /// except successfully verified files with [`Verifier::quiet`].
+    /// Warnings are written to `err`, about every improperly formatted line
+    /// only with [`Verifier::warn`]. Nothing is written with
+    /// [`Verifier::status`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn report(
+        &self,
+        report: &VerifyReport,
+        mut out: impl Write,
+        mut err: impl Write,
+    ) -> io::Result<()> {
+        if self.status {
+            return Ok(());
+        }
+
+        for check in &report.checks {
+            let Some(entry) = &check.entry else {
+                if self.warn {
+                    writeln!(
+                        err,
+                        "line {}: improperly formatted checksum line",
+                        check.line
+                    )?;
+                }
+
+                continue;
+            };
+
+            if !(self.quiet && check.status.is_ok()) {
+                writeln!(out, "{}: {}", entry.path.display(), check.status)?;
+            }
+        }
+
+        for (count, one, many) in [
+            (
+                report.malformed,
+                "line is improperly formatted",
+                "lines are improperly formatted",
+            ),
+            (
+                report.missing,
+                "listed file could not be read",
+                "listed files could not be read",
+            ),
+            (
+                report.failed,
+                "computed checksum did NOT match",
+                "computed checksums did NOT match",
+            ),
+        ] {
+            if count > 0 {
+                let message = if count == 1 { one } else { many };
+                writeln!(err, "WARNING: {count} {message}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Summary of verifying a checksum file.
+#[derive(Debug)]
+pub struct VerifyReport {
+    checks: Vec<Check>,
+    ok: usize,
+    failed: usize,
+    missing: usize,
+    malformed: usize,
+    strict: bool,
+}
+
+impl VerifyReport {
+    /// Returns the results of the checked lines.
+    #[must_use]
+    pub fn checks(&self) -> &[Check] {
+        &self.checks
+    }
+
+    /// Returns the results of the checked lines.
+    #[must_use]
+    pub fn into_checks(self) -> Vec<Check> {
+        self.checks
+    }
+
+    /// Returns the number of successfully verified files.
+    #[must_use]
+    pub const fn ok(&self) -> usize {
+        self.ok
+    }
+
+    /// Returns the number of files with a mismatching checksum.
+    #[must_use]
+    pub const fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Returns the number of files that are missing or couldn't be read.
+    #[must_use]
+    pub const fn missing(&self) -> usize {
+        self.missing
+    }
+
+    /// Returns the number of improperly formatted lines.
+    #[must_use]
+    pub const fn malformed(&self) -> usize {
+        self.malformed
+    }
+
+    /// Returns `true` if the verification passed.
+    ///
+    /// Like GNU coreutils, this fails if any file failed or couldn't be
+    /// read, or if no file was verified at all. Improperly formatted lines
+    /// only fail with [`Verifier::strict`].
+    #[must_use]
+    pub const fn passed(&self) -> bool {
+        self.failed == 0
+            && self.missing == 0
+            && self.ok > 0
+            && !(self.strict && self.malformed > 0)
+    }
+
+    /// Returns the exit code `sha256sum --check` would exit with, i.e. `0`
+    /// if the verification passed and `1` otherwise.
+    #[must_use]
+    pub const fn exit_code(&self) -> i32 {
+        if self.passed() {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+/// Returns `true` if the digest of the data matches the expected hex,
+/// comparing in constant time.
+///
+/// The hex may be upper- or lowercase. Invalid hex never matches.
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::verify::verify_hex;
+/// use clap_digest::Digest;
+///
+/// let expected = "acbd18db4cc2f85cedef654fccc4a4d8";
+///
+/// assert!(verify_hex(Digest::MD5, b"foo", expected));
+/// assert!(!verify_hex(Digest::MD5, b"bar", expected));
+/// ```
+#[must_use]
+pub fn verify_hex(digest: Digest, data: &[u8], expected_hex: &str) -> bool {
+    encode::decode_hex(expected_hex)
+        .is_ok_and(|expected| digest.hash(data).ct_eq(&expected))
+}
+
+/// Hashes the file of the entry and compares the output.
+fn check(entry: &Entry) -> Status {
+    let input = Input::from(entry.path.as_path());
+
+    match hash::hash_input(&input, entry.digest) {
+        Ok(output) if output.ct_eq(entry.expected.as_bytes()) => Status::Ok,
+        Ok(_) => Status::Failed,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Status::Missing,
+        Err(e) => Status::Unreadable(e),
+    }
+}
+
+/// Parses `<TAG> (<file>) = <hex>`.
+///
+/// If there is a digest, the tag must match it.
+fn parse_bsd(line: &str, digest: Option<Digest>) -> Option<Entry> {
+    let (escaped, line) = strip_escape(line);
+    let (tag, rest) = line.split_once(" (")?;
+    let (path, hex) = rest.rsplit_once(") = ")?;
+    let path = unescape_path(path, escaped)?;
+
+    let tagged = Digest::from_bsd_tag(tag)?;
+    let expected = encode::decode_hex(hex).ok()?;
+
+    let valid = digest.map_or(true, |digest| digest == tagged)
+        && !path.is_empty()
+        && expected.len() == tagged.output_size();
+
+    valid.then(|| Entry {
+        digest: tagged,
+        expected: expected.into(),
+        path: path.into(),
+        mode: Mode::Binary,
+    })
+}
+
+/// Parses `<hex>  <file>` or `<hex> *<file>`.
+///
+/// Without a digest, the digest is detected from the line alone.
+fn parse_gnu(line: &str, digest: Option<Digest>) -> Option<Entry> {
+    let (escaped, line) = strip_escape(line);
+    let (hex, rest) = line.split_once(' ')?;
+
+    let (mode, path) = if let Some(path) = rest.strip_prefix(' ') {
+        (Mode::Text, path)
+    } else {
+        (Mode::Binary, rest.strip_prefix('*')?)
+    };
+    let path = unescape_path(path, escaped)?;
+
+    let digest =
+        digest.or_else(|| detect::detect(line).and_then(|d| d.digest()))?;
+    let expected = encode::decode_hex(hex).ok()?;
+
+    (!path.is_empty() && expected.len() == digest.output_size()).then(|| {
+        Entry {
+            digest,
+            expected: expected.into(),
+            path: path.into(),
+            mode,
+        }
+    })
+}
+
+/// Returns whether the file name of the line is escaped, and the line
+/// without the leading `\\` marking that.
+fn strip_escape(line: &str) -> (bool, &str) {
+    line.strip_prefix('\\')
+        .map_or((false, line), |line| (true, line))
+}
+
+/// Returns the file name, unescaped if necessary.
+fn unescape_path(name: &str, escaped: bool) -> Option<String> {
+    if escaped {
+        format::unescape(name)
+    } else {
+        Some(name.to_owned())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, any(feature = "md5", feature = "sha1")))]
+mod tests {
+    #[cfg(all(feature = "md5", feature = "sha1"))]
+    use super::verify_hex;
+    use super::{Status, Verifier};
+    #[cfg(feature = "md5")]
+    use crate::format::Mode;
+    use crate::Digest;
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn verify() {
+        let dir = std::env::temp_dir().join("clap-digest-verify");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("foo"), "foo").unwrap();
+        std::fs::write(dir.join("bar"), "bar").unwrap();
+
+        let foo = dir.join("foo").display().to_string();
+        let bar = dir.join("bar").display().to_string();
+        let missing = dir.join("missing").display().to_string();
+        let md5_foo = "acbd18db4cc2f85cedef654fccc4a4d8";
+
+        let content = format!(
+            "# comment\n\
+             {md5_foo}  {foo}\n\
+             {md5_foo} *{bar}\n\
+             {md5_foo}  {missing}\n\
+             {md5_foo}x  {foo}\n\
+             \n\
+             {}  {foo}\n",
+            md5_foo.to_uppercase(),
+        );
+
+        let report = Verifier::new().verify(&content);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(report.ok(), 2);
+        assert_eq!(report.failed(), 1);
+        assert_eq!(report.missing(), 1);
+        assert_eq!(report.malformed(), 2);
+        assert_eq!(report.exit_code(), 1);
+
+        let checks = report.checks();
+
+        let statuses = checks
+            .iter()
+            .map(|check| (check.line(), check.status().to_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            statuses,
+            [
+                (2, "OK".to_owned()),
+                (3, "FAILED".to_owned()),
+                (4, "FAILED open or read".to_owned()),
+                (5, "improperly formatted".to_owned()),
+                (6, "improperly formatted".to_owned()),
+                (7, "OK".to_owned()),
+            ]
+        );
+
+        assert!(matches!(checks[2].status(), Status::Missing));
+
+        let entry = checks[1].entry().unwrap();
+        assert_eq!(entry.digest(), Digest::MD5);
+        assert_eq!(entry.mode(), Mode::Binary);
+        assert_eq!(entry.path().display().to_string(), bar);
+    }
+
+    #[cfg(all(feature = "md5", feature = "sha1"))]
+    #[test]
+    fn verify_bsd_mixed() {
+        let path = std::env::temp_dir().join("clap-digest-verify-bsd");
+        std::fs::write(&path, "foo").unwrap();
+        let path = path.display().to_string();
+
+        let content = format!(
+            "MD5 ({path}) = acbd18db4cc2f85cedef654fccc4a4d8\n\
+             SHA1 ({path}) = 0beec7b5ea3f0fdbc95d0dd47f3c5bc275da8a33\n\
+             SHA1 ({path}) = acbd18db4cc2f85cedef654fccc4a4d8\n\
+             CRC32 ({path}) = 8c736521\n",
+        );
+
+        let checks = Verifier::new().verify(&content).into_checks();
+        let checks_sha1 = Verifier::new()
+            .digest(Digest::SHA1)
+            .verify(&content)
+            .into_checks();
+        std::fs::remove_file(&path).unwrap();
+
+        let statuses = checks
+            .iter()
+            .map(|check| check.status().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            ["OK", "OK", "improperly formatted", "improperly formatted"]
+        );
+
+        let entry = checks[1].entry().unwrap();
+        assert_eq!(entry.digest(), Digest::SHA1);
+        assert_eq!(entry.mode(), Mode::Binary);
+
+        assert!(matches!(checks_sha1[0].status(), Status::Malformed));
+        assert!(checks_sha1[1].status().is_ok());
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn verify_escaped() {
+        let path = std::env::temp_dir().join("clap-digest-verify-a\\b\nc");
+        std::fs::write(&path, "foo").unwrap();
+
+        let output = Digest::MD5.hash(b"foo");
+        let content = format!(
+            "{}\n{}\n",
+            crate::format::gnu_line(&output, &path, Mode::Text),
+            crate::format::bsd_line(Digest::MD5, &output, &path),
+        );
+
+        let checks = Verifier::new().verify(&content).into_checks();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|check| check.status().is_ok()));
+        assert_eq!(checks[0].entry().unwrap().path(), path);
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn verify_flags() {
+        let path = std::env::temp_dir().join("clap-digest-verify-flags");
+        std::fs::write(&path, "foo").unwrap();
+        let path = path.display().to_string();
+
+        let content = format!(
+            "acbd18db4cc2f85cedef654fccc4a4d8  {path}\n\
+             acbd18db4cc2f85cedef654fccc4a4d8  {path}.missing\n\
+             foo\n",
+        );
+
+        let report = |verifier: &Verifier| {
+            let report = verifier.verify(&content);
+            let (mut out, mut err) = (Vec::new(), Vec::new());
+            verifier.report(&report, &mut out, &mut err).unwrap();
+
+            (
+                report.passed(),
+                String::from_utf8(out).unwrap(),
+                String::from_utf8(err).unwrap(),
+            )
+        };
+
+        let (passed, out, err) = report(&Verifier::new());
+        assert!(!passed);
+        assert_eq!(
+            out,
+            format!("{path}: OK\n{path}.missing: FAILED open or read\n")
+        );
+        assert_eq!(
+            err,
+            "WARNING: 1 line is improperly formatted\n\
+             WARNING: 1 listed file could not be read\n"
+        );
+
+        let (passed, out, err) =
+            report(&Verifier::new().ignore_missing(true).quiet(true));
+        assert!(passed);
+        assert_eq!(out, "");
+        assert_eq!(err, "WARNING: 1 line is improperly formatted\n");
+
+        let (passed, _, err) =
+            report(&Verifier::new().ignore_missing(true).warn(true));
+        assert!(passed);
+        assert!(
+            err.starts_with("line 3: improperly formatted checksum line\n")
+        );
+
+        let (passed, out, err) = report(
+            &Verifier::new()
+                .ignore_missing(true)
+                .strict(true)
+                .status(true),
+        );
+        assert!(!passed);
+        assert_eq!(out, "");
+        assert_eq!(err, "");
+
+        std::fs::remove_file(&path).unwrap();
+
+        let verifier = Verifier::new().ignore_missing(true);
+        assert!(!verifier.verify(&content).passed());
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn verify_wrong_digest() {
+        let content = "acbd18db4cc2f85cedef654fccc4a4d8  foo\n";
+        let checks = Verifier::new()
+            .digest(Digest::SHA1)
+            .verify(content)
+            .into_checks();
+
+        assert!(matches!(checks[0].status(), Status::Malformed));
+    }
+
+    #[cfg(all(feature = "md5", feature = "sha1"))]
+    #[test]
+    fn verify_hex_ct() {
+        let expected = "acbd18db4cc2f85cedef654fccc4a4d8";
+
+        assert!(verify_hex(Digest::MD5, b"foo", expected));
+        assert!(verify_hex(Digest::MD5, b"foo", &expected.to_uppercase()));
+        assert!(!verify_hex(Digest::MD5, b"foo", &expected[2..]));
+        assert!(!verify_hex(Digest::MD5, b"foo", "not hex"));
+        assert!(!verify_hex(Digest::SHA1, b"foo", expected));
+    }
+}
@@ -0,0 +1,80 @@
+//! Contains the [`DigestVisitor`] for static dispatch without macros.
+
+use digest::core_api::BlockSizeUser;
+
+use crate::{Digest, DynDigest};
+
+/// Visitor called with the concrete hasher type of a [`Digest`].
+///
+/// This is a non-macro alternative to [`with_digest!`], see
+/// [`Digest::visit`].
+pub trait DigestVisitor<R> {
+    /// Visits the concrete hasher type `D`.
+    fn visit<D>(self) -> R
+    where
+        D: digest::Digest
+            + BlockSizeUser
+            + DynDigest
+            + Default
+            + Clone
+            + Send
+            + Sync
+            + 'static;
+}
+
+impl Digest {
+    /// Calls the visitor with the concrete hasher type of this digest
+    /// algorithm.
+    ///
+    /// This lets libraries stay generic over [`digest::Digest`] while the
+    /// algorithm is still chosen at runtime, e.g. via clap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::{Digest, DigestVisitor};
+    ///
+    /// struct HashFoo;
+    ///
+    /// impl DigestVisitor<Vec<u8>> for HashFoo {
+    ///     fn visit<D: digest::Digest>(self) -> Vec<u8> {
+    ///         D::digest(b"foo").to_vec()
+    ///     }
+    /// }
+    ///
+    /// let hash = Digest::MD5.visit(HashFoo);
+    ///
+    /// assert_eq!(hash[..4], [0xac, 0xbd, 0x18, 0xdb]);
+    /// ```
+    pub fn visit<R>(self, visitor: impl DigestVisitor<R>) -> R {
+        with_digest!(self, |D| visitor.visit::<D>())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::DigestVisitor;
+    use crate::{Digest, DynDigest};
+
+    struct OutputSize;
+
+    impl DigestVisitor<usize> for OutputSize {
+        fn visit<D: digest::Digest>(self) -> usize {
+            <D as digest::Digest>::output_size()
+        }
+    }
+
+    #[test]
+    fn output_size() {
+        for digest in Digest::value_variants() {
+            let hasher: Box<dyn DynDigest> = (*digest).into();
+            assert_eq!(digest.visit(OutputSize), hasher.output_size());
+        }
+    }
+}
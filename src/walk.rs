@@ -0,0 +1,527 @@
+//! Contains hashing of directory trees.
+//!
+//! Trees are hashed either per file, see [`Walker::walk`] and
+//! [`Walker::manifest`], or into one digest, see [`Walker::dirhash`].
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::format::Formatter;
+//! use clap_digest::walk::Walker;
+//! use clap_digest::Digest;
+//!
+//! let walker = Walker::new(Digest::SHA256);
+//!
+//! let entries = walker.walk("src")?;
+//! assert!(entries.iter().any(|entry| entry.name() == "lib.rs"));
+//!
+//! let manifest = walker.manifest("src", &Formatter::new())?;
+//! assert!(manifest.lines().any(|line| line.ends_with("  lib.rs")));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use digest::Update;
+
+use crate::format::Formatter;
+use crate::glob::Pattern;
+use crate::{Digest, Hasher, Output};
+
+/// Handling of symbolic links when walking directory trees.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default,
+)]
+pub enum Symlinks {
+    /// Skip symbolic links.
+    #[default]
+    Skip,
+
+    /// Hash the files and walk the directories symbolic links point to.
+    ///
+    /// Walking a directory containing itself via a symbolic link fails.
+    Follow,
+
+    /// Record symbolic links as entries, hashing their target paths instead
+    /// of content, see [`Entry::is_symlink`].
+    Record,
+}
+
+/// Hashed file of a directory tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    path: PathBuf,
+    size: u64,
+    output: Output,
+    symlink: bool,
+}
+
+impl Entry {
+    /// Returns the path relative to the root of the tree.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path relative to the root of the tree, with `/` as
+    /// separator.
+    #[must_use]
+    pub fn name(&self) -> String {
+        name(&self.path)
+    }
+
+    /// Returns the file size in bytes, or the length of the target path of
+    /// a recorded symbolic link.
+    #[must_use]
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the digest output of the file content, or of the target path
+    /// of a recorded symbolic link.
+    #[must_use]
+    pub const fn output(&self) -> &Output {
+        &self.output
+    }
+
+    /// Returns `true` if this is a symbolic link recorded with
+    /// [`Symlinks::Record`].
+    #[must_use]
+    pub const fn is_symlink(&self) -> bool {
+        self.symlink
+    }
+}
+
+/// Hashes the files of directory trees.
+#[derive(Clone, Debug)]
+pub struct Walker {
+    digest: Digest,
+    symlinks: Symlinks,
+    excludes: Vec<Pattern>,
+}
+
+impl Walker {
+    /// Returns a new walker hashing with the digest algorithm.
+    ///
+    /// Symbolic links are skipped and nothing is excluded.
+    #[must_use]
+    pub const fn new(digest: Digest) -> Self {
+        Self {
+            digest,
+            symlinks: Symlinks::Skip,
+            excludes: Vec::new(),
+        }
+    }
+
+    /// Sets the handling of symbolic links.
+    #[must_use]
+    pub const fn symlinks(mut self, symlinks: Symlinks) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// Excludes files and directories matching the gitignore-style pattern.
+    ///
+    /// Patterns are matched against the names relative to the root, see
+    /// [`Entry::name`], the last matching pattern winning. Excluded
+    /// directories aren't walked. The syntax is that of `.gitignore` files:
+    ///
+    /// - `*` matches anything but `/`, `?` matches one character but `/`,
+    ///   and `[...]` matches one character of a class, e.g. `[a-z]`.
+    /// - `**` matches anything, including `/`.
+    /// - A pattern with a `/` at the start or in the middle is anchored at
+    ///   the root, otherwise it matches the file name at any depth.
+    /// - A pattern with a trailing `/` only matches directories.
+    /// - A pattern starting with `!` re-includes what it matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::walk::Walker;
+    /// use clap_digest::Digest;
+    ///
+    /// let walker = Walker::new(Digest::SHA256)
+    ///     .exclude("*.rs")
+    ///     .exclude("!lib.rs");
+    ///
+    /// let entries = walker.walk("src")?;
+    ///
+    /// assert_eq!(entries.len(), 1);
+    /// assert_eq!(entries[0].name(), "lib.rs");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn exclude(mut self, pattern: &str) -> Self {
+        self.excludes.push(Pattern::new(pattern));
+        self
+    }
+
+    /// Returns the digest algorithm files are hashed with.
+    #[must_use]
+    pub const fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Hashes every file below the root directory.
+    ///
+    /// The entries are sorted byte-wise by [`Entry::name`], so the order is
+    /// the same on every platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading a directory or file fails.
+    pub fn walk(&self, root: impl AsRef<Path>) -> io::Result<Vec<Entry>> {
+        let root = root.as_ref();
+        let mut entries = Vec::new();
+
+        let mut ancestors = Vec::new();
+
+        self.walk_dir(root, Path::new(""), &mut ancestors, &mut entries)?;
+
+        entries.sort_by_cached_key(Entry::name);
+        Ok(entries)
+    }
+
+    /// Returns the checksum file content for every file below the root
+    /// directory, with paths relative to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading a directory or file fails.
+    pub fn manifest(
+        &self,
+        root: impl AsRef<Path>,
+        formatter: &Formatter,
+    ) -> io::Result<String> {
+        Ok(self
+            .walk(root)?
+            .iter()
+            .map(|entry| {
+                formatter.line(self.digest, &entry.output, entry.name())
+            })
+            .collect())
+    }
+
+    /// Returns one digest of the whole tree below the root directory, like
+    /// the `dirhash` of Go modules.
+    ///
+    /// The digest is computed with the digest algorithm of this walker over
+    /// a canonical encoding of the sorted entries of [`Walker::walk`], i.e.
+    /// the concatenation of one line per file:
+    ///
+    /// ```text
+    /// <hex digest of the content> <size in bytes> <name>\n
+    /// ```
+    ///
+    /// Recorded symbolic links are encoded as `<hex digest of the target
+    /// path> link <name>\n`.
+    ///
+    /// Empty directories don't change the digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading a directory or file fails, or with
+    /// [`io::ErrorKind::InvalidData`] if a file name contains a newline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::walk::Walker;
+    /// use clap_digest::Digest;
+    ///
+    /// let walker = Walker::new(Digest::SHA256);
+    ///
+    /// assert_eq!(walker.dirhash("src")?, walker.dirhash("./src/")?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn dirhash(&self, root: impl AsRef<Path>) -> io::Result<Output> {
+        let mut hasher = Hasher::new(self.digest);
+
+        for entry in self.walk(root)? {
+            let name = entry.name();
+
+            if name.contains('\n') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("file name contains newline: {name:?}"),
+                ));
+            }
+
+            let line = if entry.symlink {
+                format!("{} link {name}\n", entry.output)
+            } else {
+                format!("{} {} {name}\n", entry.output, entry.size)
+            };
+
+            hasher.update(line.as_bytes());
+        }
+
+        Ok(hasher.finalize_output())
+    }
+
+    /// Hashes the files of the directory, recursively.
+    ///
+    /// The ancestors are the canonical paths of the directories being
+    /// walked, to detect cycles when following symbolic links.
+    fn walk_dir(
+        &self,
+        root: &Path,
+        relative: &Path,
+        ancestors: &mut Vec<PathBuf>,
+        entries: &mut Vec<Entry>,
+    ) -> io::Result<()> {
+        let dir = root.join(relative);
+
+        if self.symlinks == Symlinks::Follow {
+            let canonical = dir.canonicalize()?;
+
+            if ancestors.contains(&canonical) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("directory cycle at {}", dir.display()),
+                ));
+            }
+
+            ancestors.push(canonical);
+        }
+
+        for dir_entry in fs::read_dir(&dir)? {
+            let dir_entry = dir_entry?;
+            let path = dir_entry.path();
+            let relative = relative.join(dir_entry.file_name());
+
+            let mut file_type = dir_entry.file_type()?;
+
+            if file_type.is_symlink() {
+                match self.symlinks {
+                    Symlinks::Skip => continue,
+                    Symlinks::Follow => {
+                        file_type = fs::metadata(&path)?.file_type();
+                    }
+                    Symlinks::Record => {
+                        if !self.is_excluded(&relative, false) {
+                            entries.push(self.symlink_entry(&path, relative)?);
+                        }
+
+                        continue;
+                    }
+                }
+            }
+
+            if self.is_excluded(&relative, file_type.is_dir()) {
+                continue;
+            }
+
+            if file_type.is_dir() {
+                self.walk_dir(root, &relative, ancestors, entries)?;
+            } else if file_type.is_file() {
+                entries.push(Entry {
+                    size: fs::metadata(&path)?.len(),
+                    output: crate::hash_file(&path, self.digest)?,
+                    path: relative,
+                    symlink: false,
+                });
+            }
+        }
+
+        if self.symlinks == Symlinks::Follow {
+            ancestors.pop();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the entry of the recorded symbolic link.
+    fn symlink_entry(
+        &self,
+        path: &Path,
+        relative: PathBuf,
+    ) -> io::Result<Entry> {
+        let target = fs::read_link(path)?;
+        let target = target.to_string_lossy();
+
+        Ok(Entry {
+            path: relative,
+            size: target.len() as u64,
+            output: self.digest.hash(target.as_bytes()),
+            symlink: true,
+        })
+    }
+
+    /// Returns `true` if the relative path is excluded.
+    fn is_excluded(&self, relative: &Path, is_dir: bool) -> bool {
+        let name = name(relative);
+
+        self.excludes
+            .iter()
+            .rev()
+            .find(|pattern| pattern.matches(&name, is_dir))
+            .is_some_and(|pattern| !pattern.is_negated())
+    }
+}
+
+/// Returns the relative path with `/` as separator.
+fn name(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "md5"))]
+mod tests {
+    use super::{Entry, Symlinks, Walker};
+    use crate::format::Formatter;
+    use crate::Digest;
+
+    #[test]
+    fn walk() {
+        let dir = std::env::temp_dir().join("clap-digest-walk");
+        std::fs::create_dir_all(dir.join("a")).unwrap();
+        std::fs::create_dir_all(dir.join("empty")).unwrap();
+        std::fs::write(dir.join("a.txt"), "foo").unwrap();
+        std::fs::write(dir.join("a").join("b"), "foobar").unwrap();
+        std::fs::write(dir.join("c"), "").unwrap();
+
+        let walker = Walker::new(Digest::MD5);
+        let entries = walker.walk(&dir).unwrap();
+        let manifest = walker.manifest(&dir, &Formatter::new()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names = entries.iter().map(Entry::name).collect::<Vec<_>>();
+        assert_eq!(names, ["a.txt", "a/b", "c"]);
+
+        assert_eq!(entries[1].size(), 6);
+        assert_eq!(entries[1].output(), &Digest::MD5.hash(b"foobar"));
+        assert_eq!(entries[1].path(), std::path::Path::new("a").join("b"));
+
+        assert_eq!(
+            manifest,
+            "acbd18db4cc2f85cedef654fccc4a4d8  a.txt\n\
+             3858f62230ac3c915f300c664312c63f  a/b\n\
+             d41d8cd98f00b204e9800998ecf8427e  c\n"
+        );
+    }
+
+    #[test]
+    fn dirhash() {
+        let dir = std::env::temp_dir().join("clap-digest-dirhash");
+        std::fs::create_dir_all(dir.join("a")).unwrap();
+        std::fs::write(dir.join("a").join("b"), "foo").unwrap();
+        std::fs::write(dir.join("c"), "").unwrap();
+
+        let walker = Walker::new(Digest::MD5);
+        let hash = walker.dirhash(&dir).unwrap();
+
+        std::fs::create_dir_all(dir.join("empty")).unwrap();
+        let hash_empty_dir = walker.dirhash(&dir).unwrap();
+
+        std::fs::write(dir.join("c"), "\0").unwrap();
+        let hash_changed = walker.dirhash(&dir).unwrap();
+
+        std::fs::write(dir.join("d\ne"), "").unwrap();
+        let newline = walker.dirhash(&dir).unwrap_err();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let expected = Digest::MD5.hash(
+            b"acbd18db4cc2f85cedef654fccc4a4d8 3 a/b\n\
+              d41d8cd98f00b204e9800998ecf8427e 0 c\n",
+        );
+
+        assert_eq!(hash, expected);
+        assert_eq!(hash_empty_dir, expected);
+        assert_ne!(hash_changed, expected);
+        assert_eq!(newline.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = std::env::temp_dir().join("clap-digest-walk-symlinks");
+        std::fs::create_dir_all(dir.join("a")).unwrap();
+        std::fs::write(dir.join("a").join("b"), "foo").unwrap();
+        symlink("a", dir.join("c")).unwrap();
+        symlink("a/b", dir.join("d")).unwrap();
+
+        let names = |walker: Walker| {
+            walker
+                .walk(&dir)
+                .map(|entries| {
+                    entries.iter().map(Entry::name).collect::<Vec<_>>()
+                })
+                .map_err(|e| e.kind())
+        };
+
+        let skip = names(Walker::new(Digest::MD5));
+        let follow =
+            names(Walker::new(Digest::MD5).symlinks(Symlinks::Follow));
+
+        let walker = Walker::new(Digest::MD5).symlinks(Symlinks::Record);
+        let record = walker.walk(&dir).unwrap();
+
+        symlink(".", dir.join("a").join("e")).unwrap();
+        let cycle = names(Walker::new(Digest::MD5).symlinks(Symlinks::Follow));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(skip.unwrap(), ["a/b"]);
+        assert_eq!(follow.unwrap(), ["a/b", "c/b", "d"]);
+        assert_eq!(cycle.unwrap_err(), std::io::ErrorKind::Other);
+
+        assert_eq!(record.len(), 3);
+        assert!(record[1].is_symlink());
+        assert_eq!(record[1].name(), "c");
+        assert_eq!(record[1].output(), &Digest::MD5.hash(b"a"));
+        assert_eq!(record[2].size(), 3);
+    }
+
+    #[test]
+    fn exclude() {
+        let dir = std::env::temp_dir().join("clap-digest-walk-exclude");
+        std::fs::create_dir_all(dir.join("target").join("debug")).unwrap();
+        std::fs::create_dir_all(dir.join("src").join("target")).unwrap();
+        std::fs::write(dir.join("target").join("debug").join("a"), "")
+            .unwrap();
+        std::fs::write(dir.join("src").join("target").join("b"), "").unwrap();
+        std::fs::write(dir.join("src").join("lib.rs"), "").unwrap();
+        std::fs::write(dir.join("src").join("main.rs"), "").unwrap();
+        std::fs::write(dir.join("Cargo.lock"), "").unwrap();
+
+        let names = |walker: Walker| {
+            walker
+                .walk(&dir)
+                .unwrap()
+                .iter()
+                .map(Entry::name)
+                .collect::<Vec<_>>()
+        };
+
+        let anchored = names(Walker::new(Digest::MD5).exclude("/target/"));
+        let anywhere = names(
+            Walker::new(Digest::MD5)
+                .exclude("target/")
+                .exclude("*.rs")
+                .exclude("!main.rs"),
+        );
+        let all = names(Walker::new(Digest::MD5).exclude("**"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            anchored,
+            ["Cargo.lock", "src/lib.rs", "src/main.rs", "src/target/b"]
+        );
+        assert_eq!(anywhere, ["Cargo.lock", "src/main.rs"]);
+        assert!(all.is_empty());
+    }
+}
@@ -0,0 +1,605 @@
+//! Contains extendable-output functions ([`Xof`]).
+//!
+//! Unlike a [`Digest`](crate::Digest), an extendable-output function has no
+//! fixed output size: any number of bytes can be read from it. Use
+//! [`arg::output_length`](crate::arg::output_length) to let users choose it.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap_digest::xof::Xof;
+//!
+//! let output = Xof::SHAKE128.hash(b"abc", 5);
+//!
+//! assert_eq!(output.to_string(), "5881092dd8");
+//! ```
+
+#[cfg(feature = "k12")]
+use std::collections::BTreeSet;
+use std::fmt;
+#[cfg(feature = "k12")]
+use std::sync::{Mutex, PoisonError};
+
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use digest::{ExtendableOutput, Reset, Update, XofReader};
+
+use crate::Output;
+
+/// Applies an expression to the concrete hasher of every variant.
+macro_rules! dispatch {
+    ($self:expr, $hasher:ident => $expr:expr) => {
+        match $self {
+            #[cfg(feature = "ascon")]
+            XofHasher::AsconXof($hasher) => $expr,
+            #[cfg(feature = "blake3")]
+            XofHasher::BLAKE3($hasher) => $expr,
+            #[cfg(feature = "sha3")]
+            XofHasher::CSHAKE128($hasher) => $expr,
+            #[cfg(feature = "sha3")]
+            XofHasher::CSHAKE256($hasher) => $expr,
+            #[cfg(feature = "k12")]
+            XofHasher::K12($hasher) => $expr,
+            #[cfg(feature = "sha3")]
+            XofHasher::SHAKE128($hasher) => $expr,
+            #[cfg(feature = "sha3")]
+            XofHasher::SHAKE256($hasher) => $expr,
+        }
+    };
+}
+
+/// Supported extendable-output functions.
+#[allow(missing_docs)] // no docs for the variants
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Xof {
+    #[cfg(feature = "ascon")]
+    AsconXof,
+
+    #[cfg(feature = "blake3")]
+    BLAKE3,
+
+    #[cfg(feature = "sha3")]
+    CSHAKE128,
+
+    #[cfg(feature = "sha3")]
+    CSHAKE256,
+
+    #[cfg(feature = "k12")]
+    K12,
+
+    #[cfg(feature = "sha3")]
+    SHAKE128,
+
+    #[cfg(feature = "sha3")]
+    SHAKE256,
+}
+
+impl Xof {
+    /// Returns the name of the function.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "ascon")]
+            Self::AsconXof => "Ascon-XOF",
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3 => "BLAKE3",
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE128 => "CSHAKE128",
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE256 => "CSHAKE256",
+            #[cfg(feature = "k12")]
+            Self::K12 => "K12",
+            #[cfg(feature = "sha3")]
+            Self::SHAKE128 => "SHAKE128",
+            #[cfg(feature = "sha3")]
+            Self::SHAKE256 => "SHAKE256",
+        }
+    }
+
+    /// Returns a short human-readable description of the function.
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "ascon")]
+            Self::AsconXof => "Ascon-XOF with 128 bit security",
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3 => "BLAKE3 with extendable output",
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE128 => "customizable SHAKE with 128 bit security",
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE256 => "customizable SHAKE with 256 bit security",
+            #[cfg(feature = "k12")]
+            Self::K12 => "KangarooTwelve with 128 bit security",
+            #[cfg(feature = "sha3")]
+            Self::SHAKE128 => "SHAKE with 128 bit security",
+            #[cfg(feature = "sha3")]
+            Self::SHAKE256 => "SHAKE with 256 bit security",
+        }
+    }
+
+    /// Returns the output length in bytes used when none is chosen, which
+    /// gives the full security strength of the function.
+    #[must_use]
+    pub const fn default_output_len(&self) -> usize {
+        match self {
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE128 | Self::SHAKE128 => 32,
+            #[cfg(feature = "ascon")]
+            Self::AsconXof => 32,
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3 => 32,
+            #[cfg(feature = "k12")]
+            Self::K12 => 32,
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE256 | Self::SHAKE256 => 64,
+        }
+    }
+
+    /// Returns `true` if the function takes a customization string, see
+    /// [`XofHasher::new_customized`].
+    #[must_use]
+    pub const fn is_customizable(&self) -> bool {
+        match self {
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE128 | Self::CSHAKE256 => true,
+            #[cfg(feature = "k12")]
+            Self::K12 => true,
+            #[cfg(feature = "sha3")]
+            Self::SHAKE128 | Self::SHAKE256 => false,
+            #[cfg(feature = "ascon")]
+            Self::AsconXof => false,
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3 => false,
+        }
+    }
+
+    /// Returns a new hasher of the function.
+    #[must_use]
+    pub fn new_hasher(self) -> XofHasher {
+        XofHasher::new(self)
+    }
+
+    /// Returns the first `len` output bytes of the data.
+    #[must_use]
+    pub fn hash(self, data: &[u8], len: usize) -> Output {
+        let mut hasher = self.new_hasher();
+        hasher.update(data);
+        hasher.finalize_output(len)
+    }
+
+    /// Returns a reader of the unbounded output of the data.
+    #[must_use]
+    pub fn reader(self, data: &[u8]) -> Box<dyn XofReader> {
+        let mut hasher = self.new_hasher();
+        hasher.update(data);
+        hasher.finalize_boxed()
+    }
+}
+
+impl fmt::Display for Xof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl ValueEnum for Xof {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            #[cfg(feature = "ascon")]
+            Self::AsconXof,
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3,
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE128,
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE256,
+            #[cfg(feature = "k12")]
+            Self::K12,
+            #[cfg(feature = "sha3")]
+            Self::SHAKE128,
+            #[cfg(feature = "sha3")]
+            Self::SHAKE256,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
+
+/// Incremental hasher of an [`Xof`].
+///
+/// # Examples
+///
+/// ```
+/// use clap_digest::xof::Xof;
+/// use digest::Update;
+///
+/// let mut hasher = Xof::SHAKE256.new_hasher();
+/// hasher.update(b"a");
+/// hasher.update(b"bc");
+///
+/// assert_eq!(hasher.finalize_output(64), Xof::SHAKE256.hash(b"abc", 64));
+/// ```
+#[allow(missing_docs)] // no docs for the variants
+#[allow(clippy::large_enum_variant)] // not boxing is the whole point
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum XofHasher {
+    #[cfg(feature = "ascon")]
+    AsconXof(ascon_hash::AsconXof),
+
+    #[cfg(feature = "blake3")]
+    BLAKE3(blake3::Hasher),
+
+    #[cfg(feature = "sha3")]
+    CSHAKE128(sha3::CShake128),
+
+    #[cfg(feature = "sha3")]
+    CSHAKE256(sha3::CShake256),
+
+    #[cfg(feature = "k12")]
+    K12(k12::KangarooTwelve<'static>),
+
+    #[cfg(feature = "sha3")]
+    SHAKE128(sha3::Shake128),
+
+    #[cfg(feature = "sha3")]
+    SHAKE256(sha3::Shake256),
+}
+
+impl XofHasher {
+    /// Returns a new hasher of the function.
+    #[must_use]
+    pub fn new(xof: Xof) -> Self {
+        match xof {
+            #[cfg(feature = "ascon")]
+            Xof::AsconXof => Self::AsconXof(ascon_hash::AsconXof::default()),
+            #[cfg(feature = "blake3")]
+            Xof::BLAKE3 => Self::BLAKE3(blake3::Hasher::new()),
+            #[cfg(feature = "sha3")]
+            Xof::CSHAKE128 => Self::CSHAKE128(sha3::CShake128::from_core(
+                sha3::CShake128Core::new(&[]),
+            )),
+            #[cfg(feature = "sha3")]
+            Xof::CSHAKE256 => Self::CSHAKE256(sha3::CShake256::from_core(
+                sha3::CShake256Core::new(&[]),
+            )),
+            #[cfg(feature = "k12")]
+            Xof::K12 => Self::K12(k12::KangarooTwelve::from_core(
+                k12::KangarooTwelveCore::new(&[]),
+            )),
+            #[cfg(feature = "sha3")]
+            Xof::SHAKE128 => Self::SHAKE128(sha3::Shake128::default()),
+            #[cfg(feature = "sha3")]
+            Xof::SHAKE256 => Self::SHAKE256(sha3::Shake256::default()),
+        }
+    }
+
+    /// Returns a new hasher of the function with the customization string,
+    /// for domain separation.
+    ///
+    /// This is [`None`] if the customization is not empty and the function
+    /// doesn't take one, see [`Xof::is_customizable`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap_digest::xof::{Xof, XofHasher};
+    ///
+    /// let hasher = XofHasher::new_customized(Xof::CSHAKE128, b"Email");
+    /// assert!(hasher.is_some());
+    ///
+    /// let hasher = XofHasher::new_customized(Xof::SHAKE128, b"Email");
+    /// assert!(hasher.is_none());
+    /// ```
+    #[must_use]
+    pub fn new_customized(xof: Xof, customization: &[u8]) -> Option<Self> {
+        match xof {
+            #[cfg(feature = "sha3")]
+            Xof::CSHAKE128 => {
+                Some(Self::CSHAKE128(sha3::CShake128::from_core(
+                    sha3::CShake128Core::new(customization),
+                )))
+            }
+            #[cfg(feature = "sha3")]
+            Xof::CSHAKE256 => {
+                Some(Self::CSHAKE256(sha3::CShake256::from_core(
+                    sha3::CShake256Core::new(customization),
+                )))
+            }
+            #[cfg(feature = "k12")]
+            Xof::K12 => Some(Self::K12(k12::KangarooTwelve::from_core(
+                k12::KangarooTwelveCore::new(intern(customization)),
+            ))),
+            #[allow(unreachable_patterns)]
+            _ => customization.is_empty().then(|| Self::new(xof)),
+        }
+    }
+
+    /// Returns the function of this hasher.
+    #[must_use]
+    pub const fn xof(&self) -> Xof {
+        match self {
+            #[cfg(feature = "ascon")]
+            Self::AsconXof(_) => Xof::AsconXof,
+            #[cfg(feature = "blake3")]
+            Self::BLAKE3(_) => Xof::BLAKE3,
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE128(_) => Xof::CSHAKE128,
+            #[cfg(feature = "sha3")]
+            Self::CSHAKE256(_) => Xof::CSHAKE256,
+            #[cfg(feature = "k12")]
+            Self::K12(_) => Xof::K12,
+            #[cfg(feature = "sha3")]
+            Self::SHAKE128(_) => Xof::SHAKE128,
+            #[cfg(feature = "sha3")]
+            Self::SHAKE256(_) => Xof::SHAKE256,
+        }
+    }
+
+    /// Returns a reader of the unbounded output, consuming the hasher.
+    #[must_use]
+    pub fn finalize_boxed(self) -> Box<dyn XofReader> {
+        dispatch!(self, hasher => Box::new(hasher.finalize_xof()))
+    }
+
+    /// Returns the first `len` output bytes, consuming the hasher.
+    #[must_use]
+    pub fn finalize_output(self, len: usize) -> Output {
+        let mut output = vec![0; len];
+        self.finalize_boxed().read(&mut output);
+        output.into()
+    }
+}
+
+impl From<Xof> for XofHasher {
+    fn from(xof: Xof) -> Self {
+        Self::new(xof)
+    }
+}
+
+impl From<Xof> for Box<dyn XofReader> {
+    fn from(xof: Xof) -> Self {
+        xof.new_hasher().finalize_boxed()
+    }
+}
+
+impl fmt::Debug for XofHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("XofHasher").field(&self.xof()).finish()
+    }
+}
+
+impl Update for XofHasher {
+    fn update(&mut self, data: &[u8]) {
+        dispatch!(self, hasher => Update::update(hasher, data));
+    }
+}
+
+impl Reset for XofHasher {
+    fn reset(&mut self) {
+        dispatch!(self, hasher => Reset::reset(hasher));
+    }
+}
+
+/// Returns the customization string with the static lifetime the `k12`
+/// hasher borrows it for.
+///
+/// Every distinct string is leaked once and reused afterwards, so the memory
+/// is bounded by the customization strings actually used.
+#[cfg(feature = "k12")]
+fn intern(customization: &[u8]) -> &'static [u8] {
+    static INTERNED: Mutex<BTreeSet<&'static [u8]>> =
+        Mutex::new(BTreeSet::new());
+
+    if customization.is_empty() {
+        return &[];
+    }
+
+    let mut interned = INTERNED.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(customization) = interned.get(customization) {
+        return customization;
+    }
+
+    let customization = Box::leak(customization.into());
+    interned.insert(customization);
+    customization
+}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+    #[cfg(any(feature = "k12", feature = "sha3"))]
+    use digest::{Reset, Update};
+
+    use super::Xof;
+    #[cfg(any(feature = "k12", feature = "sha3"))]
+    use super::XofHasher;
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn hash() {
+        let cases = [
+            (
+                Xof::SHAKE128,
+                &b""[..],
+                "7f9c2ba4e88f827d616045507605853e\
+                 d73b8093f6efbc88eb1a6eacfa66ef26",
+            ),
+            (
+                Xof::SHAKE128,
+                b"abc",
+                "5881092dd818bf5cf8a3ddb793fbcba7\
+                 4097d5c526a6d35f97b83351940f2cc8",
+            ),
+            (
+                Xof::SHAKE256,
+                b"abc",
+                "483366601360a8771c6863080cc4114d\
+                 8db44530f8f1e1ee4f94ea37e78b5739\
+                 d5a15bef186a5386c75744c0527e1faa\
+                 9f8726e462a12a4feb06bd8801e751e4",
+            ),
+        ];
+
+        for (xof, data, expected) in cases {
+            let len = xof.default_output_len();
+
+            assert_eq!(xof.hash(data, len).to_string(), expected);
+        }
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn customized() {
+        let data = [0, 1, 2, 3];
+        let cases = [
+            (
+                Xof::CSHAKE128,
+                "c1c36925b6409a04f1b504fcbca9d82b\
+                 4017277cb5ed2b2065fc1d3814d5aaf5",
+            ),
+            (
+                Xof::CSHAKE256,
+                "d008828e2b80ac9d2218ffee1d070c48\
+                 b8e4c87bff32c9699d5b6896eee0edd1\
+                 64020e2be0560858d9c00c037e34a969\
+                 37c561a74c412bb4c746469527281c8c",
+            ),
+        ];
+
+        for (xof, expected) in cases {
+            let mut hasher =
+                XofHasher::new_customized(xof, b"Email Signature").unwrap();
+            hasher.update(&data);
+            let mut fork = hasher.clone();
+            fork.reset();
+            fork.update(&data);
+
+            let len = xof.default_output_len();
+            assert_eq!(hasher.finalize_output(len).to_string(), expected);
+            assert_eq!(fork.finalize_output(len).to_string(), expected);
+        }
+
+        assert_eq!(
+            Xof::CSHAKE128.hash(b"abc", 32),
+            Xof::SHAKE128.hash(b"abc", 32)
+        );
+        assert!(XofHasher::new_customized(Xof::SHAKE256, b"").is_some());
+        assert!(XofHasher::new_customized(Xof::SHAKE256, b"x").is_none());
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3() {
+        let output = Xof::BLAKE3.hash(b"", 64);
+
+        assert_eq!(
+            output.to_string(),
+            "af1349b9f5f9a1a6a0404dea36dcc949\
+             9bcb25c9adc112b7cc9a93cae41f3262\
+             e00f03e7b69af26b7faaf09fcd333050\
+             338ddfe085b8cc869ca98b206c08243a"
+        );
+        assert_eq!(
+            Xof::BLAKE3.hash(b"abc", 32),
+            crate::Digest::BLAKE3.hash(b"abc")
+        );
+        assert_eq!(
+            Xof::BLAKE3.hash(b"", 16).as_bytes(),
+            &output.as_bytes()[..16]
+        );
+    }
+
+    #[cfg(feature = "ascon")]
+    #[test]
+    fn ascon() {
+        let output = Xof::AsconXof.hash(b"", 32);
+
+        assert_eq!(
+            output.to_string(),
+            "5d4cbde6350ea4c174bd65b5b332f840\
+             8f99740b81aa02735eaefbcf0ba0339e"
+        );
+        assert_eq!(
+            Xof::AsconXof.hash(b"", 16).as_bytes(),
+            &output.as_bytes()[..16]
+        );
+        assert_ne!(
+            Xof::AsconXof.hash(b"abc", 32),
+            crate::Digest::AsconHash.hash(b"abc")
+        );
+    }
+
+    #[cfg(feature = "k12")]
+    #[test]
+    fn k12() {
+        // test vectors from RFC 9861, section 5, with ptn(n) as defined there
+        let ptn = |n| (0..0xfb).cycle().take(n).collect::<Vec<u8>>();
+
+        assert_eq!(
+            Xof::K12.hash(b"", 32).to_string(),
+            "1ac2d450fc3b4205d19da7bfca1b3751\
+             3c0803577ac7167f06fe2ce1f0ef39e5"
+        );
+        assert_eq!(
+            Xof::K12.hash(&ptn(17 * 17 * 17 * 17), 32).to_string(),
+            "8701045e22205345ff4dda05555cbb5c\
+             3af1a771c2b89baef37db43d9998b9fe"
+        );
+
+        let mut hasher =
+            XofHasher::new_customized(Xof::K12, &ptn(41)).unwrap();
+        hasher.update(b"\xff");
+        let mut fork = hasher.clone();
+        fork.reset();
+        fork.update(b"\xff");
+
+        let expected = "d848c5068ced736f4462159b9867fd4c\
+                        20b808acc3d5bc48e0b06ba0a3762ec4";
+        assert_eq!(hasher.finalize_output(32).to_string(), expected);
+        assert_eq!(fork.finalize_output(32).to_string(), expected);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn reader() {
+        let mut reader = Xof::SHAKE128.reader(b"abc");
+        let mut head = [0; 5];
+        let mut tail = [0; 27];
+        reader.read(&mut head);
+        reader.read(&mut tail);
+
+        let output = Xof::SHAKE128.hash(b"abc", 32);
+
+        assert_eq!(head, output.as_bytes()[..5]);
+        assert_eq!(tail, output.as_bytes()[5..]);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn reset() {
+        let mut hasher = Xof::SHAKE256.new_hasher();
+        hasher.update(b"foo");
+        hasher.reset();
+        hasher.update(b"abc");
+
+        assert_eq!(hasher.xof(), Xof::SHAKE256);
+        assert_eq!(hasher.finalize_output(16), Xof::SHAKE256.hash(b"abc", 16));
+    }
+
+    #[test]
+    fn value_enum() {
+        for xof in Xof::value_variants() {
+            assert_eq!(Xof::from_str(xof.name(), false), Ok(*xof));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Contains scrubbing the hashing state from memory via [`zeroize`].
+
+use std::mem::size_of;
+use std::ptr;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+use ::zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::io::{HashingReader, HashingWriter};
+use crate::{Hasher, MultiDigest};
+
+impl Zeroize for Hasher {
+    /// Overwrites the hashing state with zeros and resets the hasher.
+    ///
+    /// The hasher can be used again afterwards, for the same digest
+    /// algorithm.
+    fn zeroize(&mut self) {
+        let digest = self.digest();
+        let bytes = (self as *mut Self).cast::<u8>();
+
+        for i in 0..size_of::<Self>() {
+            // SAFETY: the pointer is valid for writes of the whole hasher
+            unsafe { ptr::write_volatile(bytes.add(i), 0) };
+        }
+
+        compiler_fence(Ordering::SeqCst);
+
+        // SAFETY: the zeroed hasher is replaced without being read or
+        // dropped. No hasher owns heap memory, so nothing leaks.
+        unsafe { ptr::write(self, Self::new(digest)) };
+    }
+}
+
+impl Drop for Hasher {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Hasher {}
+
+impl Zeroize for MultiDigest {
+    /// Overwrites the hashing states with zeros and resets the hashers.
+    fn zeroize(&mut self) {
+        self.hashers.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl ZeroizeOnDrop for MultiDigest {}
+
+impl<R> ZeroizeOnDrop for HashingReader<R> {}
+
+impl<W> ZeroizeOnDrop for HashingWriter<W> {}
+
+// ----------------------------------------------------------------------------
+// tests
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+    use digest::Update;
+    use zeroize::Zeroize;
+
+    #[cfg(all(feature = "md5", feature = "sha1"))]
+    use crate::MultiDigest;
+    use crate::{Digest, Hasher};
+
+    #[test]
+    fn hasher() {
+        for &digest in Digest::value_variants() {
+            let mut hasher = Hasher::new(digest);
+            hasher.update(b"secret");
+            hasher.zeroize();
+
+            assert_eq!(hasher.digest(), digest);
+            assert_eq!(hasher.finalize_output(), digest.hash(b""));
+        }
+    }
+
+    #[cfg(all(feature = "md5", feature = "sha1"))]
+    #[test]
+    fn multi_digest() {
+        let mut multi = MultiDigest::new(&[Digest::MD5, Digest::SHA1]);
+        multi.update(b"secret");
+        multi.zeroize();
+
+        let outputs = multi.finalize();
+
+        assert_eq!(outputs[0], (Digest::MD5, Digest::MD5.hash(b"")));
+        assert_eq!(outputs[1], (Digest::SHA1, Digest::SHA1.hash(b"")));
+    }
+}